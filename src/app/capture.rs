@@ -0,0 +1,86 @@
+//! LCD 画面录制
+//!
+//! 录制期间记录每次内容变化的帧及其时间, 停止后在后台线程编码为循环播放的 GIF
+
+use crate::robot::lcd::{LCD_HEIGHT, LCD_WIDTH};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// 最多录制的帧数, 每帧约 170KB, 超过后自动停止
+pub const MAX_CAPTURE_FRAMES: usize = 300;
+
+/// 画面录制器
+pub struct FrameCapture {
+    start: Instant,
+    last_hash: Option<u64>,
+    /// (相对开始的时间, RGB 像素)
+    frames: Vec<(Duration, Vec<u8>)>,
+}
+
+impl FrameCapture {
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            last_hash: None,
+            frames: Vec::new(),
+        }
+    }
+
+    /// 记录一帧, 内容与上一帧相同时忽略; 返回是否还能继续录制
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - 240x240 RGB 帧数据
+    /// * `hash` - 帧数据哈希值
+    pub fn push(&mut self, pixels: &[u8], hash: u64) -> bool {
+        if self.frames.len() >= MAX_CAPTURE_FRAMES {
+            return false;
+        }
+        if self.last_hash != Some(hash) {
+            self.last_hash = Some(hash);
+            self.frames.push((self.start.elapsed(), pixels.to_vec()));
+        }
+        self.frames.len() < MAX_CAPTURE_FRAMES
+    }
+
+    /// 已录制的帧数
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// 是否没有录到任何帧
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// 结束录制, 在后台线程编码为 GIF 并写入 `path`
+    ///
+    /// 每帧的显示时长为到下一帧的间隔, 最后一帧显示到停止录制的时间
+    pub fn finish(self, path: PathBuf) -> JoinHandle<anyhow::Result<PathBuf>> {
+        let end = self.start.elapsed();
+        thread::spawn(move || {
+            let file = BufWriter::new(File::create(&path)?);
+            let mut encoder = GifEncoder::new(file);
+            encoder.set_repeat(Repeat::Infinite)?;
+
+            let times: Vec<Duration> = self.frames.iter().map(|(t, _)| *t).collect();
+            for (i, (time, pixels)) in self.frames.into_iter().enumerate() {
+                let next = times.get(i + 1).copied().unwrap_or(end);
+                let delay = Delay::from_saturating_duration(next.saturating_sub(time));
+                let rgba: Vec<u8> = pixels
+                    .chunks_exact(3)
+                    .flat_map(|p| [p[0], p[1], p[2], 255])
+                    .collect();
+                let image = RgbaImage::from_raw(LCD_WIDTH as u32, LCD_HEIGHT as u32, rgba)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid frame size"))?;
+                encoder.encode_frame(Frame::from_parts(image, 0, 0, delay))?;
+            }
+            Ok(path)
+        })
+    }
+}
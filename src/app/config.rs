@@ -1,13 +1,152 @@
+use super::MenuItem;
+use crate::robot::{
+    self, PixelFormat, QueuePolicy, TransportConfig, TransportKind, UsbIds, SERVO_COUNT,
+};
+use crate::voice::{VoiceConfig, DEFAULT_MODEL_PATH};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 /// 应用配置
+///
+/// 缺失的字段使用默认值填充, 兼容旧版本配置文件
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct AppConfig {
     pub speech_name: String,
     pub wifi_ssid: String,
     pub wifi_password: String,
+    /// 唤醒词列表, 为空时关闭语音唤醒
+    pub wake_words: Vec<String>,
+    /// 唤醒后的语音应答, 为空时不播报
+    pub greeting: String,
+    /// 语音活动检测阈值 (i16 RMS), 0 表示关闭检测
+    pub vad_threshold: f32,
+    /// 静音持续多少个 100ms 分块后结束本段识别
+    pub vad_hangover: u32,
+    /// 按键通话模式, 开启后需按 Ctrl+T 切换监听
+    pub push_to_talk: bool,
+    /// 识别结果的最低平均置信度 (0.0 ~ 1.0)
+    pub min_confidence: f32,
+    /// 语音命令表, 短语 -> 动作 (`mood:<表情>` 或 `look:<方向>`)
+    pub voice_commands: BTreeMap<String, String>,
+    /// Vosk 模型路径
+    pub model_path: String,
+    /// 通信方式: usb、cdc 或 sim (模拟设备, 不访问硬件)
+    pub transport: TransportKind,
+    /// CDC 串口名, 为空时按 VID/PID 自动查找
+    pub cdc_port: String,
+    /// CDC 串口波特率, USB CDC 固件忽略该值, 接 USB 转串口芯片时需与固件一致
+    pub baud_rate: u32,
+    /// 发送给设备的像素格式: rgb888 或 rgb565, 后者只支持 cdc 和 sim, 见 [PixelFormat]
+    pub pixel_format: PixelFormat,
+    /// 设备 USB 厂商 ID (十六进制, 如 "0x1001")
+    pub vid: String,
+    /// 设备 USB 产品 ID (十六进制, 如 "0x8023")
+    pub pid: String,
+    /// 舵机插值速度 (度/帧), 0 表示直接跳到目标角度
+    pub servo_slew_rate: f32,
+    /// 按住左右方向键时的加速曲线, 每项为 [按住毫秒数, 每次步进角度], 按时间升序,
+    /// 如 `[[0, 1], [500, 5], [1500, 10]]`; 为空时每次固定 1°
+    pub servo_key_acceleration: Vec<[u32; 2]>,
+    /// 各舵机零点校准偏移 (度), 发送前加到目标角度上
+    pub calibration: [f32; SERVO_COUNT],
+    /// 零位姿势, 设备控制页按 [0] 回到该姿势
+    pub home_pose: [i16; SERVO_COUNT],
+    /// 保存的舵机姿势, 名称 -> 各舵机角度
+    pub poses: BTreeMap<String, [i16; SERVO_COUNT]>,
+    /// 发送帧间隔 (ms), 与界面刷新无关
+    pub frame_interval_ms: u64,
+    /// 待发送帧队列深度, 1 表示只保留最新一帧
+    pub frame_queue_depth: usize,
+    /// 帧队列满时的处理方式: drop-oldest、drop-newest 或 block (阻塞界面直到有空位)
+    pub frame_queue_policy: QueuePolicy,
+    /// 插入设备后自动连接
+    pub auto_connect: bool,
+    /// 启动时自动连接, 设备未插入时在后台按退避时间重试, 命令行 `--no-connect` 可关闭
+    pub connect_on_startup: bool,
+    /// 无操作超过该秒数后舵机掉电并暂停发送, 按任意键恢复, 0 表示不启用
+    pub idle_timeout_secs: u64,
+    /// 无操作时在当前姿势上让头部和身体缓慢摆动, 进入舵机控制后立即停止
+    pub idle_motion: bool,
+    /// 无操作多少秒后开始摆动
+    pub idle_motion_delay_secs: u64,
+    /// 摆动幅度 (度)
+    pub idle_motion_amplitude: f32,
+    /// 摆动频率 (Hz)
+    pub idle_motion_frequency: f32,
+    /// 热插拔轮询间隔 (ms), 平台不支持热插拔回调时使用
+    pub hotplug_poll_ms: u64,
+    /// 屏幕预览使用真彩色, 终端不支持时关闭, 改用 ASCII 灰度显示
+    pub preview_color: bool,
+    /// 上次在界面选择的表情, Ctrl+S 或退出时保存, 启动时恢复; 语音和远程命令的修改不保存
+    pub eyes_mood: String,
+    /// 上次在界面选择的注视方向, 与表情相同
+    pub eyes_position: String,
+    /// 上次选中的菜单页, Ctrl+S 或退出时保存, 启动时恢复
+    pub last_menu: MenuItem,
+    /// 上次的舵机目标角度, Ctrl+S 或退出时保存, 启动时恢复, 超出范围时截断
+    pub last_servo_values: [i16; SERVO_COUNT],
+    /// 启动画面图片路径, 为空时不显示
+    pub splash_image: String,
+    /// 启动画面显示时长 (秒), 之后切回眼睛
+    pub splash_seconds: u64,
+    /// 幻灯片图片目录
+    pub slideshow_dir: String,
+    /// 幻灯片切换间隔 (秒)
+    pub slideshow_interval_secs: u64,
+    /// 截图保存目录, 不存在时自动创建
+    pub screenshot_dir: String,
+    /// 截图格式: png, bmp 或 jpg
+    pub screenshot_format: ScreenshotFormat,
+    /// 切换显示模式的淡入淡出帧数, 0 表示直接切换
+    pub transition_frames: u32,
+    /// 眼睛颜色 (R, G, B), 白色为原始灰度
+    pub eye_tint: [u8; 3],
+    /// 屏幕亮度倍数, 发送前按查找表调整每个通道, 1.0 为原样
+    pub brightness: f32,
+    /// 屏幕伽马, 大于 1 时提亮暗部, 1.0 为原样
+    pub gamma: f32,
+    /// 屏幕画面水平翻转
+    pub flip_h: bool,
+    /// 屏幕画面垂直翻转
+    pub flip_v: bool,
+    /// 屏幕画面顺时针旋转角度: 0, 90, 180 或 270, 在翻转之前进行
+    pub rotate: u16,
+    /// 时钟模式的文字颜色 (R, G, B)
+    pub clock_color: [u8; 3],
+    /// 已连接设备时退出前弹窗确认
+    pub confirm_quit: bool,
+    /// 界面配色: dark, light 或 high-contrast
+    pub theme: ThemeName,
+    /// 在菜单、设置和设备控制页用 h/j/k/l 代替方向键, 被占用的 k/l 功能改用 K/L
+    pub vim_keys: bool,
+    /// HTTP 控制接口监听地址, 为空时不启动; 需要启用 http-api 特性编译, 修改后重启生效
+    pub http_api_addr: String,
+    /// 浏览器预览服务监听地址, 为空时不启动; 需要启用 ws-preview 特性编译, 修改后重启生效
+    pub preview_server_addr: String,
+    /// 浏览器预览最多同时连接的客户端数
+    pub preview_max_clients: usize,
+    /// OSC 监听的 UDP 地址, 为空时不启动; 需要启用 osc 特性编译, 修改后重启生效
+    pub osc_bind_addr: String,
+    /// OSC 地址前缀
+    pub osc_address_prefix: String,
+    /// 遥测输出目标: `tcp://主机:端口` 或文件路径, 为空时不输出; 修改后重启生效
+    pub telemetry_sink: String,
+    /// 遥测输出间隔 (ms)
+    pub telemetry_interval_ms: u64,
+    /// MQTT 服务器地址, 如 "mqtt://192.168.1.2:1883", 为空时不连接; 需要启用 mqtt 特性编译, 修改后重启生效
+    pub mqtt_broker: String,
+    /// MQTT 主题前缀, 订阅 `<前缀>/command`, 发布 `<前缀>/status`
+    pub mqtt_topic_prefix: String,
+    /// 配置档案名称, 不写入配置文件
+    #[serde(skip)]
+    pub profile: String,
+    /// 配置文件路径, 不写入配置文件
+    #[serde(skip)]
+    path: PathBuf,
 }
 
 impl Default for AppConfig {
@@ -16,48 +155,400 @@ impl Default for AppConfig {
             speech_name: "麦克风阵列".to_string(),
             wifi_ssid: "".to_string(),
             wifi_password: "".to_string(),
+            wake_words: ["小波", "晓波", "小博", "笑波", "晓博"]
+                .iter()
+                .map(|w| w.to_string())
+                .collect(),
+            greeting: "我在".to_string(),
+            vad_threshold: 500.0,
+            vad_hangover: 8,
+            push_to_talk: false,
+            min_confidence: 0.5,
+            voice_commands: default_voice_commands(),
+            model_path: DEFAULT_MODEL_PATH.to_string(),
+            transport: TransportKind::Usb,
+            cdc_port: String::new(),
+            baud_rate: robot::cdc::DEFAULT_BAUD_RATE,
+            pixel_format: PixelFormat::Rgb888,
+            vid: format!("{:#06x}", robot::DEVICE_VID),
+            pid: format!("{:#06x}", robot::DEVICE_PID),
+            servo_slew_rate: 2.0,
+            servo_key_acceleration: Vec::new(),
+            calibration: [0.0; SERVO_COUNT],
+            home_pose: [0; SERVO_COUNT],
+            poses: BTreeMap::from([("rest".to_string(), [0; SERVO_COUNT])]),
+            frame_interval_ms: 20,
+            frame_queue_depth: 1,
+            frame_queue_policy: QueuePolicy::DropOldest,
+            auto_connect: true,
+            connect_on_startup: false,
+            idle_timeout_secs: 0,
+            idle_motion: false,
+            idle_motion_delay_secs: 10,
+            idle_motion_amplitude: 3.0,
+            idle_motion_frequency: 0.2,
+            hotplug_poll_ms: 1000,
+            preview_color: true,
+            eyes_mood: "default".to_string(),
+            eyes_position: "center".to_string(),
+            last_menu: MenuItem::DeviceStatus,
+            last_servo_values: [0; SERVO_COUNT],
+            splash_image: String::new(),
+            splash_seconds: 3,
+            slideshow_dir: "./assets/images".to_string(),
+            slideshow_interval_secs: 5,
+            screenshot_dir: "./assets/images/screenshot".to_string(),
+            screenshot_format: ScreenshotFormat::Png,
+            transition_frames: 10,
+            eye_tint: [255, 255, 255],
+            brightness: 1.0,
+            gamma: 1.0,
+            flip_h: false,
+            flip_v: false,
+            rotate: 0,
+            clock_color: [0, 255, 128],
+            confirm_quit: true,
+            theme: ThemeName::Dark,
+            vim_keys: false,
+            http_api_addr: "127.0.0.1:8765".to_string(),
+            preview_server_addr: "127.0.0.1:8766".to_string(),
+            preview_max_clients: 4,
+            osc_bind_addr: "127.0.0.1:9000".to_string(),
+            osc_address_prefix: "/electronbot".to_string(),
+            telemetry_sink: String::new(),
+            telemetry_interval_ms: 1000,
+            mqtt_broker: String::new(),
+            mqtt_topic_prefix: "electronbot".to_string(),
+            profile: DEFAULT_PROFILE.to_string(),
+            path: profile_path(DEFAULT_PROFILE),
         }
     }
 }
 
+/// 配置档案目录, 每个档案保存为 `<名称>.toml`
+pub const PROFILE_DIR: &str = "./profiles";
+/// 记录当前档案名称的文件
+const ACTIVE_PROFILE_PATH: &str = "profiles.toml";
+/// 默认档案名称
+pub const DEFAULT_PROFILE: &str = "default";
+/// 旧版本的配置文件, 默认档案不存在时从这里迁移
+const LEGACY_CONFIG_PATH: &str = "config.toml";
+
+/// 档案名称对应的配置文件路径
+pub fn profile_path(name: &str) -> PathBuf {
+    Path::new(PROFILE_DIR).join(format!("{name}.toml"))
+}
+
+/// 列出已保存的档案名称
+pub fn list_profiles() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(PROFILE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    e.file_name()
+                        .to_string_lossy()
+                        .strip_suffix(".toml")
+                        .map(str::to_string)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// 档案名称是否可用作文件名
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// 记录当前档案的顶层文件
+#[derive(Debug, Serialize, Deserialize)]
+struct ActiveProfile {
+    active_profile: String,
+}
+
+/// 读取当前档案名称, 未记录时为默认档案
+pub fn active_profile() -> String {
+    fs::read_to_string(ACTIVE_PROFILE_PATH)
+        .ok()
+        .and_then(|content| toml::from_str::<ActiveProfile>(&content).ok())
+        .map(|active| active.active_profile)
+        .filter(|name| is_valid_profile_name(name))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// 记录当前档案名称, 下次启动时加载
+pub fn set_active_profile(name: &str) -> anyhow::Result<()> {
+    let content = toml::to_string_pretty(&ActiveProfile {
+        active_profile: name.to_string(),
+    })?;
+    fs::write(ACTIVE_PROFILE_PATH, content)?;
+    Ok(())
+}
+
+/// 界面配色方案名称
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    /// 所有配色方案
+    pub const ALL: [Self; 3] = [Self::Dark, Self::Light, Self::HighContrast];
+
+    /// 显示名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "深色",
+            Self::Light => "浅色",
+            Self::HighContrast => "高对比度",
+        }
+    }
+}
+
+/// 截图格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotFormat {
+    #[default]
+    Png,
+    Bmp,
+    Jpg,
+}
+
+impl ScreenshotFormat {
+    /// 文件扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Bmp => "bmp",
+            Self::Jpg => "jpg",
+        }
+    }
+
+    /// 对应的图片编码格式
+    pub fn image_format(self) -> image::ImageFormat {
+        match self {
+            Self::Png => image::ImageFormat::Png,
+            Self::Bmp => image::ImageFormat::Bmp,
+            Self::Jpg => image::ImageFormat::Jpeg,
+        }
+    }
+}
+
+/// 默认语音命令表
+///
+/// | 短语 | 动作 |
+/// | --- | --- |
+/// | 开心 / 高兴 | `Mood::Happy` |
+/// | 生气 | `Mood::Angry` |
+/// | 累了 / 困了 | `Mood::Tired` |
+/// | 正常 | `Mood::Default` |
+/// | 看左 / 看右 / 看上 / 看下 | `Position::W` / `E` / `N` / `S` |
+/// | 看前面 | `Position::Center` |
+fn default_voice_commands() -> BTreeMap<String, String> {
+    [
+        ("开心", "mood:happy"),
+        ("高兴", "mood:happy"),
+        ("生气", "mood:angry"),
+        ("累了", "mood:tired"),
+        ("困了", "mood:tired"),
+        ("正常", "mood:default"),
+        ("看左", "look:left"),
+        ("看右", "look:right"),
+        ("看上", "look:up"),
+        ("看下", "look:down"),
+        ("看前面", "look:center"),
+    ]
+    .iter()
+    .map(|(phrase, action)| (phrase.to_string(), action.to_string()))
+    .collect()
+}
+
 #[allow(dead_code)]
 impl AppConfig {
-    /// 配置文件路径
-    const CONFIG_PATH: &'static str = "config.toml";
+    /// 加载当前档案的配置
+    pub fn load() -> Self {
+        Self::load_profile(&active_profile())
+    }
 
-    /// 加载配置
+    /// 加载指定档案
     ///
-    /// 如果配置文件不存在或解析失败，返回默认配置
-    pub fn load() -> Self {
-        match fs::read_to_string(Self::CONFIG_PATH) {
-            Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
-                log::warn!("Failed to parse config: {e}, using default");
-                Self::default()
-            }),
+    /// 档案不存在时使用默认配置并保存, 默认档案不存在时先从旧版本的 config.toml 迁移;
+    /// 解析失败时返回默认配置
+    pub fn load_profile(name: &str) -> Self {
+        let path = profile_path(name);
+        let source = if !path.exists() && name == DEFAULT_PROFILE {
+            Path::new(LEGACY_CONFIG_PATH)
+        } else {
+            path.as_path()
+        };
+        let (config, found) = Self::read(source);
+        // 新建或迁移的档案立即保存
+        let save = !found || source != path;
+        Self::with_path(config, name, path, save)
+    }
+
+    /// 加载指定路径的配置文件, 不使用配置档案; 文件不存在时使用默认配置并保存
+    pub fn load_file(path: &Path) -> Self {
+        let (config, found) = Self::read(path);
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self::with_path(config, &name, path.to_path_buf(), !found)
+    }
+
+    /// 读取配置文件, 返回配置和文件是否存在; 解析失败时返回默认配置
+    fn read(source: &Path) -> (Self, bool) {
+        match fs::read_to_string(source) {
+            Ok(content) => (
+                toml::from_str(&content).unwrap_or_else(|e| {
+                    log::warn!("Failed to parse config: {e}, using default");
+                    Self::default()
+                }),
+                true,
+            ),
             Err(e) => {
                 log::info!("Config file not found: {e}, using default");
-                let config = Self::default();
-                // 保存默认配置
-                if let Err(e) = config.save() {
-                    log::warn!("Failed to save default config: {e}");
-                }
-                config
+                (Self::default(), false)
+            }
+        }
+    }
+
+    /// 设置档案名称和保存路径, `save` 为真时立即保存
+    fn with_path(mut config: Self, name: &str, path: PathBuf, save: bool) -> Self {
+        config.profile = name.to_string();
+        config.path = path;
+        if save {
+            if let Err(e) = config.save() {
+                log::warn!("Failed to save default config: {e}");
             }
         }
+        config
+    }
+
+    /// 另存为指定档案, 之后的保存都写入该档案
+    pub fn save_profile(&mut self, name: &str) -> anyhow::Result<()> {
+        anyhow::ensure!(is_valid_profile_name(name), "Invalid profile name: {name}");
+        self.profile = name.to_string();
+        self.path = profile_path(name);
+        self.save()
+    }
+
+    /// 配置文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 
     /// 保存配置
     pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let content = toml::to_string_pretty(self)?;
-        fs::write(Path::new(Self::CONFIG_PATH), content)?;
-        log::info!("Config saved to {}", Self::CONFIG_PATH);
+        fs::write(&self.path, content)?;
+        log::info!("Config saved to {}", self.path.display());
         Ok(())
     }
 
-    /// 更新麦克风配置并保存
-    pub fn set_speech_name(&mut self, name: String) {
-        self.speech_name = name;
-        let _ = self.save();
+    /// 配置文件的修改时间, 文件不存在时返回 `None`
+    pub fn modified_time(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path)
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+
+    /// 重新读取配置文件, 与 [AppConfig::load] 不同, 读取或解析失败时返回错误
+    pub fn reload(&self) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(&self.path)?;
+        let mut config: Self = toml::from_str(&content)?;
+        config.profile = self.profile.clone();
+        config.path = self.path.clone();
+        Ok(config)
+    }
+
+    /// 与另一份配置相比值不同的字段名
+    pub fn changed_fields(&self, other: &Self) -> Vec<String> {
+        let (Ok(old), Ok(new)) = (toml::Table::try_from(self), toml::Table::try_from(other)) else {
+            return Vec::new();
+        };
+        new.iter()
+            .filter(|(key, value)| old.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// 生成语音管理器配置
+    pub fn voice_config(&self) -> VoiceConfig {
+        VoiceConfig {
+            model_path: self.model_path.clone(),
+            speech_name: self.speech_name.clone(),
+            wake_words: self.wake_words.clone(),
+            greeting: self.greeting.clone(),
+            vad_threshold: self.vad_threshold,
+            vad_hangover: self.vad_hangover,
+            push_to_talk: self.push_to_talk,
+            min_confidence: self.min_confidence,
+            commands: self.voice_commands.clone(),
+        }
+    }
+
+    /// 解析配置的 VID/PID, 格式错误时使用默认值
+    pub fn usb_ids(&self) -> UsbIds {
+        let defaults = UsbIds::default();
+        UsbIds {
+            vid: parse_hex_id(&self.vid).unwrap_or_else(|| {
+                log::warn!("Invalid vid {:?}, use default", self.vid);
+                defaults.vid
+            }),
+            pid: parse_hex_id(&self.pid).unwrap_or_else(|| {
+                log::warn!("Invalid pid {:?}, use default", self.pid);
+                defaults.pid
+            }),
+        }
+    }
+
+    /// 生成通信后端配置
+    pub fn transport_config(&self) -> TransportConfig {
+        TransportConfig {
+            kind: self.transport,
+            ids: self.usb_ids(),
+            cdc_port: self.cdc_port.clone(),
+            baud_rate: self.baud_rate,
+            pixel_format: self.pixel_format,
+        }
+    }
+
+    /// 屏幕方向
+    pub fn orientation(&self) -> robot::lcd::Orientation {
+        robot::lcd::Orientation {
+            flip_h: self.flip_h,
+            flip_v: self.flip_v,
+            rotate: self.rotate,
+        }
+    }
+
+    /// 唤醒词以逗号拼接后的字符串, 用于设置页显示和编辑
+    pub fn wake_words_str(&self) -> String {
+        self.wake_words.join(",")
+    }
+
+    /// 解析逗号分隔的唤醒词 (支持中英文逗号), 忽略空白项
+    pub fn parse_wake_words(text: &str) -> Vec<String> {
+        text.split([',', '，'])
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .collect()
     }
 
     /// 更新 WiFi 配置并保存
@@ -67,3 +558,13 @@ impl AppConfig {
         let _ = self.save();
     }
 }
+
+/// 解析十六进制 ID, 可带 `0x` 前缀
+fn parse_hex_id(text: &str) -> Option<u16> {
+    let text = text.trim();
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    u16::from_str_radix(digits, 16).ok()
+}
@@ -0,0 +1,105 @@
+//! 连接守卫
+//!
+//! 菜单、弹窗、热插拔和启动重试都可能发起连接, 守卫保证同一时刻只有一个连接线程在打开设备.
+//! 连接线程持有 [ConnectTicket], 线程结束 (包括 panic) 时凭证销毁, 守卫随之释放
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// 连接守卫, 克隆后共享同一状态
+#[derive(Debug, Clone, Default)]
+pub struct ConnectGuard {
+    active: Arc<AtomicBool>,
+}
+
+impl ConnectGuard {
+    /// 开始一次连接, 已有连接在进行时返回 `None`
+    pub fn try_begin(&self) -> Option<ConnectTicket> {
+        self.active
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| ConnectTicket {
+                active: self.active.clone(),
+            })
+    }
+
+    /// 是否有连接正在进行
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+}
+
+/// 连接进行中的凭证, 销毁时释放守卫
+#[derive(Debug)]
+pub struct ConnectTicket {
+    active: Arc<AtomicBool>,
+}
+
+impl Drop for ConnectTicket {
+    fn drop(&mut self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn second_connect_is_ignored_while_first_in_flight() {
+        let guard = ConnectGuard::default();
+        let first = guard.try_begin();
+        assert!(first.is_some());
+        assert!(guard.is_active());
+        assert!(guard.try_begin().is_none());
+
+        drop(first);
+        assert!(!guard.is_active());
+        assert!(guard.try_begin().is_some());
+    }
+
+    #[test]
+    fn ticket_released_when_thread_ends() {
+        let guard = ConnectGuard::default();
+        let ticket = guard.try_begin().unwrap();
+        thread::spawn(move || drop(ticket)).join().unwrap();
+        assert!(!guard.is_active());
+
+        let ticket = guard.try_begin().unwrap();
+        let _ = thread::spawn(move || {
+            let _ticket = ticket;
+            panic!("connect thread panicked");
+        })
+        .join();
+        assert!(!guard.is_active());
+    }
+
+    #[test]
+    fn rapid_connects_start_one_thread() {
+        const CALLERS: usize = 8;
+        let guard = ConnectGuard::default();
+        let barrier = Arc::new(Barrier::new(CALLERS));
+        let callers: Vec<_> = (0..CALLERS)
+            .map(|_| {
+                let guard = guard.clone();
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    // 持有凭证直到所有调用方都尝试过
+                    let ticket = guard.try_begin();
+                    barrier.wait();
+                    ticket.is_some()
+                })
+            })
+            .collect();
+        let started = callers
+            .into_iter()
+            .map(|caller| caller.join().unwrap())
+            .filter(|&started| started)
+            .count();
+        assert_eq!(started, 1);
+        assert!(!guard.is_active());
+    }
+}
@@ -0,0 +1,383 @@
+//! HTTP 控制接口
+//!
+//! 在后台线程监听 [AppConfig::http_api_addr](super::config::AppConfig::http_api_addr),
+//! 请求校验通过后转换为 [RemoteCommand] 经通道交给主循环执行, 不直接修改界面状态
+//!
+//! - `POST /eyes/mood` `{"mood": "happy"}` 设置表情
+//! - `POST /servo` `{"angles": [0, 0, 0, 0, 0, 0]}` 设置舵机目标角度
+//! - `POST /image` 请求体为图片文件, 居中裁剪缩放后静态显示
+
+use super::remote::{self, RemoteCommand};
+use crate::robot::{lcd, SERVO_COUNT};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// 请求体最大字节数
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+/// 请求行和请求头合计最大字节数
+const MAX_HEADER_BYTES: u64 = 8 * 1024;
+/// 同时处理的最大连接数, 超出时直接回复 503
+const MAX_CONNECTIONS: usize = 4;
+/// 没有连接时检查退出标志的间隔
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+/// 读取请求的超时时间
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 解析后的请求
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// 正在处理的连接计数, 每个连接持有一个 [ConnectionSlot]
+#[derive(Clone, Default)]
+struct ConnectionSlots(Arc<AtomicUsize>);
+
+impl ConnectionSlots {
+    /// 占用一个连接名额, 已满时返回 `None`
+    fn try_acquire(&self) -> Option<ConnectionSlot> {
+        self.0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot(self.0.clone()))
+    }
+}
+
+/// 连接名额, 连接处理结束时释放
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Deserialize)]
+struct MoodRequest {
+    mood: String,
+}
+
+#[derive(Deserialize)]
+struct ServoRequest {
+    angles: [i16; SERVO_COUNT],
+}
+
+/// HTTP 服务, 销毁时停止后台线程
+pub struct ApiServer {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    rx: mpsc::Receiver<RemoteCommand>,
+}
+
+#[allow(dead_code)]
+impl ApiServer {
+    /// 绑定地址并启动服务线程
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - 监听地址, 如 `127.0.0.1:8765`
+    pub fn start(addr: &str) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        // 非阻塞 accept, 才能及时响应退出
+        listener.set_nonblocking(true)?;
+        log::info!("HTTP API listening on {addr}");
+
+        let running = Arc::new(AtomicBool::new(true));
+        let (tx, rx) = mpsc::channel();
+        let running_clone = running.clone();
+        let slots = ConnectionSlots::default();
+        let handle = thread::spawn(move || {
+            while running_clone.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, peer)) => {
+                        let Some(slot) = slots.try_acquire() else {
+                            log::warn!("HTTP API too many connections, rejected {peer}");
+                            let _ = stream.set_nonblocking(false);
+                            let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+                            let _ = write_response(stream, 503, "too many connections");
+                            continue;
+                        };
+                        // 每个连接单独一个线程, 慢客户端不会阻塞其他请求
+                        let tx = tx.clone();
+                        thread::spawn(move || {
+                            let _slot = slot;
+                            if let Err(e) = handle_connection(stream, &tx) {
+                                log::warn!("HTTP API request from {peer} failed: {e}");
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(ACCEPT_INTERVAL)
+                    }
+                    Err(e) => {
+                        log::warn!("HTTP API accept failed: {e}");
+                        thread::sleep(ACCEPT_INTERVAL);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+            rx,
+        })
+    }
+
+    /// 非阻塞获取命令
+    pub fn try_recv(&self) -> Option<RemoteCommand> {
+        self.rx.try_recv().ok()
+    }
+
+    /// 停止服务线程
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 读取一个请求并回复, 每个连接只处理一个请求, 每次读取最长阻塞 [READ_TIMEOUT]
+fn handle_connection(stream: TcpStream, tx: &mpsc::Sender<RemoteCommand>) -> anyhow::Result<()> {
+    // 部分平台上 accept 得到的连接会继承监听端的非阻塞设置
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let (status, message) = match read_request(&mut reader) {
+        Ok(Request { method, path, body }) => match route(&method, &path, &body) {
+            Ok(command) => {
+                log::info!("HTTP API {method} {path}");
+                match tx.send(command) {
+                    Ok(()) => (200, "ok".to_string()),
+                    Err(_) => (503, "app is shutting down".to_string()),
+                }
+            }
+            Err((status, message)) => (status, message),
+        },
+        Err((status, message)) => (status, message),
+    };
+    write_response(stream, status, &message)
+}
+
+/// 读取请求行、请求头和请求体, 失败时返回状态码和错误说明
+///
+/// 请求头超过 [MAX_HEADER_BYTES] 或请求体超过 [MAX_BODY_BYTES] 时不再继续读取
+fn read_request(reader: &mut impl BufRead) -> Result<Request, (u16, String)> {
+    let io_error = |e: std::io::Error| (400, format!("failed to read request: {e}"));
+    let mut head = reader.take(MAX_HEADER_BYTES);
+
+    let mut request_line = String::new();
+    head.read_line(&mut request_line).map_err(io_error)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if head.read_line(&mut line).map_err(io_error)? == 0 {
+            if head.limit() == 0 {
+                return Err((431, format!("header larger than {MAX_HEADER_BYTES} bytes")));
+            }
+            break;
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err((413, format!("body larger than {MAX_BODY_BYTES} bytes")));
+    }
+    let mut body = vec![0; content_length];
+    head.into_inner().read_exact(&mut body).map_err(io_error)?;
+    Ok(Request { method, path, body })
+}
+
+/// 按路径解析请求体, 失败时返回状态码和错误说明
+fn route(method: &str, path: &str, body: &[u8]) -> Result<RemoteCommand, (u16, String)> {
+    if !matches!(path, "/eyes/mood" | "/servo" | "/image") {
+        return Err((404, format!("unknown path {path}")));
+    }
+    if method != "POST" {
+        return Err((405, "only POST is supported".to_string()));
+    }
+    let bad_request = |e: &dyn std::fmt::Display| (400, e.to_string());
+
+    match path {
+        "/eyes/mood" => {
+            let request: MoodRequest = serde_json::from_slice(body).map_err(|e| bad_request(&e))?;
+            if lcd::mood_from_name(&request.mood).is_none() {
+                return Err((400, format!("unknown mood {}", request.mood)));
+            }
+            Ok(RemoteCommand::Mood(request.mood))
+        }
+        "/servo" => {
+            let request: ServoRequest =
+                serde_json::from_slice(body).map_err(|e| bad_request(&e))?;
+            remote::check_angles(&request.angles).map_err(|e| (400, e))?;
+            Ok(RemoteCommand::Servo(request.angles))
+        }
+        _ => lcd::decode_image(body)
+            .map(RemoteCommand::Image)
+            .map_err(|e| bad_request(&e)),
+    }
+}
+
+fn write_response(mut stream: TcpStream, status: u16, message: &str) -> anyhow::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Service Unavailable",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}",
+        message.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(result: Result<RemoteCommand, (u16, String)>) -> u16 {
+        result.map(|_| 200).unwrap_or_else(|(status, _)| status)
+    }
+
+    fn png() -> Vec<u8> {
+        let mut data = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(4, 4)
+            .write_to(&mut data, image::ImageFormat::Png)
+            .unwrap();
+        data.into_inner()
+    }
+
+    fn read(data: &[u8]) -> Result<Request, (u16, String)> {
+        read_request(&mut std::io::Cursor::new(data))
+    }
+
+    #[test]
+    fn request_with_body() {
+        let request = read(b"POST /servo HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}extra").unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/servo");
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[test]
+    fn oversized_header_is_431() {
+        let mut data = b"POST /servo HTTP/1.1\r\nX-Pad: ".to_vec();
+        data.extend(std::iter::repeat_n(b'a', MAX_HEADER_BYTES as usize));
+        data.extend_from_slice(b"\r\n\r\n");
+        assert_eq!(read(&data).err().map(|(status, _)| status), Some(431));
+
+        // 没有换行的超长请求行同样在上限处停止读取
+        let line = vec![b'a'; MAX_HEADER_BYTES as usize * 2];
+        assert_eq!(read(&line).err().map(|(status, _)| status), Some(431));
+    }
+
+    #[test]
+    fn oversized_body_is_413() {
+        let data = format!(
+            "POST /image HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(
+            read(data.as_bytes()).err().map(|(status, _)| status),
+            Some(413)
+        );
+    }
+
+    #[test]
+    fn truncated_body_is_400() {
+        let data = b"POST /servo HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert_eq!(read(data).err().map(|(status, _)| status), Some(400));
+    }
+
+    #[test]
+    fn connection_slots_are_capped() {
+        let slots = ConnectionSlots::default();
+        let held: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| slots.try_acquire().expect("slot"))
+            .collect();
+        assert!(slots.try_acquire().is_none());
+        drop(held);
+        assert!(slots.try_acquire().is_some());
+    }
+
+    #[test]
+    fn unknown_path_is_404() {
+        assert_eq!(status(route("POST", "/", b"")), 404);
+        assert_eq!(status(route("GET", "/unknown", b"")), 404);
+    }
+
+    #[test]
+    fn non_post_is_405() {
+        for path in ["/eyes/mood", "/servo", "/image"] {
+            assert_eq!(status(route("GET", path, b"")), 405, "{path}");
+        }
+    }
+
+    #[test]
+    fn mood() {
+        let command = route("POST", "/eyes/mood", br#"{"mood": "happy"}"#);
+        assert!(matches!(command, Ok(RemoteCommand::Mood(mood)) if mood == "happy"));
+        assert_eq!(
+            status(route("POST", "/eyes/mood", br#"{"mood": "sad"}"#)),
+            400
+        );
+        assert_eq!(status(route("POST", "/eyes/mood", b"{")), 400);
+    }
+
+    #[test]
+    fn servo() {
+        let command = route("POST", "/servo", br#"{"angles": [10, 0, 0, 0, 0, -20]}"#);
+        assert!(matches!(
+            command,
+            Ok(RemoteCommand::Servo([10, 0, 0, 0, 0, -20]))
+        ));
+        // 头部范围为 ±15°
+        let out_of_range = br#"{"angles": [40, 0, 0, 0, 0, 0]}"#;
+        assert_eq!(status(route("POST", "/servo", out_of_range)), 400);
+        assert_eq!(status(route("POST", "/servo", br#"{"angles": [0]}"#)), 400);
+    }
+
+    #[test]
+    fn image() {
+        let command = route("POST", "/image", &png());
+        assert!(
+            matches!(command, Ok(RemoteCommand::Image(pixels)) if pixels.len() == lcd::FRAME_SIZE)
+        );
+        assert_eq!(status(route("POST", "/image", b"not an image")), 400);
+    }
+}
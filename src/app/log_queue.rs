@@ -0,0 +1,336 @@
+//! 应用内日志
+//!
+//! 保存最近的日志条目供界面显示, 连续重复的消息合并计数
+
+use chrono::{DateTime, Local};
+use simplelog::{Config, LevelFilter, SharedLogger};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 日志队列默认容量
+pub const LOG_QUEUE_CAPACITY: usize = 500;
+/// 日志导出目录
+pub const LOG_EXPORT_DIR: &str = "./logs";
+/// 日志弹窗显示的条目数
+pub const LOG_POPUP_ENTRIES: usize = 5;
+/// 警告或错误自动弹出后的显示时间
+const LOG_POPUP_DURATION: Duration = Duration::from_secs(5);
+
+/// 日志级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// 显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warn,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+/// 日志条目
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: LogLevel,
+    pub message: String,
+    pub count: u32,
+}
+
+impl LogEntry {
+    /// 消息文本, 重复多次时附加次数
+    pub fn with_count(&self) -> String {
+        if self.count > 1 {
+            format!("{} (x{})", self.message, self.count)
+        } else {
+            self.message.clone()
+        }
+    }
+}
+
+/// 日志队列, 超出容量时丢弃最旧的条目
+#[derive(Debug)]
+pub struct LogQueue {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    alert: bool,
+}
+
+impl Default for LogQueue {
+    fn default() -> Self {
+        Self::new(LOG_QUEUE_CAPACITY)
+    }
+}
+
+#[allow(dead_code)]
+impl LogQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            alert: false,
+        }
+    }
+
+    /// 添加日志, 与上一条级别和内容相同时只增加计数
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        let message = message.into();
+        if level >= LogLevel::Warn {
+            self.alert = true;
+        }
+        if let Some(last) = self.entries.back_mut() {
+            if last.level == level && last.message == message {
+                last.count += 1;
+                last.timestamp = Local::now();
+                return;
+            }
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            timestamp: Local::now(),
+            level,
+            message,
+            count: 1,
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Info, message);
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Warn, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Error, message);
+    }
+
+    /// 所有条目, 从旧到新
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    /// 最近的 n 条, 从旧到新
+    pub fn latest(&self, n: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(n))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.alert = false;
+    }
+
+    /// 导出所有条目到文本文件, 每行格式为 `时间 级别 消息 (xN)`
+    pub fn export(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = BufWriter::new(File::create(path)?);
+        for entry in &self.entries {
+            writeln!(
+                file,
+                "{} {:<5} {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.level.label(),
+                entry.with_count()
+            )?;
+        }
+        file.flush()
+    }
+
+    /// 自上次调用以来是否有新的警告或错误
+    pub fn take_alert(&mut self) -> bool {
+        std::mem::take(&mut self.alert)
+    }
+}
+
+/// 转发到日志队列的日志记录: (级别, 消息)
+pub type LogRecord = (LogLevel, String);
+
+/// 把本程序的 `log` 记录转发给界面的日志队列
+///
+/// 日志可能来自任意线程, 通过通道发送, 由主循环写入 [LogQueue]; 第三方库的日志只写文件
+pub struct QueueLogger {
+    level: LevelFilter,
+    tx: Mutex<mpsc::Sender<LogRecord>>,
+}
+
+impl QueueLogger {
+    /// 创建日志转发器和对应的接收端
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - 转发的最低级别
+    pub fn new(level: LevelFilter) -> (Box<Self>, mpsc::Receiver<LogRecord>) {
+        let (tx, rx) = mpsc::channel();
+        let logger = Box::new(Self {
+            level,
+            tx: Mutex::new(tx),
+        });
+        (logger, rx)
+    }
+}
+
+impl log::Log for QueueLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.level && metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if let Ok(tx) = self.tx.lock() {
+            // 接收端销毁说明程序正在退出, 丢弃即可
+            let _ = tx.send((record.level().into(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+impl SharedLogger for QueueLogger {
+    fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn log::Log> {
+        self
+    }
+}
+
+/// 日志弹窗, 出现警告或错误时自动显示一段时间
+#[derive(Debug, Default)]
+pub struct LogPopup {
+    pinned: bool,
+    until: Option<Instant>,
+    /// 正在输入搜索词
+    searching: bool,
+    /// 搜索词, 只显示消息中包含该子串的条目
+    pub filter: String,
+}
+
+#[allow(dead_code)]
+impl LogPopup {
+    /// 临时显示, 超时后自动隐藏
+    pub fn flash(&mut self) {
+        self.until = Some(Instant::now() + LOG_POPUP_DURATION);
+    }
+
+    /// 手动切换常驻显示
+    pub fn toggle(&mut self) {
+        self.pinned = !self.pinned;
+        self.until = None;
+    }
+
+    pub fn hide(&mut self) {
+        self.pinned = false;
+        self.until = None;
+        self.end_search(false);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.searching || self.pinned || self.until.is_some_and(|until| Instant::now() < until)
+    }
+
+    /// 开始输入搜索词, 输入期间弹窗保持显示
+    pub fn begin_search(&mut self) {
+        self.searching = true;
+        self.pinned = true;
+    }
+
+    /// 结束输入, `keep` 为 false 时清除搜索词
+    pub fn end_search(&mut self, keep: bool) {
+        self.searching = false;
+        if !keep {
+            self.filter.clear();
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    /// 条目是否匹配当前搜索词
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.filter.is_empty() || entry.message.contains(&self.filter)
+    }
+}
+
+/// 日志页面的浏览状态
+#[derive(Debug)]
+pub struct LogView {
+    /// 距离最新一条的偏移, 0 表示跟随最新日志
+    pub scroll: usize,
+    /// 只显示不低于该级别的日志
+    pub min_level: LogLevel,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            scroll: 0,
+            min_level: LogLevel::Info,
+        }
+    }
+}
+
+impl LogView {
+    /// 向旧日志滚动
+    pub fn scroll_up(&mut self, lines: usize, total: usize) {
+        self.scroll = (self.scroll + lines).min(total.saturating_sub(1));
+    }
+
+    /// 向新日志滚动
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// 切换过滤级别: 全部 -> 警告及以上 -> 仅错误
+    pub fn cycle_filter(&mut self) {
+        self.min_level = match self.min_level {
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Error,
+            LogLevel::Error => LogLevel::Info,
+        };
+        self.scroll = 0;
+    }
+}
@@ -1,9 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 /// 菜单项
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MenuItem {
+    #[default]
     DeviceStatus,
     DeviceControl,
     Settings,
+    Log,
     About,
 }
 
@@ -13,15 +18,17 @@ impl MenuItem {
             MenuItem::DeviceStatus => "设备状态",
             MenuItem::DeviceControl => "设备控制",
             MenuItem::Settings => "设置",
+            MenuItem::Log => "日志",
             MenuItem::About => "关于",
         }
     }
 
-    pub fn all() -> [Self; 4] {
+    pub fn all() -> [Self; 5] {
         [
             MenuItem::DeviceStatus,
             MenuItem::DeviceControl,
             MenuItem::Settings,
+            MenuItem::Log,
             MenuItem::About,
         ]
     }
@@ -1,20 +1,135 @@
+pub mod capture;
 pub mod config;
+pub mod connect_guard;
+#[cfg(feature = "http-api")]
+pub mod http_api;
+pub mod log_queue;
 /// app模块, 负责界面调度以及实际运行功能
 pub mod menu;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "osc")]
+pub mod osc;
+pub mod preview;
+#[cfg(feature = "ws-preview")]
+pub mod preview_server;
+pub mod remote;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod settings;
+pub mod stdin_control;
+pub mod system_status;
+pub mod telemetry;
 
-use crate::robot::{self, CommState, DisplayMode, Joint, JointConfig, Lcd};
+use crate::robot::{
+    self, lcd, CommError, CommState, DisplayMode, FrameSender, HotplugEvent, HotplugWatcher, Joint,
+    JointConfig, Lcd, LinkState, ServoState, TransportKind,
+};
 
 // 导出菜单
 pub use menu::*;
 
-use crate::voice::VoiceManager;
+use crate::cli::Args;
+use crate::robot::motion::{self, IdleMotion, Motion, MotionPlayer, MotionRecorder};
+use crate::voice::{self, PartialEvent, VoiceCommand, VoiceManager};
+use capture::FrameCapture;
+use connect_guard::ConnectGuard;
 use electron_bot::{FRAME_HEIGHT, FRAME_WIDTH};
+use log_queue::{LogPopup, LogQueue, LogRecord, LogView};
+use preview::{LcdPreview, PreviewCache};
+use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 use std::sync::mpsc;
 use std::sync::mpsc::SyncSender;
+use std::time::{Duration, Instant};
 
 pub type BotRecvType = (Vec<u8>, JointConfig);
 
+/// 连接线程的结果: 通信状态、通信线程句柄、帧发送端
+type ConnectResult = anyhow::Result<(
+    CommState,
+    std::thread::JoinHandle<()>,
+    FrameSender<BotRecvType>,
+)>;
+
+/// 切换档案时值变化需要重启语音的配置项
+const VOICE_RESTART_FIELDS: [&str; 7] = [
+    "model_path",
+    "speech_name",
+    "vad_threshold",
+    "vad_hangover",
+    "push_to_talk",
+    "min_confidence",
+    "voice_commands",
+];
+
+/// MQTT 和浏览器预览的状态推送间隔
+#[cfg(any(feature = "mqtt", feature = "ws-preview"))]
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+/// 浏览器预览的最短画面间隔
+#[cfg(feature = "ws-preview")]
+const PREVIEW_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 检查配置文件是否被外部修改的间隔
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 连接设备超时时间, 超时后弹窗提示连接超时
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// 启动时自动连接失败后的首次重试间隔, 之后每次翻倍
+const AUTO_CONNECT_BACKOFF_MIN: Duration = Duration::from_secs(1);
+/// 启动时自动连接的最大重试间隔
+const AUTO_CONNECT_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// 启动时自动连接的重试状态
+struct AutoConnect {
+    /// 下次尝试的时间
+    next: Instant,
+    /// 本次失败后的等待时间
+    backoff: Duration,
+}
+
+/// 设备连接状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Disconnected,
+    /// 后台线程正在打开设备
+    Connecting,
+    Connected,
+}
+
+/// 可点击区域, 渲染时记录, 用于把鼠标坐标映射到界面元素
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MouseAreas {
+    /// 侧边栏菜单列表
+    pub menu: Rect,
+    /// 设备控制页各舵机进度条
+    pub gauge_bars: [Rect; robot::SERVO_COUNT],
+}
+
+/// 眼睛颜色预设, 名称 -> RGB
+pub const EYE_TINT_PRESETS: [(&str, [u8; 3]); 8] = [
+    ("白色", [255, 255, 255]),
+    ("红色", [255, 60, 60]),
+    ("橙色", [255, 160, 40]),
+    ("黄色", [255, 230, 60]),
+    ("绿色", [60, 255, 90]),
+    ("青色", [60, 230, 255]),
+    ("蓝色", [70, 120, 255]),
+    ("紫色", [200, 90, 255]),
+];
+
+/// 眼睛颜色的显示名称, 不是预设时显示十六进制值
+pub fn eye_tint_name(tint: [u8; 3]) -> String {
+    EYE_TINT_PRESETS
+        .iter()
+        .find(|(_, rgb)| *rgb == tint)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| format!("#{:02x}{:02x}{:02x}", tint[0], tint[1], tint[2]))
+}
+
+/// 唤醒时临时切换表情的持续时间
+const WAKE_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
 /// 主应用
 pub struct App {
     pub menu_state: ListState,
@@ -22,72 +137,1396 @@ pub struct App {
     pub running: bool,
     pub joint: Joint,
     pub in_servo_mode: bool,
+    pub key_hold: Option<KeyHold>, // 舵机模式下方向键按住状态, 用于加速
+    pub system_status: system_status::SystemStatus, // 上位机电量和网络状态
     pub in_settings: bool,
+    pub in_log_view: bool,
+    pub in_quit_confirm: bool,          // 退出确认弹窗
+    pub reveal_secrets: bool,           // 设置页明文显示密码等敏感内容
+    pub settings_error: Option<String>, // 设置项输入无效的说明
     pub settings_selected: usize,
     pub in_edit_settings_mode: bool,
+    pub in_pose_name_mode: bool,  // 输入姿势名称
+    pub in_angle_edit_mode: bool, // 直接输入舵机角度
+    pub in_lcd_text_mode: bool,   // 输入屏幕显示文字
+    pub motion_recorder: Option<MotionRecorder>,
+    pub motion_player: Option<MotionPlayer>,
+    idle_motion: Option<IdleMotion>,   // 空闲呼吸动作
+    pub capture: Option<FrameCapture>, // 正在录制的屏幕画面
+    capture_jobs: Vec<std::thread::JoinHandle<anyhow::Result<std::path::PathBuf>>>,
     pub edit_buffer: String,
     pub config: config::AppConfig,
     pub lcd: Lcd,
+    pub lcd_preview: LcdPreview,     // 当前帧的缩略图, 未连接时同样更新
+    pub preview_cache: PreviewCache, // 设备控制页的预览文本
     pub popup: Popup,
+    pub mouse_areas: MouseAreas,
+    pub dragging_servo: Option<usize>, // 鼠标正在拖动的舵机
+    pub log_queue: LogQueue,
+    pub log_popup: LogPopup,
+    pub log_view: LogView,
+    pub select_popup: SelectPopup,
     pub voice_manager: Option<VoiceManager>,
     pub left_focused: bool, // true=侧边栏有焦点，false=右侧内容有焦点
+    pub transcript: Option<PartialEvent>, // 实时语音识别文本
+    pub feedback: Option<[f32; robot::SERVO_COUNT]>, // 舵机实际角度
+    pub feedback_valid: bool, // 实际角度是否为本帧收到的回包
+    last_input: Instant,    // 最近一次按键或远程命令的时间
+    pub idle: bool,         // 无操作超时, 舵机已掉电并暂停发送
+    pub send_rate: SendRate,
+    pub comm_rate: CommRate,
+    pub frames_sent: u64,    // 已发送帧数
+    pub frames_skipped: u64, // 内容未变化而跳过的帧数
+    force_send: bool,
+    last_frame_hash: Option<u64>,
+    last_joint_bytes: Option<[u8; 32]>,
+    wake_flash_until: Option<Instant>,
+    pub eyes_mood: String, // 当前表情, 界面选择的表情另外记入配置, Ctrl+S 或退出时保存
+    pub eyes_position: String, // 当前注视方向
+    splash_until: Option<Instant>,
+    reconnecting: bool,
+    connect_start: Option<Instant>,    // 正在进行的连接开始时间
+    connect_quiet: bool,               // 正在进行的连接由自动重试发起, 不弹窗、不报错
+    auto_connect: Option<AutoConnect>, // 启动时自动连接, 首次连接成功后清除
+    connect_rx: Option<mpsc::Receiver<ConnectResult>>,
+    connecting: ConnectGuard, // 连接线程是否仍在运行, 取消后直到线程结束才允许再次连接
+    hotplug: Option<HotplugWatcher>,
     comm_state: Option<CommState>,
     comm_thread: Option<std::thread::JoinHandle<()>>,
-    comm_tx: Option<SyncSender<BotRecvType>>,
+    comm_tx: Option<FrameSender<BotRecvType>>,
+    comm_error_tx: SyncSender<CommError>,
+    comm_error_rx: mpsc::Receiver<CommError>,
+    log_rx: mpsc::Receiver<LogRecord>,
+    config_mtime: Option<std::time::SystemTime>, // 上次读取时配置文件的修改时间
+    cli: Args,                                   // 命令行参数, 重新加载配置后仍然生效
+    #[cfg(feature = "http-api")]
+    api_server: Option<http_api::ApiServer>, // HTTP 控制接口
+    #[cfg(feature = "osc")]
+    osc_server: Option<osc::OscServer>, // OSC 控制
+    #[cfg(feature = "mqtt")]
+    mqtt: Option<mqtt::MqttBridge>, // MQTT 控制与状态上报
+    #[cfg(feature = "mqtt")]
+    mqtt_status_sent: Instant, // 上次发布状态的时间
+    stdin_control: Option<stdin_control::StdinControl>, // 标准输入命令
+    #[cfg(feature = "scripting")]
+    script: Option<script::ScriptRunner>, // 正在执行的动画脚本
+    telemetry: Option<telemetry::TelemetrySink>, // 遥测输出, 未配置时为 None
+    telemetry_sent: Instant,                     // 上次输出遥测的时间
+    #[cfg(feature = "ws-preview")]
+    preview_server: Option<preview_server::PreviewServer>, // 浏览器预览
+    #[cfg(feature = "ws-preview")]
+    preview_status_sent: Instant, // 上次推送预览状态的时间
+    #[cfg(feature = "ws-preview")]
+    preview_frame_sent: Option<(Instant, u64)>, // 上次推送预览画面的时间和哈希
+    config_checked: Instant,
 }
 
 #[allow(dead_code)]
 impl App {
-    pub fn new(voice_manager: Option<VoiceManager>) -> Self {
+    pub fn new(
+        config: config::AppConfig,
+        voice_manager: Option<VoiceManager>,
+        log_rx: mpsc::Receiver<LogRecord>,
+        cli: Args,
+    ) -> Self {
         let mut menu_state = ListState::default();
         menu_state.select(Some(0));
 
-        let lcd = Lcd::new();
-        let config = config::AppConfig::load();
-        Self {
+        let mut lcd = Lcd::new();
+        lcd.set_clock_color(image::Rgb(config.clock_color));
+        lcd.set_transition_frames(config.transition_frames);
+        lcd.set_tone(config.brightness, config.gamma);
+        lcd.set_orientation(config.orientation());
+        let [r, g, b] = config.eye_tint;
+        lcd.set_eye_tint((r, g, b));
+        // 恢复上次选择的表情和注视方向
+        if let Some(mood) = lcd::mood_from_name(&config.eyes_mood) {
+            lcd.set_eyes_mood(mood);
+        }
+        if let Some(position) = lcd::position_from_name(&config.eyes_position) {
+            lcd.set_eyes_position(position);
+        }
+        let mut joint = Joint::new();
+        joint.set_calibration(config.calibration);
+        // 恢复上次的舵机角度, 舵机范围可能已变化, 超出时截断
+        joint.set_targets(&config.last_servo_values);
+        if joint.values() != &config.last_servo_values {
+            log::warn!(
+                "Saved servo values {:?} out of range, clamped to {:?}",
+                config.last_servo_values,
+                joint.values()
+            );
+        }
+        let (comm_error_tx, comm_error_rx) = mpsc::sync_channel(16);
+        let hotplug = config.auto_connect.then(|| {
+            HotplugWatcher::start(
+                config.usb_ids(),
+                Duration::from_millis(config.hotplug_poll_ms),
+            )
+        });
+        #[cfg(feature = "mqtt")]
+        let mqtt = (!config.mqtt_broker.is_empty())
+            .then(|| mqtt::MqttBridge::start(&config.mqtt_broker, &config.mqtt_topic_prefix));
+        let stdin_control = cli
+            .stdin_control
+            .then(|| {
+                stdin_control::StdinControl::start()
+                    .map_err(|e| log::error!("Failed to start stdin control: {e}"))
+                    .ok()
+            })
+            .flatten();
+        let telemetry = (!config.telemetry_sink.is_empty())
+            .then(|| {
+                telemetry::TelemetrySink::start(&config.telemetry_sink)
+                    .map_err(|e| log::error!("Failed to open telemetry sink: {e}"))
+                    .ok()
+            })
+            .flatten();
+        let eyes_mood = config.eyes_mood.clone();
+        let eyes_position = config.eyes_position.clone();
+        let mut app = Self {
             menu_state,
             selected_menu: MenuItem::DeviceStatus,
             running: true,
-            joint: Joint::new(),
+            joint,
             in_servo_mode: false,
+            key_hold: None,
+            system_status: system_status::SystemStatus::default(),
             in_settings: false,
+            in_log_view: false,
+            in_quit_confirm: false,
+            reveal_secrets: false,
+            settings_error: None,
             settings_selected: 0,
             in_edit_settings_mode: false,
+            in_pose_name_mode: false,
+            in_angle_edit_mode: false,
+            in_lcd_text_mode: false,
+            motion_recorder: None,
+            motion_player: None,
+            idle_motion: None,
+            capture: None,
+            capture_jobs: Vec::new(),
             edit_buffer: String::new(),
             config,
             lcd,
+            lcd_preview: LcdPreview::default(),
+            preview_cache: PreviewCache::default(),
             popup: Popup::new(),
+            mouse_areas: MouseAreas::default(),
+            dragging_servo: None,
+            log_queue: LogQueue::default(),
+            log_popup: LogPopup::default(),
+            log_view: LogView::default(),
+            select_popup: SelectPopup::default(),
             voice_manager,
             left_focused: true, // 默认侧边栏有焦点
+            transcript: None,
+            feedback: None,
+            feedback_valid: false,
+            last_input: Instant::now(),
+            idle: false,
+            send_rate: SendRate::default(),
+            comm_rate: CommRate::default(),
+            frames_sent: 0,
+            frames_skipped: 0,
+            force_send: true,
+            last_frame_hash: None,
+            last_joint_bytes: None,
+            wake_flash_until: None,
+            eyes_mood,
+            eyes_position,
+            splash_until: None,
+            reconnecting: false,
+            connect_start: None,
+            connect_quiet: false,
+            auto_connect: None,
+            connect_rx: None,
+            connecting: ConnectGuard::default(),
+            hotplug,
             comm_state: None,
             comm_thread: None,
             comm_tx: None,
+            comm_error_tx,
+            comm_error_rx,
+            log_rx,
+            config_mtime: None,
+            cli,
+            #[cfg(feature = "http-api")]
+            api_server: None,
+            #[cfg(feature = "osc")]
+            osc_server: None,
+            #[cfg(feature = "mqtt")]
+            mqtt,
+            #[cfg(feature = "mqtt")]
+            mqtt_status_sent: Instant::now(),
+            stdin_control,
+            #[cfg(feature = "scripting")]
+            script: None,
+            telemetry,
+            telemetry_sent: Instant::now(),
+            #[cfg(feature = "ws-preview")]
+            preview_server: None,
+            #[cfg(feature = "ws-preview")]
+            preview_status_sent: Instant::now(),
+            #[cfg(feature = "ws-preview")]
+            preview_frame_sent: None,
+            config_checked: Instant::now(),
+        };
+        app.config_mtime = app.config.modified_time();
+        if let Some(index) = MenuItem::all()
+            .iter()
+            .position(|&item| item == app.config.last_menu)
+        {
+            app.select_menu(index);
+        }
+        if app.config.connect_on_startup {
+            app.auto_connect = Some(AutoConnect {
+                next: Instant::now(),
+                backoff: AUTO_CONNECT_BACKOFF_MIN,
+            });
+        }
+        #[cfg(feature = "http-api")]
+        app.start_api_server();
+        #[cfg(feature = "ws-preview")]
+        app.start_preview_server();
+        #[cfg(feature = "osc")]
+        app.start_osc_server();
+        app.show_splash();
+        app
+    }
+
+    /// 显示配置的启动画面, 加载失败时保持眼睛动画
+    fn show_splash(&mut self) {
+        if self.config.splash_image.is_empty() {
+            return;
+        }
+        let path = self.config.splash_image.clone();
+        match self.load_image_from_file(&path) {
+            Ok(()) => {
+                self.splash_until =
+                    Some(Instant::now() + Duration::from_secs(self.config.splash_seconds));
+            }
+            Err(e) => log::warn!("Failed to show splash image, fall back to eyes: {e}"),
+        }
+    }
+
+    /// 每次主循环调用, 处理后台线程上报的事件
+    pub fn tick(&mut self) {
+        let mut commands = Vec::new();
+        let mut woken = false;
+        if let Some(vm) = &self.voice_manager {
+            while let Some(event) = vm.try_recv_partial() {
+                self.transcript = Some(event);
+            }
+            while let Some(command) = vm.try_recv_command() {
+                commands.push(command);
+            }
+            while let Some(event) = vm.try_recv_event() {
+                log::debug!("App received wake event: {}", event.text);
+                woken = true;
+            }
+        }
+        for command in commands {
+            self.apply_voice_command(command);
+        }
+        #[cfg(feature = "http-api")]
+        self.poll_api_server();
+        #[cfg(feature = "mqtt")]
+        self.poll_mqtt();
+        #[cfg(feature = "osc")]
+        self.poll_osc_server();
+        #[cfg(feature = "ws-preview")]
+        self.push_preview_status();
+        let stdin_commands: Vec<remote::RemoteCommand> = self
+            .stdin_control
+            .as_ref()
+            .map(|control| std::iter::from_fn(|| control.try_recv()).collect())
+            .unwrap_or_default();
+        for command in stdin_commands {
+            self.apply_remote_command(command);
+        }
+        #[cfg(feature = "scripting")]
+        self.poll_script();
+        self.poll_auto_connect();
+        self.check_idle();
+        self.emit_telemetry();
+        self.system_status.refresh();
+
+        // 设备插入时自动连接, 拔出时断开
+        let hotplug_events: Vec<HotplugEvent> = self
+            .hotplug
+            .as_ref()
+            .map(|watcher| std::iter::from_fn(|| watcher.try_recv()).collect())
+            .unwrap_or_default();
+        for event in hotplug_events {
+            match event {
+                HotplugEvent::Arrived(_) if !self.is_connected() && !self.is_connecting() => {
+                    self.connect_robot()
+                }
+                // 只有正在使用的 USB 设备拔出才断开, CDC 和模拟设备不受影响
+                HotplugEvent::Left(ids)
+                    if self.config.transport == TransportKind::Usb
+                        && ids == self.config.usb_ids() =>
+                {
+                    self.stop_comm_thread()
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(feedback) = self
+            .comm_state
+            .as_ref()
+            .and_then(|state| state.try_recv_feedback())
+        {
+            // 过期的角度不用于跟随, 避免掉电时目标跳回旧值
+            if feedback.feedback_valid {
+                self.joint.track_feedback(&feedback.angles);
+            }
+            self.feedback = Some(feedback.angles);
+            self.feedback_valid = feedback.feedback_valid;
+        }
+
+        if let Some(state) = &self.comm_state {
+            self.comm_rate.update(state.stats.snapshot());
+        }
+
+        // 程序日志和通信错误写入日志队列, 警告和错误自动弹出日志窗口
+        while let Ok((level, message)) = self.log_rx.try_recv() {
+            self.log_queue.push(level, message);
+        }
+        while let Ok(error) = self.comm_error_rx.try_recv() {
+            self.log_queue.error(error.to_string());
+        }
+        if self.log_queue.take_alert() {
+            self.log_popup.flash();
+        }
+
+        self.poll_connect();
+        self.poll_config_file();
+
+        // 通信线程断线重连时显示提示, 恢复后关闭
+        let reconnecting = self
+            .comm_state
+            .as_ref()
+            .is_some_and(|state| state.link_state() == LinkState::Reconnecting);
+        // 退出确认弹窗显示期间不切换弹窗内容
+        if reconnecting != self.reconnecting && !self.in_quit_confirm {
+            self.reconnecting = reconnecting;
+            if reconnecting {
+                self.popup.show_reconnecting();
+            } else {
+                self.popup.hide();
+            }
+        }
+
+        // 编码完成的录屏
+        let (finished, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.capture_jobs)
+            .into_iter()
+            .partition(|job| job.is_finished());
+        self.capture_jobs = pending;
+        for job in finished {
+            match job.join() {
+                Ok(Ok(path)) => self
+                    .log_queue
+                    .info(format!("录屏已保存: {}", path.display())),
+                Ok(Err(e)) => self.log_queue.error(format!("录屏保存失败: {e}")),
+                Err(_) => self.log_queue.error("录屏编码线程异常退出"),
+            }
+        }
+
+        // 启动画面到时后切回眼睛, 期间用户已切换模式时不干预
+        if self
+            .splash_until
+            .is_some_and(|until| Instant::now() >= until)
+        {
+            self.splash_until = None;
+            if self.lcd.mode() == DisplayMode::Static {
+                self.lcd.set_mode(DisplayMode::Eyes);
+            }
+        }
+
+        // 唤醒时短暂显示开心表情, 之后恢复当前表情
+        if woken {
+            if let Some(mood) = lcd::mood_from_name("happy") {
+                self.lcd.set_eyes_mood(mood);
+            }
+            self.wake_flash_until = Some(Instant::now() + WAKE_FLASH_DURATION);
+        } else if self
+            .wake_flash_until
+            .is_some_and(|until| Instant::now() >= until)
+        {
+            self.wake_flash_until = None;
+            if let Some(mood) = lcd::mood_from_name(&self.eyes_mood) {
+                self.lcd.set_eyes_mood(mood);
+            }
+        }
+    }
+
+    /// 按名称设置当前表情, 名称未知时返回 false
+    ///
+    /// 语音和远程命令调用, 不写入配置
+    pub fn set_eyes_mood(&mut self, name: &str) -> bool {
+        match lcd::mood_from_name(name) {
+            Some(mood) => {
+                self.lcd.set_eyes_mood(mood);
+                self.eyes_mood = name.to_string();
+                self.wake_flash_until = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 按名称设置当前注视方向, 名称未知时返回 false
+    ///
+    /// 语音和远程命令调用, 不写入配置
+    pub fn set_eyes_position(&mut self, name: &str) -> bool {
+        match lcd::position_from_name(name) {
+            Some(position) => {
+                self.lcd.set_eyes_position(position);
+                self.eyes_position = name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 切换到下一个表情, 记入配置, Ctrl+S 或退出时保存
+    pub fn next_eyes_mood(&mut self) {
+        let name = next_name(&lcd::MOOD_NAMES, &self.eyes_mood);
+        self.set_eyes_mood(name);
+        self.config.eyes_mood = name.to_string();
+    }
+
+    /// 切换到下一个注视方向, 记入配置, Ctrl+S 或退出时保存
+    pub fn next_eyes_position(&mut self) {
+        let name = next_name(&lcd::POSITION_NAMES, &self.eyes_position);
+        self.set_eyes_position(name);
+        self.config.eyes_position = name.to_string();
+    }
+
+    /// 保存配置, 失败时只记录日志
+    ///
+    /// 被命令行参数覆盖的项保存配置文件中原来的值, 文件无法读取时保存默认值
+    fn save_config(&self) {
+        let mut config = self.config.clone();
+        let saved = self.config.reload().unwrap_or_default();
+        self.cli.restore(&mut config, &saved);
+        if let Err(e) = config.save() {
+            log::error!("Failed to save settings: {e}");
+        }
+    }
+
+    /// 执行语音命令, 未知的表情或方向忽略
+    pub fn apply_voice_command(&mut self, command: VoiceCommand) {
+        self.note_input();
+        match command {
+            VoiceCommand::Mood(name) => {
+                if !self.set_eyes_mood(&name) {
+                    log::warn!("Unknown mood: {name}");
+                }
+            }
+            VoiceCommand::Look(name) => {
+                if !self.set_eyes_position(&name) {
+                    log::warn!("Unknown eyes position: {name}");
+                }
+            }
+        }
+    }
+
+    /// 按配置的地址启动 HTTP 控制接口, 地址为空时不启动
+    #[cfg(feature = "http-api")]
+    fn start_api_server(&mut self) {
+        if self.config.http_api_addr.is_empty() {
+            return;
+        }
+        match http_api::ApiServer::start(&self.config.http_api_addr) {
+            Ok(server) => self.api_server = Some(server),
+            Err(e) => log::error!(
+                "Failed to start HTTP API on {}: {e}",
+                self.config.http_api_addr
+            ),
+        }
+    }
+
+    /// 执行 HTTP 控制接口收到的命令
+    #[cfg(feature = "http-api")]
+    fn poll_api_server(&mut self) {
+        let commands: Vec<remote::RemoteCommand> = self
+            .api_server
+            .as_ref()
+            .map(|server| std::iter::from_fn(|| server.try_recv()).collect())
+            .unwrap_or_default();
+        for command in commands {
+            self.apply_remote_command(command);
+        }
+    }
+
+    /// 按配置的地址启动 OSC 服务, 地址为空时不启动
+    #[cfg(feature = "osc")]
+    fn start_osc_server(&mut self) {
+        if self.config.osc_bind_addr.is_empty() {
+            return;
+        }
+        match osc::OscServer::start(&self.config.osc_bind_addr, &self.config.osc_address_prefix) {
+            Ok(server) => self.osc_server = Some(server),
+            Err(e) => log::error!("Failed to start OSC on {}: {e}", self.config.osc_bind_addr),
+        }
+    }
+
+    /// 执行 OSC 收到的命令
+    #[cfg(feature = "osc")]
+    fn poll_osc_server(&mut self) {
+        let commands: Vec<remote::RemoteCommand> = self
+            .osc_server
+            .as_ref()
+            .map(|server| std::iter::from_fn(|| server.try_recv()).collect())
+            .unwrap_or_default();
+        for command in commands {
+            self.apply_remote_command(command);
+        }
+    }
+
+    /// 执行 MQTT 收到的命令, 按间隔发布状态
+    #[cfg(feature = "mqtt")]
+    fn poll_mqtt(&mut self) {
+        let Some(bridge) = &self.mqtt else {
+            return;
+        };
+        let commands: Vec<remote::RemoteCommand> =
+            std::iter::from_fn(|| bridge.try_recv()).collect();
+        if self.mqtt_status_sent.elapsed() >= STATUS_INTERVAL {
+            self.mqtt_status_sent = Instant::now();
+            bridge.publish_status(self.status_json());
+        }
+        for command in commands {
+            self.apply_remote_command(command);
+        }
+    }
+
+    /// 按配置的地址启动浏览器预览服务, 地址为空时不启动
+    #[cfg(feature = "ws-preview")]
+    fn start_preview_server(&mut self) {
+        if self.config.preview_server_addr.is_empty() {
+            return;
+        }
+        match preview_server::PreviewServer::start(
+            &self.config.preview_server_addr,
+            self.config.preview_max_clients,
+        ) {
+            Ok(server) => self.preview_server = Some(server),
+            Err(e) => log::error!(
+                "Failed to start preview server on {}: {e}",
+                self.config.preview_server_addr
+            ),
+        }
+    }
+
+    /// 按间隔向浏览器预览推送状态
+    #[cfg(feature = "ws-preview")]
+    fn push_preview_status(&mut self) {
+        if self.preview_status_sent.elapsed() < STATUS_INTERVAL {
+            return;
+        }
+        self.preview_status_sent = Instant::now();
+        if let Some(server) = &self.preview_server {
+            server.push_status(self.status_json());
+        }
+    }
+
+    /// 向浏览器预览推送画面, 限制帧率, 内容与上次推送相同时跳过
+    #[cfg(feature = "ws-preview")]
+    fn push_preview_frame(&mut self, hash: u64) {
+        let Some(server) = &self.preview_server else {
+            return;
+        };
+        if self.preview_frame_sent.is_some_and(|(time, last_hash)| {
+            last_hash == hash || time.elapsed() < PREVIEW_FRAME_INTERVAL
+        }) {
+            return;
+        }
+        // 服务线程繁忙丢弃了画面时不记录, 下次继续推送
+        if server.push_frame(self.lcd.frame_bytes()) {
+            self.preview_frame_sent = Some((Instant::now(), hash));
+        }
+    }
+
+    /// 远程状态 JSON: 连接状态、音量、发送的舵机角度和反馈角度
+    #[cfg(any(feature = "mqtt", feature = "ws-preview"))]
+    fn status_json(&self) -> String {
+        serde_json::json!({
+            "connected": self.is_connected(),
+            "reconnecting": self.reconnecting,
+            "volume": self.voice_manager.as_ref().map(|vm| vm.volume_smoothed()),
+            "angles": self.joint.config().angles,
+            "feedback": self.feedback,
+            "feedback_valid": self.feedback_valid,
+        })
+        .to_string()
+    }
+
+    /// 执行远程命令, 命令内容已在接收线程校验
+    fn apply_remote_command(&mut self, command: remote::RemoteCommand) {
+        self.note_input();
+        match command {
+            remote::RemoteCommand::Mood(name) => {
+                self.set_eyes_mood(&name);
+            }
+            remote::RemoteCommand::Position(name) => {
+                self.set_eyes_position(&name);
+            }
+            remote::RemoteCommand::Servo(values) => self.joint.set_targets(&values),
+            remote::RemoteCommand::Image(pixels) => match self.lcd.set_image_data(pixels) {
+                Ok(()) => self.log_queue.info("显示远程发送的图片"),
+                Err(e) => self.log_queue.error(format!("显示图片失败: {e}")),
+            },
+            remote::RemoteCommand::Joint(index, angle) => {
+                let mut values = *self.joint.values();
+                values[index] = angle;
+                self.joint.set_targets(&values);
+            }
+            remote::RemoteCommand::Quit => self.quit(),
+        }
+    }
+
+    /// 切换语音监听 (按键通话)
+    pub fn toggle_listening(&mut self) {
+        if let Some(vm) = &self.voice_manager {
+            vm.set_listening(!vm.is_listening());
+        }
+    }
+
+    /// 切换麦克风静音
+    pub fn toggle_mute(&mut self) {
+        if let Some(vm) = &self.voice_manager {
+            vm.set_muted(!vm.is_muted());
+        }
+    }
+
+    /// 开始/停止麦克风录音, 文件保存在 ./recordings 目录
+    pub fn toggle_recording(&mut self) -> anyhow::Result<()> {
+        let Some(vm) = &self.voice_manager else {
+            anyhow::bail!("Voice manager not available");
+        };
+        if vm.is_recording() {
+            return vm.stop_recording();
+        }
+
+        let dir = std::path::Path::new("./recordings");
+        std::fs::create_dir_all(dir)?;
+        let now = chrono::Local::now();
+        let path = dir.join(format!("mic_{}.wav", now.format("%Y%m%d_%H%M%S")));
+        vm.start_recording(&path)
+    }
+
+    /// 打开麦克风选择弹窗
+    pub fn open_mic_select(&mut self) {
+        let devices = voice::input_device_names();
+        let current = devices.iter().position(|d| *d == self.config.speech_name);
+        self.select_popup.open(
+            SelectPurpose::Microphone,
+            " 选择麦克风 ",
+            "未找到音频输入设备",
+            devices,
+            current,
+        );
+    }
+
+    /// 打开姿势选择弹窗
+    pub fn open_pose_select(&mut self) {
+        let poses: Vec<String> = self.config.poses.keys().cloned().collect();
+        self.select_popup.open(
+            SelectPurpose::Pose,
+            " 选择姿势 ",
+            "没有保存的姿势, 按 [n] 保存当前姿势",
+            poses,
+            None,
+        );
+    }
+
+    /// 开始输入新姿势名称
+    pub fn begin_pose_name(&mut self) {
+        self.in_pose_name_mode = true;
+        self.edit_buffer.clear();
+    }
+
+    /// 结束姿势名称输入, `save` 为 true 时以输入的名称保存当前姿势
+    pub fn finish_pose_name(&mut self, save: bool) {
+        self.in_pose_name_mode = false;
+        let name = std::mem::take(&mut self.edit_buffer);
+        if save {
+            self.save_pose(name.trim());
+        }
+    }
+
+    /// 开始直接输入当前舵机角度
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - 触发输入的第一个字符 (数字或负号)
+    pub fn begin_angle_edit(&mut self, first: char) {
+        self.in_angle_edit_mode = true;
+        self.edit_buffer.clear();
+        self.edit_buffer.push(first);
+    }
+
+    /// 结束角度输入, `save` 为 true 时把输入的角度设为当前舵机目标, 超出范围时截断
+    pub fn finish_angle_edit(&mut self, save: bool) {
+        self.in_angle_edit_mode = false;
+        let input = std::mem::take(&mut self.edit_buffer);
+        if !save {
+            return;
+        }
+        let index = self.joint.selected();
+        match input.trim().parse::<i16>() {
+            Ok(value) => {
+                let applied = self.joint.set_selected_value(value);
+                if applied != value {
+                    self.log_queue.warn(format!(
+                        "{} 角度超出范围 {}, 已限制为 {applied}°",
+                        ServoState::name(index),
+                        ServoState::range_str(index)
+                    ));
+                }
+            }
+            Err(_) => self.log_queue.warn(format!("无效的角度: {input}")),
+        }
+    }
+
+    /// 开始输入屏幕显示文字
+    pub fn begin_lcd_text(&mut self) {
+        self.in_lcd_text_mode = true;
+        self.edit_buffer.clear();
+    }
+
+    /// 结束屏幕文字输入, `show` 为 true 时在屏幕上显示输入的文字
+    pub fn finish_lcd_text(&mut self, show: bool) {
+        self.in_lcd_text_mode = false;
+        let text = std::mem::take(&mut self.edit_buffer);
+        if show {
+            self.lcd.set_text(text.trim(), image::Rgb([255, 255, 255]));
+        }
+    }
+
+    /// 以指定名称保存当前目标角度, 同名姿势会被覆盖
+    pub fn save_pose(&mut self, name: &str) {
+        if name.is_empty() {
+            return;
+        }
+        self.config
+            .poses
+            .insert(name.to_string(), *self.joint.values());
+        self.save_config();
+        self.log_queue.info(format!("已保存姿势: {name}"));
+    }
+
+    /// 应用保存的姿势, 按插值速度平滑运动过去; 名称不存在时返回 false
+    pub fn apply_pose(&mut self, name: &str) -> bool {
+        match self.config.poses.get(name) {
+            Some(values) => {
+                self.joint.set_targets(values);
+                self.log_queue.info(format!("应用姿势: {name}"));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 开始/停止动作录制, 停止时保存到动作目录
+    pub fn toggle_motion_recording(&mut self) -> anyhow::Result<()> {
+        match self.motion_recorder.take() {
+            Some(recorder) => {
+                let filename = format!(
+                    "motion_{}.json",
+                    chrono::Local::now().format("%Y%m%d_%H%M%S")
+                );
+                let path = std::path::Path::new(motion::MOTION_DIR).join(&filename);
+                recorder.finish().save(&path)?;
+                self.log_queue.info(format!("动作已保存: {filename}"));
+            }
+            None => {
+                self.motion_player = None;
+                self.motion_recorder = Some(MotionRecorder::start(self.joint.values()));
+                self.log_queue.info("开始录制动作");
+            }
+        }
+        Ok(())
+    }
+
+    /// 打开动作文件选择弹窗
+    pub fn open_motion_select(&mut self) {
+        self.select_popup.open(
+            SelectPurpose::Motion,
+            " 选择动作 ",
+            "没有录制的动作, 按 [r] 开始录制",
+            motion::list_motion_files(),
+            None,
+        );
+    }
+
+    /// 打开眼睛颜色选择弹窗
+    pub fn open_eye_tint_select(&mut self) {
+        let current = self.config.eye_tint;
+        self.select_popup.open(
+            SelectPurpose::EyeTint,
+            " 眼睛颜色 ",
+            "",
+            EYE_TINT_PRESETS
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            EYE_TINT_PRESETS.iter().position(|(_, rgb)| *rgb == current),
+        );
+    }
+
+    /// 打开界面主题选择弹窗
+    pub fn open_theme_select(&mut self) {
+        let current = self.config.theme;
+        self.select_popup.open(
+            SelectPurpose::Theme,
+            " 界面主题 ",
+            "",
+            config::ThemeName::ALL
+                .iter()
+                .map(|theme| theme.label().to_string())
+                .collect(),
+            config::ThemeName::ALL.iter().position(|t| *t == current),
+        );
+    }
+
+    /// 打开配置档案选择弹窗
+    pub fn open_profile_select(&mut self) {
+        let mut profiles = config::list_profiles();
+        if !profiles.contains(&self.config.profile) {
+            profiles.push(self.config.profile.clone());
+        }
+        let current = profiles.iter().position(|p| *p == self.config.profile);
+        self.select_popup
+            .open(SelectPurpose::Profile, " 配置档案 ", "", profiles, current);
+    }
+
+    /// 切换到指定配置档案, 语音相关设置变化时重启语音
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 档案名称, 档案不存在时以默认配置新建
+    pub fn switch_profile(&mut self, name: &str) {
+        let mut new_config = config::AppConfig::load_profile(name);
+        self.cli.apply(&mut new_config);
+        let changed = self.config.changed_fields(&new_config);
+        let restart = changed
+            .iter()
+            .any(|field| VOICE_RESTART_FIELDS.contains(&field.as_str()));
+        self.apply_config(new_config);
+        self.config_mtime = self.config.modified_time();
+        if restart {
+            self.restart_voice();
+        }
+        if let Err(e) = config::set_active_profile(name) {
+            log::error!("Failed to save active profile: {e}");
+        }
+        log::info!("Switched to profile {name}");
+        self.log_queue.info(format!("已切换到配置档案: {name}"));
+    }
+
+    /// 打开显示模式选择弹窗
+    pub fn open_display_mode_select(&mut self) {
+        let current = self.lcd.mode();
+        self.select_popup.open(
+            SelectPurpose::DisplayMode,
+            " 显示模式 ",
+            "",
+            lcd::SELECTABLE_MODES
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            lcd::SELECTABLE_MODES
+                .iter()
+                .position(|(_, mode)| *mode == current),
+        );
+    }
+
+    /// 按配置的目录和间隔开始幻灯片, 失败时保持当前模式
+    pub fn start_slideshow(&mut self) {
+        let interval = Duration::from_secs(self.config.slideshow_interval_secs.max(1));
+        match self
+            .lcd
+            .load_slideshow(&self.config.slideshow_dir, interval)
+        {
+            Ok(count) => self.log_queue.info(format!("幻灯片: {count} 张图片")),
+            Err(e) => self.log_queue.error(format!("加载幻灯片失败: {e}")),
+        }
+    }
+
+    /// 打开测试图案选择弹窗
+    pub fn open_test_pattern_select(&mut self) {
+        let current = self.lcd.test_pattern();
+        self.select_popup.open(
+            SelectPurpose::TestPattern,
+            " 测试图案 ",
+            "",
+            lcd::TEST_PATTERNS
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            lcd::TEST_PATTERNS.iter().position(|(_, p)| *p == current),
+        );
+    }
+
+    /// 正在播放 GIF 时切回眼睛动画, 否则打开 GIF 选择弹窗
+    pub fn toggle_gif(&mut self) {
+        if self.lcd.mode() == DisplayMode::Gif {
+            self.lcd.set_mode(DisplayMode::Eyes);
+            return;
+        }
+        self.select_popup.open(
+            SelectPurpose::Gif,
+            " 选择动图 ",
+            "assets/images 下没有 GIF 文件",
+            lcd::list_gif_files(),
+            None,
+        );
+    }
+
+    /// 加载并播放动作文件
+    pub fn play_motion(&mut self, filename: &str) -> anyhow::Result<()> {
+        let path = std::path::Path::new(motion::MOTION_DIR).join(filename);
+        let motion = Motion::load(&path)?;
+        log::info!(
+            "Play motion {filename}: {} keyframes, {:?}",
+            motion.keyframes.len(),
+            motion.duration()
+        );
+        self.motion_recorder = None;
+        self.motion_player = Some(MotionPlayer::new(motion, false));
+        self.log_queue.info(format!("播放动作: {filename}"));
+        Ok(())
+    }
+
+    /// 切换动作循环播放
+    pub fn toggle_motion_loop(&mut self) {
+        if let Some(player) = &mut self.motion_player {
+            player.looping = !player.looping;
+        }
+    }
+
+    /// 停止动作回放和动画脚本
+    pub fn stop_motion(&mut self) {
+        if self.motion_player.take().is_some() {
+            self.log_queue.info("动作回放已停止");
+        }
+        #[cfg(feature = "scripting")]
+        if self.script.take().is_some() {
+            self.log_queue.info("脚本已停止");
+        }
+    }
+
+    /// 打开动画脚本选择弹窗
+    #[cfg(feature = "scripting")]
+    pub fn open_script_select(&mut self) {
+        self.select_popup.open(
+            SelectPurpose::Script,
+            " 运行脚本 ",
+            &format!("scripts 目录下没有 {} 脚本", script::SCRIPT_EXTENSION),
+            script::list_script_files(),
+            None,
+        );
+    }
+
+    /// 未启用 `scripting` 特性时只提示
+    #[cfg(not(feature = "scripting"))]
+    pub fn open_script_select(&mut self) {
+        self.log_queue.warn("脚本需要启用 scripting 特性编译");
+    }
+
+    /// 运行动画脚本, 正在运行的脚本先停止
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - 脚本目录下的文件名
+    #[cfg(feature = "scripting")]
+    pub fn run_script(&mut self, filename: &str) -> anyhow::Result<()> {
+        self.script = None;
+        let path = std::path::Path::new(script::SCRIPT_DIR).join(filename);
+        self.script = Some(script::ScriptRunner::start(&path)?);
+        log::info!("Run script {filename}");
+        self.log_queue.info(format!("运行脚本: {filename}"));
+        Ok(())
+    }
+
+    /// 按间隔输出一行遥测, 未配置输出目标时什么也不做
+    fn emit_telemetry(&mut self) {
+        let Some(sink) = &self.telemetry else {
+            return;
+        };
+        if self.telemetry_sent.elapsed() < Duration::from_millis(self.config.telemetry_interval_ms)
+        {
+            return;
+        }
+        self.telemetry_sent = Instant::now();
+        sink.emit(&serde_json::json!({
+            "time": chrono::Local::now().to_rfc3339(),
+            "connected": self.is_connected(),
+            "volume": self.voice_manager.as_ref().map(|vm| vm.volume_smoothed()),
+            "fps": self.comm_rate.fps,
+            "kbps": self.comm_rate.kbps,
+            "failures": self.comm_rate.failures,
+            "servo_commanded": self.joint.config().angles,
+            "servo_feedback": self.feedback,
+            "feedback_valid": self.feedback_valid,
+            "mode": format!("{:?}", self.lcd.mode()).to_lowercase(),
+        }));
+    }
+
+    /// 执行脚本发来的命令, 每次最多执行通道容量条, 脚本结束后释放, 运行出错时显示错误
+    #[cfg(feature = "scripting")]
+    fn poll_script(&mut self) {
+        let Some(runner) = &self.script else {
+            return;
+        };
+        // 先看线程是否结束, 结束后取到的命令不足上限说明通道已清空
+        let finished = runner.is_finished();
+        let commands: Vec<remote::RemoteCommand> = std::iter::from_fn(|| runner.try_recv())
+            .take(script::COMMAND_QUEUE)
+            .collect();
+        let finished = finished && commands.len() < script::COMMAND_QUEUE;
+        for command in commands {
+            self.apply_remote_command(command);
+        }
+        if !finished {
+            return;
+        }
+        if let Some(mut runner) = self.script.take() {
+            match runner.join() {
+                Ok(()) => self.log_queue.info("脚本执行完毕"),
+                Err(e) => {
+                    log::error!("Script failed: {e:#}");
+                    self.log_queue.error(format!("脚本错误: {e:#}"));
+                }
+            }
+        }
+    }
+
+    /// 确认弹窗选择
+    pub fn confirm_select(&mut self) {
+        let purpose = self.select_popup.purpose;
+        let selected = self.select_popup.selected_item().map(str::to_string);
+        self.select_popup.close();
+        let Some(item) = selected else {
+            return;
+        };
+
+        match purpose {
+            SelectPurpose::Microphone => {
+                self.config.speech_name = item;
+                self.save_config();
+                self.restart_voice();
+            }
+            SelectPurpose::Pose => {
+                self.apply_pose(&item);
+            }
+            SelectPurpose::Motion => {
+                if let Err(e) = self.play_motion(&item) {
+                    self.log_queue.error(format!("加载动作失败: {e}"));
+                }
+            }
+            SelectPurpose::EyeTint => {
+                if let Some((_, rgb)) = EYE_TINT_PRESETS.iter().find(|(name, _)| *name == item) {
+                    self.config.eye_tint = *rgb;
+                    self.lcd.set_eye_tint((rgb[0], rgb[1], rgb[2]));
+                    self.save_config();
+                }
+            }
+            SelectPurpose::Theme => {
+                if let Some(theme) = config::ThemeName::ALL
+                    .iter()
+                    .find(|theme| theme.label() == item)
+                {
+                    self.config.theme = *theme;
+                    self.save_config();
+                }
+            }
+            #[cfg(feature = "scripting")]
+            SelectPurpose::Script => {
+                if let Err(e) = self.run_script(&item) {
+                    self.log_queue.error(format!("脚本错误: {e}"));
+                }
+            }
+            SelectPurpose::Profile => {
+                if item != self.config.profile {
+                    self.switch_profile(&item);
+                }
+            }
+            SelectPurpose::DisplayMode => {
+                match lcd::SELECTABLE_MODES.iter().find(|(name, _)| *name == item) {
+                    // 测试图案还需选择具体图案
+                    Some((_, DisplayMode::TestPattern)) => self.open_test_pattern_select(),
+                    Some((_, DisplayMode::Slideshow)) => self.start_slideshow(),
+                    Some((_, mode)) => self.lcd.set_mode(*mode),
+                    None => {}
+                }
+            }
+            SelectPurpose::TestPattern => {
+                if let Some((_, pattern)) =
+                    lcd::TEST_PATTERNS.iter().find(|(name, _)| *name == item)
+                {
+                    self.lcd.set_test_pattern(*pattern);
+                    self.lcd.set_mode(DisplayMode::TestPattern);
+                }
+            }
+            SelectPurpose::Gif => {
+                let path = std::path::Path::new(lcd::IMAGE_DIR).join(&item);
+                match self.lcd.load_gif(&path.to_string_lossy()) {
+                    Ok(()) => self.log_queue.info(format!("播放动图: {item}")),
+                    Err(e) => self.log_queue.error(format!("加载动图失败: {e}")),
+                }
+            }
+        }
+    }
+
+    /// 按当前配置重启语音管理器
+    pub fn restart_voice(&mut self) {
+        if self.cli.no_voice {
+            return;
+        }
+        self.transcript = None;
+        let voice_config = self.config.voice_config();
+        let result = match &mut self.voice_manager {
+            Some(vm) => vm.restart(&voice_config),
+            None => VoiceManager::new(&voice_config).map(|vm| {
+                self.voice_manager = Some(vm);
+            }),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to restart voice: {e}");
         }
     }
 
     /// 连接机器人
+    ///
+    /// 在后台线程打开设备, 结果由 [App::tick] 取回; 打开设备卡住时界面不会被阻塞.
+    /// 正在连接时忽略, 避免重复按键启动多个连接
     pub fn connect_robot(&mut self) {
-        self.stop_comm_thread();
-        self.popup.show_connecting();
+        self.start_connect(false);
+    }
+
+    /// 启动连接线程
+    ///
+    /// # Arguments
+    ///
+    /// * `quiet` - 自动重试发起的连接, 不显示连接弹窗, 打开失败不写入错误日志
+    fn start_connect(&mut self, quiet: bool) {
+        if self.connect_rx.is_some() {
+            log::debug!("Connect ignored, previous result not collected yet");
+            return;
+        }
+        let Some(ticket) = self.connecting.try_begin() else {
+            log::debug!("Connect ignored, previous attempt still running");
+            return;
+        };
+        // 自动重试时未连接则无需断开, 断开会关闭用户正在查看的弹窗
+        if !quiet || self.comm_state.is_some() {
+            self.stop_comm_thread();
+        }
+        self.connect_quiet = quiet;
+        if !quiet {
+            self.popup.show_connecting();
+        }
+        let error_tx = self.comm_error_tx.clone();
 
         log::info!("Connecting to robot...");
-        let (tx, rx) = mpsc::sync_channel(1);
-        match robot::start_comm_thread(rx) {
-            Ok((state, handle)) => {
+        let (result_tx, result_rx) = mpsc::channel();
+        let transport_config = self.config.transport_config();
+        // 像素格式与本次连接的后端保持一致, 修改配置后重新连接才生效
+        self.lcd.set_pixel_format(transport_config.pixel_format);
+        let queue_depth = self.config.frame_queue_depth;
+        let queue_policy = self.config.frame_queue_policy;
+        std::thread::spawn(move || {
+            let (tx, rx) = robot::frame_queue(queue_depth, queue_policy);
+            let result = robot::start_comm_thread(rx, error_tx.clone(), transport_config)
+                .map(|(state, handle)| (state, handle, tx));
+            if let (Err(e), false) = (&result, quiet) {
+                let _ = error_tx.try_send(CommError::Open(format!("{e:#}")));
+            }
+            // 连接已取消时接收端已销毁, 等通信线程退出、释放设备后才允许下一次连接
+            if let Err(mpsc::SendError(Ok((state, handle, tx)))) = result_tx.send(result) {
+                drop(tx);
+                robot::stop_comm_thread(&state);
+                let _ = handle.join();
+            }
+            drop(ticket);
+        });
+        self.connect_rx = Some(result_rx);
+        self.connect_start = Some(Instant::now());
+    }
+
+    /// 配置文件被外部修改时重新加载
+    ///
+    /// 编辑设置期间暂不处理, 结束编辑后再检查; 本程序自己保存的内容与当前配置相同, 不会重复应用
+    fn poll_config_file(&mut self) {
+        if self.in_edit_settings_mode || self.config_checked.elapsed() < CONFIG_CHECK_INTERVAL {
+            return;
+        }
+        self.config_checked = Instant::now();
+        let mtime = self.config.modified_time();
+        if mtime.is_none() || mtime == self.config_mtime {
+            return;
+        }
+        self.config_mtime = mtime;
+
+        match self.config.reload() {
+            Ok(mut new_config) => {
+                self.cli.apply(&mut new_config);
+                let changed = self.config.changed_fields(&new_config);
+                if changed.is_empty() {
+                    return;
+                }
+                log::info!("Config file changed, reloaded: {}", changed.join(", "));
+                self.apply_config(new_config);
+                self.log_queue
+                    .info(format!("配置已重新加载: {}", changed.join(", ")));
+            }
+            Err(e) => log::warn!("Failed to reload config: {e}"),
+        }
+    }
+
+    /// 应用新配置, 立即更新屏幕、舵机和语音相关设置
+    ///
+    /// 通信方式、模型路径等需要重新连接或重启语音的设置在下次连接或重启时生效
+    pub fn apply_config(&mut self, mut config: config::AppConfig) {
+        // 命令行参数优先于配置文件
+        self.cli.apply(&mut config);
+        self.config = config;
+        self.lcd
+            .set_clock_color(image::Rgb(self.config.clock_color));
+        self.lcd
+            .set_transition_frames(self.config.transition_frames);
+        self.apply_tone();
+        self.apply_orientation();
+        let [r, g, b] = self.config.eye_tint;
+        self.lcd.set_eye_tint((r, g, b));
+        let (mood, position) = (
+            self.config.eyes_mood.clone(),
+            self.config.eyes_position.clone(),
+        );
+        self.set_eyes_mood(&mood);
+        self.set_eyes_position(&position);
+        self.joint.set_calibration(self.config.calibration);
+        if let Some(vm) = &self.voice_manager {
+            vm.set_wake_words(self.config.wake_words.clone());
+            vm.set_greeting(self.config.greeting.clone());
+        }
+    }
+
+    /// 是否正在连接设备, 已取消但连接线程尚未结束时也算
+    pub fn is_connecting(&self) -> bool {
+        self.connect_rx.is_some() || self.connecting.is_active()
+    }
+
+    /// 当前连接状态, 由通信线程和连接线程的句柄推出
+    pub fn connection_state(&self) -> ConnectionState {
+        if self.is_connected() {
+            ConnectionState::Connected
+        } else if self.is_connecting() {
+            ConnectionState::Connecting
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    /// 取回后台连接结果, 超时未连上时提示连接超时
+    fn poll_connect(&mut self) {
+        let Some(rx) = &self.connect_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                if self
+                    .connect_start
+                    .is_some_and(|start| start.elapsed() >= CONNECT_TIMEOUT)
+                {
+                    self.connect_start = None;
+                    log::warn!("Connecting to robot timed out");
+                    if !self.connect_quiet {
+                        self.popup.show_connect_timeout();
+                    }
+                }
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(anyhow::anyhow!("Connect thread exited unexpectedly"))
+            }
+        };
+        self.connect_rx = None;
+        self.connect_start = None;
+        let quiet = std::mem::take(&mut self.connect_quiet);
+
+        match result {
+            Ok((state, handle, tx)) => {
+                self.auto_connect = None;
+                // 连接后的第一帧必须发送
+                self.force_send = true;
                 self.comm_state = Some(state);
                 self.comm_thread = Some(handle);
                 self.comm_tx = Some(tx);
                 log::info!("Successfully connected to robot...");
+                self.log_queue.info("设备已连接");
             }
+            Err(e) if quiet => log::debug!("Auto connect failed: {e:#}"),
             Err(e) => {
                 log::warn!("Failed to start comm thread: {e:?}");
             }
         }
-        self.popup.hide();
+        if !quiet {
+            self.popup.hide();
+        }
+    }
+
+    /// 启动时自动连接, 失败后按退避时间在后台重试, 直到连接成功
+    ///
+    /// 用户手动连接或热插拔连接成功后同样停止重试
+    fn poll_auto_connect(&mut self) {
+        let Some(retry) = &mut self.auto_connect else {
+            return;
+        };
+        if self.comm_state.is_some() {
+            self.auto_connect = None;
+            return;
+        }
+        let now = Instant::now();
+        if now < retry.next || self.connecting.is_active() || self.connect_rx.is_some() {
+            return;
+        }
+        retry.next = now + retry.backoff;
+        retry.backoff = (retry.backoff * 2).min(AUTO_CONNECT_BACKOFF_MAX);
+        self.start_connect(true);
+    }
+
+    /// 按方向键调整当前舵机, 配置了加速曲线时按住越久步进越大
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - 1 增加, -1 减少
+    pub fn step_servo(&mut self, direction: i16) {
+        let held = KeyHold::press(&mut self.key_hold, direction).as_millis();
+        let step = self
+            .config
+            .servo_key_acceleration
+            .iter()
+            .take_while(|[after_ms, _]| held >= *after_ms as u128)
+            .last()
+            .map_or(1, |&[_, step]| step.clamp(1, i16::MAX as u32) as i16);
+        self.joint.adjust(direction * step);
     }
 
     /// 断开机器人连接
     pub fn stop_comm_thread(&mut self) {
+        // 放弃正在进行的连接
+        self.connect_rx = None;
+        self.connect_start = None;
         if let Some(tx) = self.comm_tx.take() {
             drop(tx);
         }
@@ -98,40 +1537,295 @@ impl App {
             let _ = handle.join();
         }
         self.comm_state = None;
+        self.comm_rate = CommRate::default();
+        self.feedback = None;
+        self.feedback_valid = false;
+        self.reconnecting = false;
         self.popup.hide();
     }
 
     /// 发送帧数据 (原始像素数据)
+    ///
+    /// 画面和关节配置都未变化时跳过发送, 连接后的第一帧和关节配置变化时强制发送;
+    /// 通信线程忙时用新帧覆盖未取走的旧帧, 只有通信线程已退出时返回错误
     pub fn send_frame(&mut self) -> anyhow::Result<()> {
-        if let Some(tx) = &self.comm_tx {
-            let pixels = self.lcd.frame_vec();
-            let config = self.joint.config();
-            tx.try_send((pixels, config))?;
+        // 画面在未连接时同样生成, 预览、录屏和网页预览不依赖设备;
+        // 借用内部缓冲区, 只有真正发送时才复制
+        self.lcd.generate_pixels();
+        let pixels = self.lcd.frame_bytes();
+        let hash = lcd::compute_hash(pixels);
+        self.lcd_preview.update(pixels, hash);
+        let capture_full = self
+            .capture
+            .as_mut()
+            .is_some_and(|capture| !capture.push(pixels, hash));
+        #[cfg(feature = "ws-preview")]
+        self.push_preview_frame(hash);
+        if capture_full {
+            self.log_queue.warn(format!(
+                "录屏已达 {} 帧上限, 自动停止",
+                capture::MAX_CAPTURE_FRAMES
+            ));
+            self.stop_capture();
+        }
+
+        if self.comm_tx.is_none() {
+            return Ok(());
+        }
+        // 动作回放和录制都按发送间隔进行
+        if let Some(player) = &mut self.motion_player {
+            if let Some(values) = player.poll() {
+                self.joint.set_targets(&values);
+            }
+            if player.is_finished() && !player.looping {
+                self.motion_player = None;
+                self.log_queue.info("动作回放结束");
+            }
+        }
+        if let Some(recorder) = &mut self.motion_recorder {
+            recorder.record(self.joint.values());
+        }
+        self.update_idle_motion();
+        // 每次发送前插值一步, 到达目标后关节配置不再变化
+        self.joint.step(self.config.servo_slew_rate);
+        let mut config = self.joint.config();
+        if self.idle {
+            config.enable = 0;
         }
+        let joint_bytes = config.as_bytes();
+
+        // 空闲时只发送一次掉电帧, 之后暂停发送直到有新的输入
+        if self.idle && self.last_joint_bytes == Some(joint_bytes) {
+            return Ok(());
+        }
+        let force = self.force_send || self.last_joint_bytes != Some(joint_bytes);
+        if !force && self.last_frame_hash == Some(hash) {
+            self.frames_skipped += 1;
+            return Ok(());
+        }
+
+        let Some(tx) = &self.comm_tx else {
+            return Ok(());
+        };
+        // 优先复用通信线程发送完的缓冲区
+        let mut frame = self
+            .comm_state
+            .as_ref()
+            .and_then(|state| state.try_recv_buffer())
+            .unwrap_or_default();
+        self.lcd.write_output(&mut frame);
+        if tx.send((frame, config)).is_err() {
+            anyhow::bail!("Comm thread stopped");
+        }
+        self.force_send = false;
+        self.last_frame_hash = Some(hash);
+        self.last_joint_bytes = Some(joint_bytes);
+        self.frames_sent += 1;
+        self.send_rate.record();
         Ok(())
     }
 
-    /// 截图并保存为 BMP 文件
-    pub fn take_screenshot(&mut self) -> anyhow::Result<()> {
+    /// 记录一次输入, 空闲掉电时恢复舵机并继续发送
+    pub fn note_input(&mut self) {
+        self.last_input = Instant::now();
+        self.stop_idle_motion();
+        if self.idle {
+            self.idle = false;
+            self.force_send = true;
+            self.log_queue.info("检测到操作, 舵机恢复");
+        }
+    }
+
+    /// 无操作超时后进入空闲, 动作回放期间不算空闲
+    fn check_idle(&mut self) {
+        let timeout = self.config.idle_timeout_secs;
+        if self.idle
+            || timeout == 0
+            || !self.is_connected()
+            || self.motion_player.is_some()
+            || self.last_input.elapsed() < Duration::from_secs(timeout)
+        {
+            return;
+        }
+        self.idle = true;
+        self.log_queue
+            .info(format!("{timeout} 秒无操作, 舵机掉电, 按任意键恢复"));
+    }
+
+    /// 无操作一段时间后开始呼吸动作, 条件不满足时停止, 运行中每帧更新目标角度
+    fn update_idle_motion(&mut self) {
+        let active = self.config.idle_motion
+            && !self.idle
+            && !self.in_servo_mode
+            && self.motion_player.is_none()
+            && self.motion_recorder.is_none()
+            && self.joint.is_enabled()
+            && self.last_input.elapsed() >= Duration::from_secs(self.config.idle_motion_delay_secs);
+        if !active {
+            self.stop_idle_motion();
+            return;
+        }
+        let motion = self.idle_motion.get_or_insert_with(|| {
+            IdleMotion::start(
+                self.joint.values(),
+                self.config.idle_motion_amplitude,
+                self.config.idle_motion_frequency,
+            )
+        });
+        self.joint.set_targets(&motion.poll());
+    }
+
+    /// 停止呼吸动作, 回到开始摆动时的姿势
+    fn stop_idle_motion(&mut self) {
+        if let Some(motion) = self.idle_motion.take() {
+            self.joint.set_targets(motion.base());
+        }
+    }
+
+    /// 按配置更新屏幕亮度和伽马, 有变化时强制重发当前画面
+    pub fn apply_tone(&mut self) {
+        if self.lcd.set_tone(self.config.brightness, self.config.gamma) {
+            self.force_send = true;
+        }
+    }
+
+    /// 按配置更新屏幕翻转和旋转, 有变化时强制重发当前画面
+    pub fn apply_orientation(&mut self) {
+        if self.lcd.set_orientation(self.config.orientation()) {
+            self.force_send = true;
+        }
+    }
+
+    /// 下一帧无论内容是否变化都发送
+    pub fn force_send(&mut self) {
+        self.force_send = true;
+    }
+
+    /// 把日志队列导出到带时间戳的文本文件, 结果写回日志
+    pub fn export_logs(&mut self) {
+        let path = std::path::Path::new(log_queue::LOG_EXPORT_DIR).join(format!(
+            "log_{}.txt",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        match self.log_queue.export(&path) {
+            Ok(()) => self
+                .log_queue
+                .info(format!("日志已导出: {}", path.display())),
+            Err(e) => self.log_queue.error(format!("日志导出失败: {e}")),
+        }
+    }
+
+    /// 开始录制屏幕画面, 按发送间隔录制, 不需要连接设备
+    pub fn start_capture(&mut self) {
+        self.capture = Some(FrameCapture::start());
+        self.log_queue.info("开始录屏");
+    }
+
+    /// 停止录制, 在后台编码为 GIF 保存到截图目录
+    pub fn stop_capture(&mut self) {
+        let Some(capture) = self.capture.take() else {
+            return;
+        };
+        if capture.is_empty() {
+            self.log_queue.warn("录屏没有画面, 已丢弃");
+            return;
+        }
+        let dir = std::path::Path::new(&self.config.screenshot_dir);
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            self.log_queue.error(format!("录屏保存失败: {e}"));
+            return;
+        }
+        let path = dir.join(format!(
+            "capture_{}.gif",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        self.log_queue
+            .info(format!("录屏结束, 正在编码 {} 帧", capture.len()));
+        self.capture_jobs.push(capture.finish(path));
+    }
+
+    /// 退出前停止录屏并等待所有编码完成, 避免文件写到一半
+    pub fn finish_captures(&mut self) {
+        self.stop_capture();
+        for job in self.capture_jobs.drain(..) {
+            match job.join() {
+                Ok(Ok(path)) => log::info!("Capture saved to: {}", path.display()),
+                Ok(Err(e)) => log::error!("Failed to save capture: {e}"),
+                Err(_) => log::error!("Capture encoder thread panicked"),
+            }
+        }
+    }
+
+    /// 开始/停止录屏
+    pub fn toggle_capture(&mut self) {
+        if self.capture.is_some() {
+            self.stop_capture();
+        } else {
+            self.start_capture();
+        }
+    }
+
+    /// 截图并保存到配置的截图目录, 返回保存的文件路径
+    pub fn take_screenshot(&mut self) -> anyhow::Result<std::path::PathBuf> {
         let pixels = self.lcd.frame_vec();
         let img = image::RgbImage::from_raw(FRAME_WIDTH as u32, FRAME_HEIGHT as u32, pixels)
             .ok_or_else(|| anyhow::anyhow!("Invalid image dimensions"))?;
-        // 生成文件名: screenshot_YYYYMMDD_HHMMSS.bmp
+        let dir = std::path::Path::new(&self.config.screenshot_dir);
+        std::fs::create_dir_all(dir)?;
+        // 生成文件名: screenshot_YYYYMMDD_HHMMSS.<格式>
+        let format = self.config.screenshot_format;
         let now = chrono::Local::now();
-        let filename = format!(
-            "./assets/images/screenshot/screenshot_{}.bmp",
-            now.format("%Y%m%d_%H%M%S")
-        );
-        img.save(&filename)?;
-        log::info!("Screenshot saved to: {filename}");
+        let path = dir.join(format!(
+            "screenshot_{}.{}",
+            now.format("%Y%m%d_%H%M%S"),
+            format.extension()
+        ));
+        img.save_with_format(&path, format.image_format())?;
+        log::info!("Screenshot saved to: {}", path.display());
 
-        Ok(())
+        Ok(path)
     }
 
     pub fn quit(&mut self) {
+        self.save_session();
         self.running = false;
     }
 
+    /// 把当前菜单页和舵机角度写入配置并保存, 下次启动时恢复
+    pub fn save_session(&mut self) {
+        self.config.last_menu = self.selected_menu;
+        // 呼吸动作中的角度只是临时摆动, 保存摆动前的姿势
+        self.config.last_servo_values = match &self.idle_motion {
+            Some(motion) => *motion.base(),
+            None => *self.joint.values(),
+        };
+        self.save_config();
+    }
+
+    /// 请求退出, 已连接设备且开启了退出确认时先弹窗确认
+    pub fn request_quit(&mut self) {
+        if self.config.confirm_quit && self.is_connected() {
+            self.in_quit_confirm = true;
+            self.popup.show_quit_confirm();
+        } else {
+            self.quit();
+        }
+    }
+
+    /// 处理退出确认结果
+    ///
+    /// # Arguments
+    ///
+    /// * `confirmed` - true 时断开设备并退出, false 时取消
+    pub fn confirm_quit(&mut self, confirmed: bool) {
+        self.in_quit_confirm = false;
+        self.popup.hide();
+        if confirmed {
+            self.stop_comm_thread();
+            self.quit();
+        }
+    }
+
     pub fn next_menu(&mut self) {
         let items = MenuItem::all();
         let i = match self.menu_state.selected() {
@@ -142,6 +1836,15 @@ impl App {
         self.selected_menu = items[i];
     }
 
+    /// 选中指定菜单项, 超出范围时忽略
+    pub fn select_menu(&mut self, index: usize) {
+        let items = MenuItem::all();
+        if let Some(&item) = items.get(index) {
+            self.menu_state.select(Some(index));
+            self.selected_menu = item;
+        }
+    }
+
     pub fn prev_menu(&mut self) {
         let items = MenuItem::all();
         let i = match self.menu_state.selected() {
@@ -159,7 +1862,7 @@ impl App {
 
     /// 设置项数量
     pub fn settings_item_count(&self) -> usize {
-        3 // Wifi名称, Wifi密码, 麦克风名称
+        settings::items().len()
     }
 
     /// 设置模式: 上一项
@@ -175,23 +1878,41 @@ impl App {
     }
 
     /// 保存设置项编辑内容
+    ///
+    /// 输入无效时保持编辑状态, 错误说明显示在设置项后面
     pub fn save_settings_edit(&mut self) {
-        match self.settings_selected {
-            0 => self.config.wifi_ssid = self.edit_buffer.clone(),
-            1 => self.config.wifi_password = self.edit_buffer.clone(),
-            2 => self.config.speech_name = self.edit_buffer.clone(),
-            _ => {}
-        }
-        if let Err(e) = self.config.save() {
-            log::error!("Failed to save settings: {e}");
+        let items = settings::items();
+        if let Some(settings::SettingKind::Edit(apply)) =
+            items.get(self.settings_selected).map(|item| &item.kind)
+        {
+            let input = self.edit_buffer.clone();
+            if let Err(e) = apply(self, &input) {
+                log::warn!(
+                    "Invalid input for {}: {e}",
+                    items[self.settings_selected].label
+                );
+                self.settings_error = Some(e);
+                return;
+            }
         }
+        self.settings_error = None;
+        self.save_config();
         self.in_edit_settings_mode = false;
         self.edit_buffer.clear();
     }
 
+    /// 清零所有舵机校准偏移并保存
+    pub fn reset_calibration(&mut self) {
+        self.config.calibration = [0.0; robot::SERVO_COUNT];
+        self.joint.set_calibration(self.config.calibration);
+        self.save_config();
+        self.log_queue.info("舵机校准已重置");
+    }
+
     /// 取消设置项编辑
     pub fn cancel_settings_edit(&mut self) {
         self.in_edit_settings_mode = false;
+        self.settings_error = None;
         self.edit_buffer.clear();
     }
 
@@ -199,6 +1920,16 @@ impl App {
         self.comm_state.is_some()
     }
 
+    /// 帧队列状态, 未连接时返回 `None`
+    pub fn frame_queue_stats(&self) -> Option<robot::QueueStats> {
+        self.comm_tx.as_ref().map(|tx| tx.stats())
+    }
+
+    /// 已连接设备的描述信息
+    pub fn device_info(&self) -> Option<&robot::DeviceInfo> {
+        self.comm_state.as_ref().map(|state| &state.device_info)
+    }
+
     pub fn load_image_from_file(&mut self, path: &str) -> anyhow::Result<()> {
         self.lcd.load_image(path)?;
         self.lcd.set_mode(DisplayMode::Static);
@@ -206,6 +1937,232 @@ impl App {
     }
 }
 
+impl Drop for App {
+    /// 异常退出 (panic 展开) 时也要让通信线程停下, 舵机收到停止帧
+    fn drop(&mut self) {
+        self.stop_comm_thread();
+    }
+}
+
+/// 相邻两次按键事件间隔不超过该值时视为一直按住 (终端不上报按键松开)
+const KEY_REPEAT_GAP: Duration = Duration::from_millis(150);
+
+/// 方向键按住时长跟踪
+#[derive(Debug)]
+pub struct KeyHold {
+    direction: i16,
+    started: Instant,
+    last: Instant,
+}
+
+impl KeyHold {
+    /// 记录一次按键, 返回已按住的时长; 方向改变或间隔过长时重新计时
+    ///
+    /// # Arguments
+    ///
+    /// * `hold` - 上一次的按住状态
+    /// * `direction` - 按键方向, 1 或 -1
+    fn press(hold: &mut Option<KeyHold>, direction: i16) -> Duration {
+        let now = Instant::now();
+        match hold {
+            Some(h) if h.direction == direction && now - h.last <= KEY_REPEAT_GAP => {
+                h.last = now;
+                now - h.started
+            }
+            _ => {
+                *hold = Some(KeyHold {
+                    direction,
+                    started: now,
+                    last: now,
+                });
+                Duration::ZERO
+            }
+        }
+    }
+}
+
+/// 名称列表中 `current` 的下一项, 找不到时返回第一项
+fn next_name(names: &[&'static str], current: &str) -> &'static str {
+    let index = names
+        .iter()
+        .position(|name| *name == current)
+        .map_or(0, |i| (i + 1) % names.len());
+    names[index]
+}
+
+/// 发送帧率统计, 每秒更新一次
+#[derive(Debug)]
+pub struct SendRate {
+    count: u32,
+    window_start: Instant,
+    rate: f32,
+}
+
+impl Default for SendRate {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            window_start: Instant::now(),
+            rate: 0.0,
+        }
+    }
+}
+
+impl SendRate {
+    /// 记录一次发送
+    pub fn record(&mut self) {
+        self.count += 1;
+        self.update();
+    }
+
+    /// 当前发送帧率 (fps)
+    pub fn rate(&self) -> f32 {
+        if self.window_start.elapsed() > Duration::from_secs(2) {
+            // 超过两个统计周期没有发送, 视为已停止
+            0.0
+        } else {
+            self.rate
+        }
+    }
+
+    fn update(&mut self) {
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            self.rate = self.count as f32 / elapsed.as_secs_f32();
+            self.count = 0;
+            self.window_start = Instant::now();
+        }
+    }
+}
+
+/// 通信线程吞吐统计, 按一秒滚动窗口计算
+#[derive(Debug)]
+pub struct CommRate {
+    last: robot::CommStatsSnapshot,
+    window_start: Instant,
+    pub fps: f32,
+    pub kbps: f32,
+    pub failures: u64,
+}
+
+impl Default for CommRate {
+    fn default() -> Self {
+        Self {
+            last: robot::CommStatsSnapshot::default(),
+            window_start: Instant::now(),
+            fps: 0.0,
+            kbps: 0.0,
+            failures: 0,
+        }
+    }
+}
+
+impl CommRate {
+    /// 用最新计数更新, 满一秒时重新计算速率
+    pub fn update(&mut self, current: robot::CommStatsSnapshot) {
+        self.failures = current.failures;
+        let elapsed = self.window_start.elapsed();
+        if elapsed < Duration::from_secs(1) {
+            return;
+        }
+        let secs = elapsed.as_secs_f32();
+        self.fps = (current.frames - self.last.frames) as f32 / secs;
+        self.kbps = (current.bytes - self.last.bytes) as f32 / 1024.0 / secs;
+        self.last = current;
+        self.window_start = Instant::now();
+    }
+}
+
+/// 选择弹窗用途
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectPurpose {
+    #[default]
+    Microphone,
+    Pose,
+    Motion,
+    Gif,
+    DisplayMode,
+    TestPattern,
+    EyeTint,
+    Theme,
+    Profile,
+    #[cfg(feature = "scripting")]
+    Script,
+}
+
+/// 列表选择弹窗
+#[derive(Debug, Default)]
+pub struct SelectPopup {
+    pub visible: bool,
+    pub purpose: SelectPurpose,
+    pub title: String,
+    pub empty_message: String,
+    pub items: Vec<String>,
+    pub state: ListState,
+}
+
+impl SelectPopup {
+    /// 打开弹窗并填充选项
+    pub fn open(
+        &mut self,
+        purpose: SelectPurpose,
+        title: &str,
+        empty_message: &str,
+        items: Vec<String>,
+        selected: Option<usize>,
+    ) {
+        self.purpose = purpose;
+        self.title = title.to_string();
+        self.empty_message = empty_message.to_string();
+        let selected = if items.is_empty() {
+            None
+        } else {
+            Some(selected.unwrap_or(0))
+        };
+        self.items = items;
+        self.state.select(selected);
+        self.visible = true;
+    }
+
+    /// 关闭弹窗
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    /// 是否可见
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// 上一项
+    pub fn prev(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let count = self.items.len();
+        let i = self.state.selected().unwrap_or(0);
+        self.state.select(Some((i + count - 1) % count));
+    }
+
+    /// 下一项
+    pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let count = self.items.len();
+        let i = self.state.selected().unwrap_or(0);
+        self.state.select(Some((i + 1) % count));
+    }
+
+    /// 当前选中项
+    pub fn selected_item(&self) -> Option<&str> {
+        self.state
+            .selected()
+            .and_then(|i| self.items.get(i))
+            .map(String::as_str)
+    }
+}
+
 /// 通用弹窗配置
 #[derive(Debug, Clone)]
 pub struct PopupConfig {
@@ -271,7 +2228,7 @@ impl Popup {
     pub fn show_connecting(&mut self) {
         self.configure(PopupConfig {
             title: " 连接设备 ".to_string(),
-            content: "正在通过 USB 连接设备...".to_string(),
+            content: "正在通过 USB 连接设备... (Esc 取消)".to_string(),
             width: 40,
             height: 5,
             border_color: ratatui::style::Color::Green,
@@ -280,4 +2237,91 @@ impl Popup {
         });
         self.show();
     }
+
+    /// 快速设置连接超时弹窗
+    pub fn show_connect_timeout(&mut self) {
+        self.configure(PopupConfig {
+            title: " 连接超时 ".to_string(),
+            content: "连接超时, 请检查设备 (Esc 关闭)".to_string(),
+            width: 40,
+            height: 5,
+            border_color: ratatui::style::Color::Red,
+            bg_color: ratatui::style::Color::DarkGray,
+            title_color: ratatui::style::Color::Red,
+        });
+        self.show();
+    }
+
+    /// 快速设置退出确认弹窗
+    pub fn show_quit_confirm(&mut self) {
+        self.configure(PopupConfig {
+            title: " 退出 ".to_string(),
+            content: "设备已连接, 确定退出? [Y/N]".to_string(),
+            width: 40,
+            height: 5,
+            border_color: ratatui::style::Color::Yellow,
+            bg_color: ratatui::style::Color::DarkGray,
+            title_color: ratatui::style::Color::Yellow,
+        });
+        self.show();
+    }
+
+    /// 快速设置断线重连弹窗
+    pub fn show_reconnecting(&mut self) {
+        self.configure(PopupConfig {
+            title: " 连接断开 ".to_string(),
+            content: "重新连接中... (Esc 取消)".to_string(),
+            width: 40,
+            height: 5,
+            border_color: ratatui::style::Color::Yellow,
+            bg_color: ratatui::style::Color::DarkGray,
+            title_color: ratatui::style::Color::Yellow,
+        });
+        self.show();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 使用临时配置文件创建不启动后台服务的 App
+    fn test_app(name: &str, cli: Args) -> (App, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("ele_bot_{name}_{}", std::process::id()));
+        let path = dir.join("config.toml");
+        let _ = std::fs::remove_file(&path);
+        let mut config = config::AppConfig::load_file(&path);
+        config.auto_connect = false;
+        config.connect_on_startup = true;
+        config.http_api_addr.clear();
+        config.preview_server_addr.clear();
+        config.osc_bind_addr.clear();
+        config.save().unwrap();
+        cli.apply(&mut config);
+
+        let (_log_tx, log_rx) = mpsc::channel();
+        (App::new(config, None, log_rx, cli), path)
+    }
+
+    #[test]
+    fn quit_keeps_cli_overrides_out_of_config_file() {
+        let cli = Args {
+            simulate: true,
+            no_connect: true,
+            ..Default::default()
+        };
+        let (mut app, path) = test_app("quit", cli);
+        assert_eq!(app.config.transport, TransportKind::Sim);
+        assert!(!app.config.connect_on_startup);
+
+        app.selected_menu = MenuItem::all()[1];
+        app.quit();
+
+        let saved = config::AppConfig::load_file(&path);
+        assert_eq!(saved.transport, TransportKind::Usb);
+        assert!(saved.connect_on_startup);
+        // 会话状态仍然保存
+        assert_eq!(saved.last_menu, MenuItem::all()[1]);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
 }
@@ -0,0 +1,475 @@
+//! MQTT 控制与状态上报
+//!
+//! 后台线程连接 [AppConfig::mqtt_broker](super::config::AppConfig::mqtt_broker),
+//! 订阅 `<前缀>/command` 并把命令转换为 [RemoteCommand] 交给主循环; 主循环定时生成的状态
+//! 以保留消息发布到 `<前缀>/status`. 断开后每隔 [RECONNECT_DELAY] 重连
+//!
+//! 命令为 JSON, 各字段可同时出现:
+//! `{"mood": "happy", "angles": [0, 0, 0, 0, 0, 0], "image": "./assets/images/a.png"}`
+//!
+//! 只实现了 MQTT 3.1.1 的 QoS 0 收发, 不支持用户名密码和 TLS
+
+use super::remote::{self, RemoteCommand};
+use crate::robot::{lcd, SERVO_COUNT};
+use serde::Deserialize;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 默认端口
+const DEFAULT_PORT: u16 = 1883;
+/// 心跳间隔, 连接时告知服务器
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+/// 断开后的重连间隔
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// 读取超时, 同时是检查退出标志和待发布状态的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 连接超时
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// 写入超时, 服务器不再读取时断开重连, 退出时也不会一直阻塞
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+/// 发送 PINGREQ 后等待 PINGRESP 的时间, 超时视为连接已断开
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 报文类型 (固定报头高 4 位)
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const SUBSCRIBE: u8 = 0x82;
+const PINGREQ: u8 = 0xC0;
+const PINGRESP: u8 = 0xD0;
+/// PUBLISH 报文的保留标志
+const RETAIN: u8 = 0x01;
+
+/// 命令消息
+#[derive(Deserialize)]
+struct CommandMessage {
+    mood: Option<String>,
+    angles: Option<[i16; SERVO_COUNT]>,
+    image: Option<String>,
+}
+
+/// MQTT 桥, 销毁时停止后台线程
+pub struct MqttBridge {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    rx: mpsc::Receiver<RemoteCommand>,
+    status: Arc<Mutex<Option<String>>>, // 待发布的最新状态
+}
+
+#[allow(dead_code)]
+impl MqttBridge {
+    /// 启动后台线程, 连接失败时在线程内重试
+    ///
+    /// # Arguments
+    ///
+    /// * `broker` - 服务器地址, 如 `mqtt://192.168.1.2:1883`, 省略端口时为 1883
+    /// * `prefix` - 主题前缀
+    pub fn start(broker: &str, prefix: &str) -> Self {
+        let address = broker
+            .trim_start_matches("mqtt://")
+            .trim_start_matches("tcp://")
+            .to_string();
+        let address = if address.contains(':') {
+            address
+        } else {
+            format!("{address}:{DEFAULT_PORT}")
+        };
+        let prefix = prefix.trim_end_matches('/').to_string();
+
+        let running = Arc::new(AtomicBool::new(true));
+        let (tx, rx) = mpsc::channel();
+        let status = Arc::new(Mutex::new(None));
+        let status_clone = status.clone();
+        let running_clone = running.clone();
+        let handle = thread::spawn(move || {
+            while running_clone.load(Ordering::Relaxed) {
+                match run_session(&address, &prefix, &running_clone, &tx, &status_clone) {
+                    Ok(()) => break,
+                    Err(e) => log::warn!("MQTT session with {address} ended: {e}"),
+                }
+                // 分段等待, 退出时不必等满重连间隔
+                let start = Instant::now();
+                while running_clone.load(Ordering::Relaxed) && start.elapsed() < RECONNECT_DELAY {
+                    thread::sleep(POLL_INTERVAL);
+                }
+            }
+        });
+
+        Self {
+            running,
+            handle: Some(handle),
+            rx,
+            status,
+        }
+    }
+
+    /// 非阻塞获取命令
+    pub fn try_recv(&self) -> Option<RemoteCommand> {
+        self.rx.try_recv().ok()
+    }
+
+    /// 发布状态, 未连接时只保留最新的一条
+    pub fn publish_status(&self, status: String) {
+        *self.status.lock().unwrap_or_else(|e| e.into_inner()) = Some(status);
+    }
+
+    /// 停止后台线程
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MqttBridge {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 连接服务器并收发消息, 直到出错或退出标志被清除 (返回 `Ok`)
+fn run_session(
+    address: &str,
+    prefix: &str,
+    running: &AtomicBool,
+    tx: &mpsc::Sender<RemoteCommand>,
+    status: &Mutex<Option<String>>,
+) -> anyhow::Result<()> {
+    let socket = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Cannot resolve {address}"))?;
+    let mut stream = TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    // CONNECT: 协议名、版本 4、clean session、心跳, 载荷为客户端 ID
+    let mut connect = Vec::new();
+    put_str(&mut connect, "MQTT");
+    connect.push(4);
+    connect.push(0x02);
+    connect.extend_from_slice(&(KEEP_ALIVE.as_secs() as u16).to_be_bytes());
+    put_str(&mut connect, &format!("ele_bot-{}", std::process::id()));
+    write_packet(&mut stream, CONNECT, &connect)?;
+
+    let mut buffer = Vec::new();
+    let deadline = Instant::now() + CONNECT_TIMEOUT;
+    let (header, body) = loop {
+        if let Some(packet) = next_packet(&mut buffer) {
+            break packet;
+        }
+        anyhow::ensure!(Instant::now() < deadline, "CONNACK timed out");
+        read_available(&mut stream, &mut buffer)?;
+    };
+    anyhow::ensure!(
+        header & 0xF0 == CONNACK && body.get(1) == Some(&0),
+        "Broker refused connection: {:?}",
+        body.get(1)
+    );
+
+    // SUBSCRIBE: 报文 ID 1, QoS 0
+    let command_topic = format!("{prefix}/command");
+    let mut subscribe = vec![0, 1];
+    put_str(&mut subscribe, &command_topic);
+    subscribe.push(0);
+    write_packet(&mut stream, SUBSCRIBE, &subscribe)?;
+    log::info!("MQTT connected to {address}, subscribed {command_topic}");
+
+    let status_topic = format!("{prefix}/status");
+    let mut keep_alive = KeepAlive::new(Instant::now());
+    while running.load(Ordering::Relaxed) {
+        read_available(&mut stream, &mut buffer)?;
+        while let Some((header, body)) = next_packet(&mut buffer) {
+            match header & 0xF0 {
+                PUBLISH => handle_publish(header, &body, tx),
+                PINGRESP => keep_alive.pong(),
+                _ => {}
+            }
+        }
+
+        // 只发布最新状态
+        let latest = status.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(latest) = latest {
+            let mut publish = Vec::new();
+            put_str(&mut publish, &status_topic);
+            publish.extend_from_slice(latest.as_bytes());
+            write_packet(&mut stream, PUBLISH | RETAIN, &publish)?;
+            keep_alive.sent(Instant::now());
+        }
+        if keep_alive.poll(Instant::now())? {
+            write_packet(&mut stream, PINGREQ, &[])?;
+            keep_alive.ping(Instant::now());
+        }
+    }
+    Ok(())
+}
+
+/// 心跳状态: 空闲半个心跳间隔后发送 PINGREQ, 之后 [PING_TIMEOUT] 内没有收到 PINGRESP
+/// 视为连接已断开, 用于发现半开连接
+struct KeepAlive {
+    last_sent: Instant,
+    ping_sent: Option<Instant>,
+}
+
+impl KeepAlive {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_sent: now,
+            ping_sent: None,
+        }
+    }
+
+    /// 记录发送了报文
+    fn sent(&mut self, now: Instant) {
+        self.last_sent = now;
+    }
+
+    /// 记录发送了 PINGREQ
+    fn ping(&mut self, now: Instant) {
+        self.last_sent = now;
+        self.ping_sent = Some(now);
+    }
+
+    /// 收到 PINGRESP
+    fn pong(&mut self) {
+        self.ping_sent = None;
+    }
+
+    /// 返回是否需要发送 PINGREQ, 等待 PINGRESP 超时时返回错误
+    fn poll(&self, now: Instant) -> anyhow::Result<bool> {
+        if let Some(sent) = self.ping_sent {
+            anyhow::ensure!(
+                now.duration_since(sent) < PING_TIMEOUT,
+                "No PINGRESP within {PING_TIMEOUT:?}"
+            );
+            return Ok(false);
+        }
+        Ok(now.duration_since(self.last_sent) >= KEEP_ALIVE / 2)
+    }
+}
+
+/// 解析收到的 PUBLISH 报文并转换为命令
+fn handle_publish(header: u8, body: &[u8], tx: &mpsc::Sender<RemoteCommand>) {
+    let Some(topic_len) = body
+        .get(..2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+    else {
+        return;
+    };
+    // QoS 大于 0 时主题后有 2 字节报文 ID
+    let qos = (header >> 1) & 0x03;
+    let offset = 2 + topic_len + if qos > 0 { 2 } else { 0 };
+    let Some(payload) = body.get(offset..) else {
+        return;
+    };
+
+    let message: CommandMessage = match serde_json::from_slice(payload) {
+        Ok(message) => message,
+        Err(e) => {
+            log::warn!("Invalid MQTT command: {e}");
+            return;
+        }
+    };
+    if let Some(mood) = message.mood {
+        if lcd::mood_from_name(&mood).is_some() {
+            let _ = tx.send(RemoteCommand::Mood(mood));
+        } else {
+            log::warn!("Unknown mood from MQTT: {mood}");
+        }
+    }
+    if let Some(angles) = message.angles {
+        match remote::check_angles(&angles) {
+            Ok(()) => {
+                let _ = tx.send(RemoteCommand::Servo(angles));
+            }
+            Err(e) => log::warn!("Invalid angles from MQTT: {e}"),
+        }
+    }
+    if let Some(path) = message.image {
+        match std::fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| lcd::decode_image(&data))
+        {
+            Ok(pixels) => {
+                let _ = tx.send(RemoteCommand::Image(pixels));
+            }
+            Err(e) => log::warn!("Failed to load image {path} from MQTT: {e}"),
+        }
+    }
+}
+
+/// 读取已到达的数据, 读超时不算错误, 连接关闭时返回错误
+fn read_available(stream: &mut TcpStream, buffer: &mut Vec<u8>) -> anyhow::Result<()> {
+    let mut chunk = [0u8; 4096];
+    match stream.read(&mut chunk) {
+        Ok(0) => anyhow::bail!("Connection closed by broker"),
+        Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+        Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+/// 从缓冲区取出一个完整报文 (固定报头首字节, 剩余部分), 数据不完整时返回 `None`
+fn next_packet(buffer: &mut Vec<u8>) -> Option<(u8, Vec<u8>)> {
+    // 剩余长度为变长编码, 最多 4 字节
+    let mut length = 0usize;
+    let mut header_len = 1;
+    loop {
+        let byte = *buffer.get(header_len)?;
+        length |= ((byte & 0x7F) as usize) << (7 * (header_len - 1));
+        header_len += 1;
+        if byte & 0x80 == 0 || header_len > 4 {
+            break;
+        }
+    }
+    if buffer.len() < header_len + length {
+        return None;
+    }
+    let header = buffer[0];
+    let body = buffer[header_len..header_len + length].to_vec();
+    buffer.drain(..header_len + length);
+    Some((header, body))
+}
+
+/// 写入一个报文, 格式见 [encode_packet]
+fn write_packet(stream: &mut TcpStream, header: u8, body: &[u8]) -> anyhow::Result<()> {
+    stream.write_all(&encode_packet(header, body))?;
+    Ok(())
+}
+
+/// 编码一个报文: 固定报头、变长编码的剩余长度、内容
+fn encode_packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut length = body.len();
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(body);
+    packet
+}
+
+/// 写入带 2 字节长度前缀的 UTF-8 字符串
+fn put_str(buffer: &mut Vec<u8>, text: &str) {
+    buffer.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    buffer.extend_from_slice(text.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_length_round_trip() {
+        // 剩余长度和对应的编码字节数
+        for (length, length_bytes) in [(0, 1), (127, 1), (128, 2), (16383, 2), (16384, 3)] {
+            let body: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let mut buffer = encode_packet(PUBLISH, &body);
+            assert_eq!(buffer.len(), 1 + length_bytes + length, "length {length}");
+            assert_eq!(next_packet(&mut buffer), Some((PUBLISH, body)));
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn partial_packet_waits_for_more_data() {
+        let packet = encode_packet(PUBLISH, &[7; 200]);
+        let mut buffer = Vec::new();
+        for &byte in &packet[..packet.len() - 1] {
+            buffer.push(byte);
+            assert_eq!(next_packet(&mut buffer), None);
+        }
+        // 数据不完整时不消耗缓冲区
+        assert_eq!(buffer.len(), packet.len() - 1);
+        buffer.push(packet[packet.len() - 1]);
+        assert_eq!(next_packet(&mut buffer), Some((PUBLISH, vec![7; 200])));
+    }
+
+    #[test]
+    fn consecutive_packets_are_split() {
+        let mut buffer = encode_packet(CONNACK, &[0, 0]);
+        buffer.extend(encode_packet(PINGREQ, &[]));
+        assert_eq!(next_packet(&mut buffer), Some((CONNACK, vec![0, 0])));
+        assert_eq!(next_packet(&mut buffer), Some((PINGREQ, vec![])));
+        assert_eq!(next_packet(&mut buffer), None);
+    }
+
+    /// 构造 PUBLISH 报文内容, QoS 大于 0 时带报文 ID
+    fn publish_body(qos: u8, payload: &str) -> Vec<u8> {
+        let mut body = Vec::new();
+        put_str(&mut body, "ele_bot/command");
+        if qos > 0 {
+            body.extend_from_slice(&[0x12, 0x34]);
+        }
+        body.extend_from_slice(payload.as_bytes());
+        body
+    }
+
+    fn received(header: u8, body: &[u8]) -> Vec<RemoteCommand> {
+        let (tx, rx) = mpsc::channel();
+        handle_publish(header, body, &tx);
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn publish_qos0() {
+        let body = publish_body(0, r#"{"mood": "happy"}"#);
+        let commands = received(PUBLISH, &body);
+        assert!(matches!(&commands[..], [RemoteCommand::Mood(mood)] if mood == "happy"));
+    }
+
+    #[test]
+    fn publish_qos1_skips_packet_id() {
+        let body = publish_body(1, r#"{"angles": [5, 0, 0, 0, 0, 0]}"#);
+        let commands = received(PUBLISH | 0x02, &body);
+        assert!(matches!(
+            &commands[..],
+            [RemoteCommand::Servo([5, 0, 0, 0, 0, 0])]
+        ));
+    }
+
+    #[test]
+    fn keep_alive_pings_when_idle() {
+        let start = Instant::now();
+        let mut keep_alive = KeepAlive::new(start);
+        assert!(!keep_alive.poll(start + KEEP_ALIVE / 4).unwrap());
+        // 发布状态也算活动
+        keep_alive.sent(start + KEEP_ALIVE / 4);
+        assert!(!keep_alive.poll(start + KEEP_ALIVE / 2).unwrap());
+        assert!(keep_alive.poll(start + KEEP_ALIVE).unwrap());
+    }
+
+    #[test]
+    fn keep_alive_detects_missing_pingresp() {
+        let start = Instant::now();
+        let mut keep_alive = KeepAlive::new(start);
+        keep_alive.ping(start);
+        // 等待回复期间不重复发送
+        assert!(!keep_alive.poll(start + PING_TIMEOUT / 2).unwrap());
+        assert!(keep_alive.poll(start + PING_TIMEOUT).is_err());
+
+        keep_alive.pong();
+        assert!(keep_alive.poll(start + PING_TIMEOUT).is_ok());
+    }
+
+    #[test]
+    fn publish_invalid_payload_is_ignored() {
+        assert!(received(PUBLISH, &publish_body(0, "not json")).is_empty());
+        assert!(received(PUBLISH, &publish_body(0, r#"{"mood": "sad"}"#)).is_empty());
+        assert!(received(PUBLISH, &[0]).is_empty());
+    }
+}
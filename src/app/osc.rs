@@ -0,0 +1,324 @@
+//! OSC 控制
+//!
+//! 在后台线程监听 UDP 端口 [AppConfig::osc_bind_addr](super::config::AppConfig::osc_bind_addr),
+//! 把 OSC 消息转换为 [RemoteCommand] 交给主循环. 地址前缀默认为 `/electronbot`:
+//!
+//! - `<前缀>/servo/<序号>` 参数为数字, 设置单个舵机角度, 超出范围时截断
+//! - `<前缀>/mood` 参数为字符串, 设置表情
+//!
+//! 支持 OSC bundle, 但忽略其中的时间标签, 收到即执行
+
+use super::remote::RemoteCommand;
+use crate::robot::{lcd, ServoState, SERVO_COUNT};
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// 读超时, 同时是检查退出标志的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 最大报文长度
+const MAX_PACKET_BYTES: usize = 4096;
+
+/// OSC 参数
+#[derive(Debug, Clone, PartialEq)]
+enum OscArg {
+    Float(f64),
+    Str(String),
+}
+
+/// OSC 服务, 销毁时停止后台线程
+pub struct OscServer {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    rx: mpsc::Receiver<RemoteCommand>,
+}
+
+#[allow(dead_code)]
+impl OscServer {
+    /// 绑定 UDP 端口并启动接收线程
+    ///
+    /// # Arguments
+    ///
+    /// * `bind_addr` - 监听地址, 如 `127.0.0.1:9000`
+    /// * `prefix` - OSC 地址前缀, 如 `/electronbot`
+    pub fn start(bind_addr: &str, prefix: &str) -> anyhow::Result<Self> {
+        let socket = UdpSocket::bind(bind_addr)?;
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+        log::info!("OSC listening on {bind_addr}");
+
+        let prefix = prefix.trim_end_matches('/').to_string();
+        let running = Arc::new(AtomicBool::new(true));
+        let (tx, rx) = mpsc::channel();
+        let running_clone = running.clone();
+        let handle = thread::spawn(move || {
+            let mut buffer = [0u8; MAX_PACKET_BYTES];
+            while running_clone.load(Ordering::Relaxed) {
+                let len = match socket.recv_from(&mut buffer) {
+                    Ok((len, _)) => len,
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        continue
+                    }
+                    Err(e) => {
+                        log::warn!("OSC receive failed: {e}");
+                        thread::sleep(POLL_INTERVAL);
+                        continue;
+                    }
+                };
+                let mut messages = Vec::new();
+                if let Err(e) = parse_packet(&buffer[..len], &mut messages) {
+                    log::warn!("Invalid OSC packet: {e}");
+                    continue;
+                }
+                for (address, args) in messages {
+                    match to_command(&prefix, &address, &args) {
+                        Some(command) => {
+                            if tx.send(command).is_err() {
+                                return;
+                            }
+                        }
+                        None => log::debug!("Ignore OSC message {address} {args:?}"),
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+            rx,
+        })
+    }
+
+    /// 非阻塞获取命令
+    pub fn try_recv(&self) -> Option<RemoteCommand> {
+        self.rx.try_recv().ok()
+    }
+
+    /// 停止接收线程
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 把 OSC 消息映射为命令, 地址或参数不匹配时返回 `None`
+fn to_command(prefix: &str, address: &str, args: &[OscArg]) -> Option<RemoteCommand> {
+    let path = address.strip_prefix(prefix)?;
+    match (path, args.first()?) {
+        ("/mood", OscArg::Str(name)) if lcd::mood_from_name(name).is_some() => {
+            Some(RemoteCommand::Mood(name.clone()))
+        }
+        (path, OscArg::Float(value)) => {
+            let index: usize = path.strip_prefix("/servo/")?.parse().ok()?;
+            if index >= SERVO_COUNT || !value.is_finite() {
+                return None;
+            }
+            let min = ServoState::min_angle(index) as f64;
+            let max = ServoState::max_angle(index) as f64;
+            Some(RemoteCommand::Joint(
+                index,
+                value.round().clamp(min, max) as i16,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// 解析报文, 消息或 bundle, 结果追加到 `messages`
+fn parse_packet(data: &[u8], messages: &mut Vec<(String, Vec<OscArg>)>) -> anyhow::Result<()> {
+    if data.starts_with(b"#bundle\0") {
+        // 跳过 8 字节时间标签, 之后每个元素为 4 字节长度加内容
+        let mut rest = data.get(16..).unwrap_or_default();
+        while rest.len() >= 4 {
+            let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            let element = rest
+                .get(4..4 + len)
+                .ok_or_else(|| anyhow::anyhow!("Truncated bundle element"))?;
+            parse_packet(element, messages)?;
+            rest = &rest[4 + len..];
+        }
+        return Ok(());
+    }
+
+    let (address, rest) = read_string(data)?;
+    let (tags, mut rest) = read_string(rest)?;
+    let tags = tags
+        .strip_prefix(',')
+        .ok_or_else(|| anyhow::anyhow!("Missing type tags"))?;
+    let mut args = Vec::new();
+    for tag in tags.chars() {
+        let arg = match tag {
+            'f' | 'i' => {
+                let bytes: [u8; 4] = take(&mut rest, 4)?.try_into()?;
+                if tag == 'f' {
+                    OscArg::Float(f32::from_be_bytes(bytes) as f64)
+                } else {
+                    OscArg::Float(i32::from_be_bytes(bytes) as f64)
+                }
+            }
+            'd' => {
+                let bytes: [u8; 8] = take(&mut rest, 8)?.try_into()?;
+                OscArg::Float(f64::from_be_bytes(bytes))
+            }
+            's' => {
+                let (text, remaining) = read_string(rest)?;
+                rest = remaining;
+                OscArg::Str(text)
+            }
+            // 其他类型的参数长度未知, 无法继续解析
+            _ => anyhow::bail!("Unsupported OSC type tag {tag}"),
+        };
+        args.push(arg);
+    }
+    messages.push((address, args));
+    Ok(())
+}
+
+/// 读取以 0 结尾并补齐到 4 字节的字符串, 返回字符串和剩余数据
+fn read_string(data: &[u8]) -> anyhow::Result<(String, &[u8])> {
+    let end = data
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| anyhow::anyhow!("Unterminated OSC string"))?;
+    let text = String::from_utf8(data[..end].to_vec())?;
+    let padded = (end + 4) & !3;
+    Ok((text, data.get(padded..).unwrap_or_default()))
+}
+
+/// 取出前 `len` 字节
+fn take<'a>(data: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    anyhow::ensure!(data.len() >= len, "Truncated OSC argument");
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 编码以 0 结尾并补齐到 4 字节的字符串
+    fn osc_string(text: &str) -> Vec<u8> {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        while !bytes.len().is_multiple_of(4) {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    fn message(address: &str, tags: &str, args: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = osc_string(address);
+        bytes.extend(osc_string(tags));
+        for arg in args {
+            bytes.extend_from_slice(arg);
+        }
+        bytes
+    }
+
+    fn parse(data: &[u8]) -> anyhow::Result<Vec<(String, Vec<OscArg>)>> {
+        let mut messages = Vec::new();
+        parse_packet(data, &mut messages)?;
+        Ok(messages)
+    }
+
+    #[test]
+    fn parses_message() {
+        let data = message(
+            "/electronbot/servo/1",
+            ",fis",
+            &[
+                &12.5f32.to_be_bytes(),
+                &(-3i32).to_be_bytes(),
+                &osc_string("happy"),
+            ],
+        );
+        assert_eq!(
+            parse(&data).unwrap(),
+            vec![(
+                "/electronbot/servo/1".to_string(),
+                vec![
+                    OscArg::Float(12.5),
+                    OscArg::Float(-3.0),
+                    OscArg::Str("happy".to_string()),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_bundle() {
+        let first = message("/electronbot/mood", ",s", &[&osc_string("happy")]);
+        let second = message("/electronbot/servo/0", ",d", &[&10.0f64.to_be_bytes()]);
+        let mut data = osc_string("#bundle");
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+        for element in [&first, &second] {
+            data.extend_from_slice(&(element.len() as u32).to_be_bytes());
+            data.extend_from_slice(element);
+        }
+
+        let messages = parse(&data).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, "/electronbot/mood");
+        assert_eq!(messages[1].1, vec![OscArg::Float(10.0)]);
+    }
+
+    #[test]
+    fn rejects_truncated_packets() {
+        let data = message("/electronbot/servo/0", ",f", &[&1.0f32.to_be_bytes()]);
+        assert!(parse(&data[..data.len() - 1]).is_err());
+        assert!(parse(b"/electronbot").is_err());
+
+        let mut bundle = osc_string("#bundle");
+        bundle.extend_from_slice(&[0; 8]);
+        bundle.extend_from_slice(&(data.len() as u32 + 4).to_be_bytes());
+        bundle.extend_from_slice(&data);
+        assert!(parse(&bundle).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_tags() {
+        let data = message("/electronbot/servo/0", ",b", &[&[0, 0, 0, 0]]);
+        assert!(parse(&data).is_err());
+        let data = message("/electronbot/servo/0", "f", &[&1.0f32.to_be_bytes()]);
+        assert!(parse(&data).is_err());
+    }
+
+    #[test]
+    fn maps_addresses_under_prefix() {
+        let prefix = "/electronbot";
+        assert!(matches!(
+            to_command(prefix, "/electronbot/servo/0", &[OscArg::Float(999.0)]),
+            Some(RemoteCommand::Joint(0, angle)) if angle == ServoState::max_angle(0)
+        ));
+        assert!(matches!(
+            to_command(prefix, "/electronbot/mood", &[OscArg::Str("happy".into())]),
+            Some(RemoteCommand::Mood(name)) if name == "happy"
+        ));
+        // 前缀不匹配, 或只是以前缀开头的其他地址
+        assert!(to_command(prefix, "/other/servo/0", &[OscArg::Float(1.0)]).is_none());
+        assert!(to_command(prefix, "/electronbot2/servo/0", &[OscArg::Float(1.0)]).is_none());
+        // 参数类型, 序号或表情名称无效
+        assert!(to_command(prefix, "/electronbot/mood", &[OscArg::Float(1.0)]).is_none());
+        assert!(to_command(prefix, "/electronbot/mood", &[OscArg::Str("sad".into())]).is_none());
+        assert!(to_command(prefix, "/electronbot/servo/6", &[OscArg::Float(1.0)]).is_none());
+        assert!(to_command(prefix, "/electronbot/servo/0", &[OscArg::Float(f64::NAN)]).is_none());
+        assert!(to_command(prefix, "/electronbot/servo/0", &[]).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! LCD 预览
+//!
+//! 把实际发送给设备的帧缩小后缓存, 供设备控制页显示; 帧哈希不变时不重新计算
+
+use crate::robot::lcd::{LCD_HEIGHT, LCD_WIDTH};
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+
+/// 预览边长 (像素), 每个终端字符显示上下两个像素
+pub const PREVIEW_SIZE: usize = 40;
+/// 每个预览像素对应的 LCD 像素边长
+const SCALE: usize = LCD_WIDTH / PREVIEW_SIZE;
+
+/// 缩小后的 LCD 帧
+#[derive(Debug, Default)]
+pub struct LcdPreview {
+    hash: Option<u64>,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl LcdPreview {
+    /// 用新帧更新预览, 哈希与上次相同时跳过
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - 240x240 RGB 帧数据
+    /// * `hash` - 帧数据哈希值
+    pub fn update(&mut self, frame: &[u8], hash: u64) {
+        if self.hash == Some(hash) || frame.len() < LCD_WIDTH * LCD_HEIGHT * 3 {
+            return;
+        }
+        self.hash = Some(hash);
+        self.pixels.clear();
+        for py in 0..PREVIEW_SIZE {
+            for px in 0..PREVIEW_SIZE {
+                // 对 SCALE x SCALE 区域取平均
+                let mut sum = [0u32; 3];
+                for y in py * SCALE..(py + 1) * SCALE {
+                    for x in px * SCALE..(px + 1) * SCALE {
+                        let i = (y * LCD_WIDTH + x) * 3;
+                        for (c, s) in sum.iter_mut().enumerate() {
+                            *s += frame[i + c] as u32;
+                        }
+                    }
+                }
+                let n = (SCALE * SCALE) as u32;
+                self.pixels
+                    .push([(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]);
+            }
+        }
+    }
+
+    /// 当前预览对应的帧哈希
+    pub fn hash(&self) -> Option<u64> {
+        self.hash
+    }
+
+    /// 是否已有预览数据
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// 获取预览像素亮度 (0~255)
+    pub fn luma(&self, x: usize, y: usize) -> u8 {
+        let [r, g, b] = self.pixel(x, y);
+        ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+    }
+
+    /// 获取预览像素颜色
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 3] {
+        self.pixels
+            .get(y * PREVIEW_SIZE + x)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// 预览文本缓存的键: 帧哈希、是否真彩色、显示区域
+pub type PreviewKey = (Option<u64>, bool, Rect);
+
+/// 设备控制页生成的预览文本, 键不变时直接复用
+#[derive(Debug, Default)]
+pub struct PreviewCache {
+    key: Option<PreviewKey>,
+    lines: Vec<Line<'static>>,
+}
+
+impl PreviewCache {
+    /// 取出缓存的预览文本, 键变化时先用 `build` 重新生成
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - 缓存键, 任一部分变化都会重新生成
+    /// * `build` - 生成预览文本
+    pub fn get_or_build(
+        &mut self,
+        key: PreviewKey,
+        build: impl FnOnce() -> Vec<Line<'static>>,
+    ) -> &[Line<'static>] {
+        if self.key != Some(key) {
+            self.lines = build();
+            self.key = Some(key);
+        }
+        &self.lines
+    }
+}
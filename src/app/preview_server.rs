@@ -0,0 +1,460 @@
+//! 浏览器实时预览
+//!
+//! 在后台线程监听 [AppConfig::preview_server_addr](super::config::AppConfig::preview_server_addr),
+//! 用浏览器打开该地址即可看到屏幕画面. 主循环经通道推送屏幕帧和状态, 服务线程把帧编码为 JPEG,
+//! 通过 WebSocket 以二进制消息发给所有客户端, 状态以文本 JSON 消息发送.
+//! 握手在单独的线程中进行, 新客户端连接后先收到最近一帧
+//!
+//! 只实现了服务端发送, 忽略客户端发来的消息
+
+use crate::robot::lcd::{LCD_HEIGHT, LCD_WIDTH};
+use image::codecs::jpeg::JpegEncoder;
+use image::ExtendedColorType;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// 没有新连接和新画面时的等待间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// 握手和发送的超时时间, 超时的客户端会被断开
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+/// 同时进行的握手数上限, 超出时直接断开新连接
+const MAX_PENDING_HANDSHAKES: usize = 8;
+/// JPEG 质量
+const JPEG_QUALITY: u8 = 80;
+/// 握手时与客户端密钥拼接的固定 GUID (RFC 6455)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// 不带 WebSocket 握手的请求返回的预览页面
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>ElectronBot</title></head>
+<body style="background:#111;color:#ccc;font-family:monospace">
+<img id="lcd" width="480" height="480" style="image-rendering:pixelated">
+<pre id="status"></pre>
+<script>
+const ws = new WebSocket("ws://" + location.host + "/");
+ws.binaryType = "blob";
+ws.onmessage = (e) => {
+  if (typeof e.data === "string") {
+    document.getElementById("status").textContent = JSON.stringify(JSON.parse(e.data), null, 2);
+  } else {
+    const img = document.getElementById("lcd");
+    URL.revokeObjectURL(img.src);
+    img.src = URL.createObjectURL(e.data);
+  }
+};
+</script></body></html>"#;
+
+/// 推送给客户端的内容
+enum Update {
+    /// 240x240 RGB 像素
+    Frame(Vec<u8>),
+    /// 状态 JSON
+    Status(String),
+}
+
+/// 预览服务, 销毁时停止后台线程
+pub struct PreviewServer {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    tx: mpsc::SyncSender<Update>,
+}
+
+#[allow(dead_code)]
+impl PreviewServer {
+    /// 绑定地址并启动服务线程
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - 监听地址, 如 `127.0.0.1:8766`
+    /// * `max_clients` - 最多同时连接的客户端数, 超出时拒绝新连接
+    pub fn start(addr: &str, max_clients: usize) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        log::info!("Preview server listening on {addr}");
+
+        let running = Arc::new(AtomicBool::new(true));
+        // 服务线程来不及发送时丢弃新画面, 不阻塞主循环
+        let (tx, rx) = mpsc::sync_channel(2);
+        let running_clone = running.clone();
+        let handle = thread::spawn(move || {
+            let mut clients: Vec<TcpStream> = Vec::new();
+            // 握手线程完成后送回连接, 握手失败或不是 WebSocket 请求时送回 None
+            let (client_tx, client_rx) = mpsc::channel();
+            let mut pending = 0;
+            let mut last_frame: Option<Vec<u8>> = None;
+            while running_clone.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((_, peer)) if pending >= MAX_PENDING_HANDSHAKES => {
+                        log::warn!("Preview server busy, dropped {peer}");
+                    }
+                    Ok((stream, peer)) => {
+                        let has_room = clients.len() + pending < max_clients;
+                        pending += 1;
+                        let client_tx = client_tx.clone();
+                        thread::spawn(move || {
+                            let client = match accept_client(stream, has_room) {
+                                Ok(client) => client,
+                                Err(e) => {
+                                    log::warn!("Preview client {peer} handshake failed: {e}");
+                                    None
+                                }
+                            };
+                            if client.is_some() {
+                                log::info!("Preview client {peer} connected");
+                            }
+                            let _ = client_tx.send(client);
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => log::warn!("Preview server accept failed: {e}"),
+                }
+                for client in client_rx.try_iter() {
+                    pending -= 1;
+                    let Some(mut client) = client else {
+                        continue;
+                    };
+                    // 画面静止时不会有新帧, 先补发最近一帧
+                    if last_frame
+                        .as_ref()
+                        .is_none_or(|frame| client.write_all(frame).is_ok())
+                    {
+                        clients.push(client);
+                    }
+                }
+
+                let message = match rx.recv_timeout(POLL_INTERVAL) {
+                    Ok(Update::Frame(pixels)) => match encode_jpeg(&pixels) {
+                        Ok(jpeg) => {
+                            let message = ws_frame(OPCODE_BINARY, &jpeg);
+                            last_frame = Some(message.clone());
+                            message
+                        }
+                        Err(e) => {
+                            log::warn!("Preview JPEG encoding failed: {e}");
+                            continue;
+                        }
+                    },
+                    Ok(Update::Status(status)) => ws_frame(OPCODE_TEXT, status.as_bytes()),
+                    Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                };
+                // 发送失败的客户端视为已断开
+                clients.retain_mut(|client| client.write_all(&message).is_ok());
+            }
+        });
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+            tx,
+        })
+    }
+
+    /// 推送一帧画面, 服务线程繁忙时丢弃并返回 false
+    pub fn push_frame(&self, pixels: &[u8]) -> bool {
+        self.tx.try_send(Update::Frame(pixels.to_vec())).is_ok()
+    }
+
+    /// 推送状态 JSON, 服务线程繁忙时丢弃
+    pub fn push_status(&self, status: String) {
+        let _ = self.tx.try_send(Update::Status(status));
+    }
+
+    /// 停止服务线程, 断开所有客户端
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for PreviewServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 处理新连接: WebSocket 握手成功时返回连接; 普通请求返回预览页面, 超出人数时返回 503
+fn accept_client(stream: TcpStream, has_room: bool) -> anyhow::Result<Option<TcpStream>> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut key = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let mut stream = stream;
+    let Some(key) = key else {
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{INDEX_HTML}",
+            INDEX_HTML.len()
+        )?;
+        return Ok(None);
+    };
+    if !has_room {
+        write!(
+            stream,
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )?;
+        return Ok(None);
+    }
+    let accept = accept_key(&key);
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+    )?;
+    Ok(Some(stream))
+}
+
+/// 把 RGB 帧编码为 JPEG
+fn encode_jpeg(pixels: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY).encode(
+        pixels,
+        LCD_WIDTH as u32,
+        LCD_HEIGHT as u32,
+        ExtendedColorType::Rgb8,
+    )?;
+    Ok(jpeg)
+}
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+
+/// 生成服务端发出的 WebSocket 帧 (FIN, 不加掩码)
+fn ws_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// 由客户端的 `Sec-WebSocket-Key` 计算握手应答 `Sec-WebSocket-Accept`
+fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{WEBSOCKET_GUID}").as_bytes()))
+}
+
+/// SHA-1 摘要, 只用于计算握手应答
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// 标准 Base64 编码
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+    use std::net::SocketAddr;
+    use std::time::Instant;
+
+    /// 在空闲端口上启动服务
+    fn start_server() -> (PreviewServer, SocketAddr) {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        (PreviewServer::start(&addr.to_string(), 4).unwrap(), addr)
+    }
+
+    /// 完成 WebSocket 握手, 返回读取了应答头的连接
+    fn ws_connect(addr: SocketAddr) -> BufReader<TcpStream> {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(IO_TIMEOUT)).unwrap();
+        write!(
+            stream,
+            "GET / HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+        )
+        .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut status = String::new();
+        reader.read_line(&mut status).unwrap();
+        assert!(status.starts_with("HTTP/1.1 101"), "{status}");
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break reader;
+            }
+        }
+    }
+
+    /// 推送一帧并等待服务线程取走
+    fn push_and_wait(server: &PreviewServer) {
+        assert!(server.push_frame(&vec![0x80; LCD_WIDTH * LCD_HEIGHT * 3]));
+        thread::sleep(POLL_INTERVAL * 10);
+    }
+
+    #[test]
+    fn late_client_gets_last_frame() {
+        let (server, addr) = start_server();
+        push_and_wait(&server);
+
+        // 之后没有新画面, 新客户端仍能收到最近一帧
+        let mut client = ws_connect(addr);
+        let mut opcode = [0u8; 1];
+        client.read_exact(&mut opcode).unwrap();
+        assert_eq!(opcode[0], 0x80 | OPCODE_BINARY);
+    }
+
+    #[test]
+    fn slow_handshake_does_not_block_others() {
+        let (server, addr) = start_server();
+        let first = ws_connect(addr);
+        // 只连接不发送请求头, 握手会一直等到超时
+        let _stalled = TcpStream::connect(addr).unwrap();
+        thread::sleep(POLL_INTERVAL * 5);
+
+        let start = Instant::now();
+        push_and_wait(&server);
+        let mut opcode = [0u8; 1];
+        first.into_inner().read_exact(&mut opcode).unwrap();
+        assert_eq!(opcode[0], 0x80 | OPCODE_BINARY);
+        assert!(start.elapsed() < IO_TIMEOUT / 2, "{:?}", start.elapsed());
+    }
+
+    #[test]
+    fn rfc6455_handshake_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn sha1_known_digests() {
+        let hex = |digest: [u8; 20]| {
+            digest
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        };
+        assert_eq!(hex(sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        // 填充后跨越两个分组
+        assert_eq!(
+            hex(sha1(&[b'a'; 56])),
+            "c2db330f6083854c99d4b5bfb6e8f29f201be699"
+        );
+    }
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+    }
+
+    /// 帧头长度和其中的长度字段
+    fn header(len: usize) -> Vec<u8> {
+        let frame = ws_frame(OPCODE_BINARY, &vec![0; len]);
+        assert_eq!(frame[0], 0x80 | OPCODE_BINARY);
+        frame[..frame.len() - len].to_vec()
+    }
+
+    #[test]
+    fn ws_frame_length_boundaries() {
+        assert_eq!(header(125), [0x82, 125]);
+        assert_eq!(header(126), [0x82, 126, 0x00, 126]);
+        assert_eq!(header(65535), [0x82, 126, 0xFF, 0xFF]);
+        assert_eq!(header(65536), [0x82, 127, 0, 0, 0, 0, 0, 0x01, 0x00, 0x00]);
+    }
+}
@@ -0,0 +1,98 @@
+//! 远程控制命令
+//!
+//! HTTP 接口、MQTT 桥、标准输入和脚本在各自线程里校验请求, 转换为 [RemoteCommand] 经通道交给主循环执行
+
+use crate::robot::{lcd, ServoState, SERVO_COUNT};
+
+/// 交给主循环执行的远程命令
+#[derive(Debug, Clone)]
+pub enum RemoteCommand {
+    /// 表情名称, 已确认有效
+    Mood(String),
+    /// 注视方向名称, 已确认有效
+    Position(String),
+    /// 各舵机目标角度, 已确认在范围内
+    Servo([i16; SERVO_COUNT]),
+    /// 240x240 RGB 像素
+    Image(Vec<u8>),
+    /// 单个舵机的序号和目标角度, 已确认在范围内
+    Joint(usize, i16),
+    /// 直接退出, 不弹窗确认
+    Quit,
+}
+
+/// 检查各舵机角度是否在范围内, 超出时返回错误说明
+pub fn check_angles(angles: &[i16; SERVO_COUNT]) -> Result<(), String> {
+    for (i, &angle) in angles.iter().enumerate() {
+        if ServoState::clamp_angle(i, angle) != angle {
+            return Err(format!(
+                "{} out of range {}",
+                ServoState::name(i),
+                ServoState::range_str(i)
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 解析一行文本命令, 空行和 `#` 开头的注释返回 `None`
+///
+/// - `mood <名称>` 设置表情
+/// - `look <方向>` 设置注视方向
+/// - `servo <序号> <角度>` 设置单个舵机目标角度, 序号从 0 开始
+/// - `servo <a> <b> <c> <d> <e> <f>` 设置所有舵机目标角度
+/// - `image <路径>` 静态显示图片
+/// - `quit` 退出程序
+pub fn parse_command(line: &str) -> anyhow::Result<Option<RemoteCommand>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+    let args = args.trim();
+    let command = match name {
+        "mood" => {
+            anyhow::ensure!(lcd::mood_from_name(args).is_some(), "unknown mood");
+            RemoteCommand::Mood(args.to_string())
+        }
+        "look" => {
+            anyhow::ensure!(lcd::position_from_name(args).is_some(), "unknown direction");
+            RemoteCommand::Position(args.to_string())
+        }
+        "servo" => {
+            let parts: Vec<&str> = args.split_whitespace().collect();
+            match parts.len() {
+                2 => {
+                    let index: usize = parts[0].parse()?;
+                    let angle: i16 = parts[1].parse()?;
+                    anyhow::ensure!(
+                        index < SERVO_COUNT,
+                        "servo index must be below {SERVO_COUNT}"
+                    );
+                    anyhow::ensure!(
+                        ServoState::clamp_angle(index, angle) == angle,
+                        "angle out of range {}",
+                        ServoState::range_str(index)
+                    );
+                    RemoteCommand::Joint(index, angle)
+                }
+                SERVO_COUNT => {
+                    let mut angles = [0; SERVO_COUNT];
+                    for (angle, part) in angles.iter_mut().zip(&parts) {
+                        *angle = part.parse()?;
+                    }
+                    check_angles(&angles).map_err(anyhow::Error::msg)?;
+                    RemoteCommand::Servo(angles)
+                }
+                _ => anyhow::bail!("usage: servo <index> <angle> or servo <6 angles>"),
+            }
+        }
+        "image" => {
+            anyhow::ensure!(!args.is_empty(), "usage: image <path>");
+            RemoteCommand::Image(lcd::decode_image(&std::fs::read(args)?)?)
+        }
+        "quit" => RemoteCommand::Quit,
+        _ => anyhow::bail!("unknown command"),
+    };
+    Ok(Some(command))
+}
@@ -0,0 +1,258 @@
+//! 动画脚本 (需要 `scripting` 特性)
+//!
+//! 脚本为 Rhai (`.rhai`), 放在 [SCRIPT_DIR] 下, 在后台线程执行, 命令经有界通道交给主循环,
+//! 主循环来不及执行时脚本等待. 脚本出错时只结束脚本本身, 错误显示在日志中
+//!
+//! 脚本可以调用:
+//!
+//! - `set_mood(名称)` 设置表情
+//! - `set_position(方向)` 设置注视方向
+//! - `set_servo(序号, 角度)` 设置单个舵机目标角度, 序号从 0 开始
+//! - `sleep(毫秒)` 等待
+//! - `show_image(路径)` 静态显示图片
+//!
+//! ```text
+//! // 开心地点头三次
+//! set_mood("happy");
+//! for i in 0..3 {
+//!     set_servo(0, 15);
+//!     sleep(300);
+//!     set_servo(0, 0);
+//!     sleep(300);
+//! }
+//! ```
+
+use super::remote::{self, RemoteCommand};
+use rhai::{Dynamic, Engine, EvalAltResult, INT};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 脚本目录
+pub const SCRIPT_DIR: &str = "./scripts";
+/// 脚本文件扩展名
+pub const SCRIPT_EXTENSION: &str = ".rhai";
+/// 等待时检查停止标志的间隔
+const SLEEP_STEP: Duration = Duration::from_millis(10);
+/// 命令通道容量, 也是主循环每次最多执行的脚本命令数
+pub const COMMAND_QUEUE: usize = 16;
+
+/// 列出脚本目录下的脚本文件名
+pub fn list_script_files() -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(SCRIPT_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(SCRIPT_EXTENSION))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// 脚本执行器, 销毁时停止后台线程
+pub struct ScriptRunner {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<anyhow::Result<()>>>,
+    rx: mpsc::Receiver<RemoteCommand>,
+}
+
+#[allow(dead_code)]
+impl ScriptRunner {
+    /// 编译脚本并启动执行线程, 有语法错误时返回带行号的错误
+    pub fn start(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        check(&content)?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let (tx, rx) = mpsc::sync_channel(COMMAND_QUEUE);
+        let running_clone = running.clone();
+        let handle = thread::spawn(move || run(&content, running_clone, tx));
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+            rx,
+        })
+    }
+
+    /// 非阻塞获取命令
+    pub fn try_recv(&self) -> Option<RemoteCommand> {
+        self.rx.try_recv().ok()
+    }
+
+    /// 脚本是否已执行完
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(|h| h.is_finished())
+    }
+
+    /// 等待执行线程结束并返回脚本的运行错误, 已经取走结果时返回 `Ok`
+    pub fn join(&mut self) -> anyhow::Result<()> {
+        match self.handle.take().map(|handle| handle.join()) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(anyhow::anyhow!("script thread panicked")),
+            None => Ok(()),
+        }
+    }
+
+    /// 停止执行
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ScriptRunner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+type FnResult = Result<(), Box<EvalAltResult>>;
+
+/// 检查脚本语法, 有错误时不启动执行线程
+pub fn check(content: &str) -> anyhow::Result<()> {
+    Engine::new()
+        .compile(content)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(())
+}
+
+/// 执行脚本, 停止标志被清除时以错误结束
+pub fn run(
+    content: &str,
+    running: Arc<AtomicBool>,
+    tx: mpsc::SyncSender<RemoteCommand>,
+) -> anyhow::Result<()> {
+    let mut engine = Engine::new();
+    // 标准输出由界面占用, 脚本输出写入日志
+    engine.on_print(|text| log::info!("Script: {text}"));
+    engine.on_debug(|text, _, _| log::debug!("Script: {text}"));
+    let running_clone = running.clone();
+    engine.on_progress(move |_| (!running_clone.load(Ordering::Relaxed)).then_some(Dynamic::UNIT));
+
+    let send = command_sender(&tx, &running);
+    engine.register_fn("set_mood", move |name: &str| send(&format!("mood {name}")));
+    let send = command_sender(&tx, &running);
+    engine.register_fn("set_position", move |name: &str| {
+        send(&format!("look {name}"))
+    });
+    let send = command_sender(&tx, &running);
+    engine.register_fn("set_servo", move |index: INT, angle: INT| {
+        send(&format!("servo {index} {angle}"))
+    });
+    let send = command_sender(&tx, &running);
+    engine.register_fn("show_image", move |path: &str| {
+        send(&format!("image {path}"))
+    });
+    engine.register_fn("sleep", move |ms: INT| -> FnResult {
+        let duration = Duration::from_millis(ms.max(0) as u64);
+        let start = Instant::now();
+        while start.elapsed() < duration {
+            if !running.load(Ordering::Relaxed) {
+                return Err("script stopped".into());
+            }
+            thread::sleep(SLEEP_STEP.min(duration.saturating_sub(start.elapsed())));
+        }
+        Ok(())
+    });
+
+    engine.run(content).map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// 把一行命令解析为 [RemoteCommand] 发给主循环, 参数无效时返回脚本错误
+///
+/// 通道已满时等待主循环取走命令, 期间仍检查停止标志, 避免 [ScriptRunner::stop] 等待线程时卡住
+fn command_sender(
+    tx: &mpsc::SyncSender<RemoteCommand>,
+    running: &Arc<AtomicBool>,
+) -> impl Fn(&str) -> FnResult {
+    let tx = tx.clone();
+    let running = running.clone();
+    move |line| {
+        let mut command = remote::parse_command(line)
+            .map_err(|e| format!("{line}: {e}"))?
+            .ok_or_else(|| format!("{line}: empty command"))?;
+        loop {
+            if !running.load(Ordering::Relaxed) {
+                return Err("script stopped".into());
+            }
+            match tx.try_send(command) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::TrySendError::Full(c)) => command = c,
+                Err(mpsc::TrySendError::Disconnected(_)) => return Err("app closed".into()),
+            }
+            thread::sleep(SLEEP_STEP);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_script(content: &str) -> (anyhow::Result<()>, Vec<RemoteCommand>) {
+        let (tx, rx) = mpsc::sync_channel(COMMAND_QUEUE);
+        let result = run(content, Arc::new(AtomicBool::new(true)), tx);
+        (result, rx.try_iter().collect())
+    }
+
+    #[test]
+    fn functions_send_commands() {
+        let (result, commands) =
+            run_script(r#"set_mood("happy"); set_position("left"); sleep(1); set_servo(0, 15);"#);
+        result.unwrap();
+        assert!(matches!(
+            &commands[..],
+            [
+                RemoteCommand::Mood(mood),
+                RemoteCommand::Position(position),
+                RemoteCommand::Joint(0, 15),
+            ] if mood == "happy" && position == "left"
+        ));
+    }
+
+    #[test]
+    fn invalid_argument_ends_script() {
+        let (result, commands) =
+            run_script(r#"set_mood("happy"); set_servo(0, 40); set_mood("tired");"#);
+        assert!(result.unwrap_err().to_string().contains("servo 0 40"));
+        assert_eq!(commands.len(), 1);
+    }
+
+    #[test]
+    fn syntax_error_is_reported_before_start() {
+        assert!(check("set_mood(").is_err());
+        assert!(check(r#"set_mood("happy");"#).is_ok());
+    }
+
+    #[test]
+    fn stop_interrupts_loop() {
+        let (tx, _rx) = mpsc::sync_channel(COMMAND_QUEUE);
+        let running = Arc::new(AtomicBool::new(false));
+        assert!(run("loop { }", running, tx).is_err());
+    }
+
+    #[test]
+    fn tight_loop_waits_for_main_loop() {
+        let path = std::env::temp_dir().join("ele_bot_tight_loop.rhai");
+        fs::write(&path, r#"loop { set_mood("happy"); }"#).unwrap();
+        let mut runner = ScriptRunner::start(&path).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(runner.rx.try_iter().count(), COMMAND_QUEUE);
+        assert!(!runner.is_finished());
+        // 通道已满时停止也不会卡住
+        thread::sleep(Duration::from_millis(50));
+        runner.stop();
+        assert!(runner.is_finished());
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,300 @@
+//! 设置页配置项
+//!
+//! 每个配置项由显示名称、当前值和编辑方式描述, 设置页的显示、编辑和保存都遍历 [items],
+//! 新增配置项只需在这里添加一项
+
+use super::{config, config::AppConfig, eye_tint_name, App};
+use crate::robot::lcd::{BRIGHTNESS_RANGE, GAMMA_RANGE};
+use crate::robot::{ServoState, SERVO_COUNT};
+use std::path::Path;
+
+/// WiFi SSID 最大字节数
+const MAX_SSID_BYTES: usize = 32;
+/// WPA 密码长度范围
+const WIFI_PASSWORD_LEN: std::ops::RangeInclusive<usize> = 8..=63;
+/// 校准偏移最大绝对值 (度)
+const MAX_CALIBRATION: f32 = 30.0;
+
+/// 校验输入内容并更新配置, 输入无效时返回错误说明且不修改配置
+pub type ApplyFn = Box<dyn Fn(&mut App, &str) -> Result<(), String>>;
+
+/// 配置项的编辑方式
+pub enum SettingKind {
+    /// 回车进入文本编辑, 初始内容为当前值, 保存时用输入内容调用, 返回错误时保持编辑
+    Edit(ApplyFn),
+    /// 回车直接执行, 如打开选择弹窗
+    Action(fn(&mut App)),
+}
+
+/// 设置页中的一个配置项
+pub struct SettingItem {
+    /// 显示名称
+    pub label: String,
+    /// 当前值
+    pub value: Box<dyn Fn(&AppConfig) -> String>,
+    pub kind: SettingKind,
+    /// 敏感内容 (如密码), 默认以圆点遮盖显示
+    pub secret: bool,
+}
+
+impl SettingItem {
+    fn edit(
+        label: impl Into<String>,
+        value: impl Fn(&AppConfig) -> String + 'static,
+        apply: impl Fn(&mut App, &str) -> Result<(), String> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value: Box::new(value),
+            kind: SettingKind::Edit(Box::new(apply)),
+            secret: false,
+        }
+    }
+
+    fn action(
+        label: impl Into<String>,
+        value: impl Fn(&AppConfig) -> String + 'static,
+        action: fn(&mut App),
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value: Box::new(value),
+            kind: SettingKind::Action(action),
+            secret: false,
+        }
+    }
+
+    /// 标记为敏感内容
+    fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+}
+
+/// 设置页的所有配置项, 按显示顺序排列
+pub fn items() -> Vec<SettingItem> {
+    let mut items = vec![
+        SettingItem::edit(
+            "Wifi名称",
+            |config| config.wifi_ssid.clone(),
+            |app, input| {
+                if input.len() > MAX_SSID_BYTES {
+                    return Err(format!("SSID 不能超过 {MAX_SSID_BYTES} 字节"));
+                }
+                app.config.wifi_ssid = input.to_string();
+                Ok(())
+            },
+        ),
+        SettingItem::edit(
+            "Wifi密码",
+            |config| config.wifi_password.clone(),
+            |app, input| {
+                // 开放网络不设密码
+                if !input.is_empty() && !WIFI_PASSWORD_LEN.contains(&input.len()) {
+                    return Err("密码长度应为 8~63 个字符".to_string());
+                }
+                app.config.wifi_password = input.to_string();
+                Ok(())
+            },
+        )
+        .secret(),
+        // 麦克风从设备列表中选择, 不手动输入
+        SettingItem::action(
+            "麦克风名称(Enter选择)",
+            |config| config.speech_name.clone(),
+            App::open_mic_select,
+        ),
+        SettingItem::edit(
+            "唤醒词(逗号分隔)",
+            |config| config.wake_words_str(),
+            |app, input| {
+                let words = AppConfig::parse_wake_words(input);
+                if words.is_empty() {
+                    return Err("至少需要一个唤醒词".to_string());
+                }
+                app.config.wake_words = words;
+                if let Some(vm) = &app.voice_manager {
+                    vm.set_wake_words(app.config.wake_words.clone());
+                }
+                Ok(())
+            },
+        ),
+        SettingItem::edit(
+            "唤醒应答",
+            |config| config.greeting.clone(),
+            |app, input| {
+                app.config.greeting = input.to_string();
+                if let Some(vm) = &app.voice_manager {
+                    vm.set_greeting(app.config.greeting.clone());
+                }
+                Ok(())
+            },
+        ),
+        SettingItem::edit(
+            "模型路径",
+            |config| config.model_path.clone(),
+            |app, input| {
+                if !Path::new(input.trim()).is_dir() {
+                    return Err("模型目录不存在".to_string());
+                }
+                app.config.model_path = input.trim().to_string();
+                if let Some(vm) = &mut app.voice_manager {
+                    if let Err(e) = vm.reload_model(&app.config.model_path) {
+                        log::error!("Failed to reload model: {e}");
+                    }
+                }
+                Ok(())
+            },
+        ),
+        SettingItem::edit(
+            "插值速度(°/帧, 0为不插值)",
+            |config| config.servo_slew_rate.to_string(),
+            |app, input| match input.trim().parse::<f32>() {
+                Ok(rate) if rate.is_finite() && rate >= 0.0 => {
+                    app.config.servo_slew_rate = rate;
+                    Ok(())
+                }
+                _ => Err("请输入不小于 0 的数字".to_string()),
+            },
+        ),
+    ];
+    for i in 0..SERVO_COUNT {
+        items.push(SettingItem::edit(
+            format!("校准偏移: {}(°)", ServoState::name(i)),
+            move |config| format!("{:+.1}", config.calibration[i]),
+            move |app, input| match input.trim().parse::<f32>() {
+                Ok(offset) if offset.abs() <= MAX_CALIBRATION => {
+                    app.config.calibration[i] = offset;
+                    app.joint.set_calibration(app.config.calibration);
+                    Ok(())
+                }
+                _ => Err(format!("请输入 ±{MAX_CALIBRATION} 以内的数字")),
+            },
+        ));
+    }
+    items.extend([
+        SettingItem::action("重置校准(Enter)", |_| String::new(), App::reset_calibration),
+        // 眼睛颜色从预设中选择
+        SettingItem::action(
+            "眼睛颜色(Enter选择)",
+            |config| eye_tint_name(config.eye_tint),
+            App::open_eye_tint_select,
+        ),
+        SettingItem::edit(
+            "屏幕亮度",
+            |config| format!("{:.2}", config.brightness),
+            |app, input| match input.trim().parse::<f32>() {
+                Ok(value) if BRIGHTNESS_RANGE.contains(&value) => {
+                    app.config.brightness = value;
+                    app.apply_tone();
+                    Ok(())
+                }
+                _ => Err(format!(
+                    "请输入 {} 到 {} 之间的数字",
+                    BRIGHTNESS_RANGE.start(),
+                    BRIGHTNESS_RANGE.end()
+                )),
+            },
+        ),
+        SettingItem::edit(
+            "屏幕伽马",
+            |config| format!("{:.2}", config.gamma),
+            |app, input| match input.trim().parse::<f32>() {
+                Ok(value) if GAMMA_RANGE.contains(&value) => {
+                    app.config.gamma = value;
+                    app.apply_tone();
+                    Ok(())
+                }
+                _ => Err(format!(
+                    "请输入 {} 到 {} 之间的数字",
+                    GAMMA_RANGE.start(),
+                    GAMMA_RANGE.end()
+                )),
+            },
+        ),
+        SettingItem::action(
+            "空闲呼吸动作(Enter切换)",
+            |config| on_off(config.idle_motion),
+            |app| app.config.idle_motion = !app.config.idle_motion,
+        ),
+        SettingItem::action(
+            "水平翻转(Enter切换)",
+            |config| on_off(config.flip_h),
+            |app| {
+                app.config.flip_h = !app.config.flip_h;
+                app.apply_orientation();
+            },
+        ),
+        SettingItem::action(
+            "垂直翻转(Enter切换)",
+            |config| on_off(config.flip_v),
+            |app| {
+                app.config.flip_v = !app.config.flip_v;
+                app.apply_orientation();
+            },
+        ),
+        SettingItem::action(
+            "旋转(Enter切换)",
+            |config| format!("{}°", config.rotate),
+            |app| {
+                app.config.rotate = (app.config.rotate / 90 * 90 + 90) % 360;
+                app.apply_orientation();
+            },
+        ),
+        SettingItem::edit(
+            "幻灯片目录",
+            |config| config.slideshow_dir.clone(),
+            |app, input| {
+                if !Path::new(input.trim()).is_dir() {
+                    return Err("目录不存在".to_string());
+                }
+                app.config.slideshow_dir = input.trim().to_string();
+                Ok(())
+            },
+        ),
+        SettingItem::edit(
+            "幻灯片间隔(秒)",
+            |config| config.slideshow_interval_secs.to_string(),
+            |app, input| match input.trim().parse::<u64>() {
+                Ok(secs) if secs > 0 => {
+                    app.config.slideshow_interval_secs = secs;
+                    Ok(())
+                }
+                _ => Err("请输入大于 0 的整数".to_string()),
+            },
+        ),
+        SettingItem::action(
+            "界面主题(Enter选择)",
+            |config| config.theme.label().to_string(),
+            App::open_theme_select,
+        ),
+        SettingItem::action(
+            "配置档案(Enter选择)",
+            |config| config.profile.clone(),
+            App::open_profile_select,
+        ),
+        // 以新名称保存当前配置并切换过去, 同名档案会被覆盖
+        SettingItem::edit(
+            "另存为档案",
+            |config| config.profile.clone(),
+            |app, input| {
+                let name = input.trim();
+                if !config::is_valid_profile_name(name) {
+                    return Err("名称只能包含字母、数字、- 和 _".to_string());
+                }
+                app.config.save_profile(name).map_err(|e| e.to_string())?;
+                if let Err(e) = config::set_active_profile(name) {
+                    log::error!("Failed to save active profile: {e}");
+                }
+                app.log_queue.info(format!("已另存为配置档案: {name}"));
+                Ok(())
+            },
+        ),
+    ]);
+    items
+}
+
+/// 开关状态的显示文字
+fn on_off(value: bool) -> String {
+    if value { "开" } else { "关" }.to_string()
+}
@@ -0,0 +1,57 @@
+//! 标准输入控制
+//!
+//! 启用 `--stdin-control` 时在后台线程按行读取标准输入, 解析为 [RemoteCommand] 交给主循环,
+//! 可以把脚本通过管道传给程序. 解析失败的行写入日志后跳过
+//!
+//! 命令格式见 [remote::parse_command]
+
+use super::remote::{self, RemoteCommand};
+use std::io::{BufRead, IsTerminal};
+use std::sync::mpsc;
+use std::thread;
+
+/// 标准输入控制, 读到文件末尾后线程自行结束
+pub struct StdinControl {
+    rx: mpsc::Receiver<RemoteCommand>,
+}
+
+#[allow(dead_code)]
+impl StdinControl {
+    /// 启动读取线程
+    ///
+    /// 标准输入是终端时界面的按键也从这里读取, 两者会互相抢输入, 此时返回错误
+    pub fn start() -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !std::io::stdin().is_terminal(),
+            "stdin is a terminal, pipe commands into the program to use --stdin-control"
+        );
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (number, line) in std::io::stdin().lock().lines().enumerate() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(e) => {
+                        log::warn!("Failed to read stdin: {e}");
+                        break;
+                    }
+                };
+                match remote::parse_command(&line) {
+                    Ok(Some(command)) => {
+                        if tx.send(command).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("stdin line {}: {e}: {line}", number + 1),
+                }
+            }
+            log::info!("stdin closed, stop reading commands");
+        });
+        Ok(Self { rx })
+    }
+
+    /// 非阻塞获取命令
+    pub fn try_recv(&self) -> Option<RemoteCommand> {
+        self.rx.try_recv().ok()
+    }
+}
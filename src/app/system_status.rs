@@ -0,0 +1,77 @@
+//! 上位机状态
+//!
+//! 设备状态页显示的电量和网络状态, 由后台线程每隔 10 秒读取一次,
+//! 主循环只取最近的结果, 读取较慢时也不会卡住界面.
+//! 需要启用 `system-status` 特性, 否则以及读取失败时均为未知:
+//!
+//! - 电量: 经 `starship-battery` 读取第一块电池, 支持 Linux、macOS 和 Windows
+//! - 网络: 查询到公网地址的路由, 只做本机路由查找, 不发送数据
+
+use std::sync::mpsc;
+#[cfg(feature = "system-status")]
+use std::time::Duration;
+
+/// 刷新间隔
+#[cfg(feature = "system-status")]
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 电量和网络状态
+type Reading = (Option<u8>, Option<bool>);
+
+/// 上位机电量和网络状态, `None` 表示未知
+#[derive(Debug, Default)]
+pub struct SystemStatus {
+    pub battery: Option<u8>,
+    pub network: Option<bool>,
+    rx: Option<mpsc::Receiver<Reading>>,
+}
+
+impl SystemStatus {
+    /// 取后台线程最近一次读取的结果, 第一次调用时启动线程
+    pub fn refresh(&mut self) {
+        let rx = self.rx.get_or_insert_with(start_reader);
+        if let Some((battery, network)) = rx.try_iter().last() {
+            self.battery = battery;
+            self.network = network;
+        }
+    }
+}
+
+/// 启动读取线程, 接收端销毁后线程在下次读取时退出
+fn start_reader() -> mpsc::Receiver<Reading> {
+    let (tx, rx) = mpsc::channel();
+    #[cfg(feature = "system-status")]
+    std::thread::spawn(move || {
+        let manager = starship_battery::Manager::new()
+            .inspect_err(|e| log::warn!("Failed to open battery manager: {e}"))
+            .ok();
+        while tx
+            .send((read_battery(manager.as_ref()), read_network()))
+            .is_ok()
+        {
+            std::thread::sleep(REFRESH_INTERVAL);
+        }
+    });
+    #[cfg(not(feature = "system-status"))]
+    drop(tx);
+    rx
+}
+
+#[cfg(feature = "system-status")]
+fn read_battery(manager: Option<&starship_battery::Manager>) -> Option<u8> {
+    use starship_battery::units::ratio::percent;
+
+    let battery = manager?
+        .batteries()
+        .ok()?
+        .find_map(|battery| battery.ok())?;
+    let capacity = battery.state_of_charge().get::<percent>();
+    Some(capacity.round().clamp(0.0, 100.0) as u8)
+}
+
+#[cfg(feature = "system-status")]
+fn read_network() -> Option<bool> {
+    // UDP connect 只查找路由, 有默认路由即视为已联网
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    Some(socket.connect("8.8.8.8:53").is_ok())
+}
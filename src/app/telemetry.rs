@@ -0,0 +1,176 @@
+//! 遥测输出
+//!
+//! 主循环按 [AppConfig::telemetry_interval_ms](super::config::AppConfig::telemetry_interval_ms)
+//! 生成一行 JSON, 后台线程写入 [AppConfig::telemetry_sink](super::config::AppConfig::telemetry_sink):
+//! `tcp://主机:端口` 发送到 TCP 连接, 断开后在下一行重连; 其他值视为文件路径, 追加写入
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// 待写入行数上限, 写入过慢时丢弃新数据
+const QUEUE_SIZE: usize = 64;
+/// TCP 连接超时
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// TCP 写超时, 对端不读取时放弃这一行并断开
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 输出目标
+enum Target {
+    File(File),
+    /// 连接在第一次写入时建立, 写入失败后断开
+    Tcp {
+        address: String,
+        stream: Option<TcpStream>,
+    },
+}
+
+impl Target {
+    /// 写入一行, TCP 未连接时先连接
+    fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        match self {
+            Target::File(file) => file.write_all(data)?,
+            Target::Tcp { address, stream } => {
+                let connected = match stream {
+                    Some(connected) => connected,
+                    None => stream.insert(connect(address)?),
+                };
+                if let Err(e) = connected.write_all(data) {
+                    *stream = None;
+                    return Err(e.into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 遥测输出目标, 销毁时丢弃未写入的数据, 不等待写入线程
+pub struct TelemetrySink {
+    tx: Option<mpsc::SyncSender<String>>,
+    running: Arc<AtomicBool>,
+}
+
+#[allow(dead_code)]
+impl TelemetrySink {
+    /// 打开输出目标并启动写入线程, 文件无法打开时返回错误
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - `tcp://主机:端口` 或文件路径
+    pub fn start(sink: &str) -> anyhow::Result<Self> {
+        let mut target = match sink.strip_prefix("tcp://") {
+            Some(address) => Target::Tcp {
+                address: address.to_string(),
+                stream: None,
+            },
+            None => {
+                if let Some(dir) = Path::new(sink).parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                Target::File(OpenOptions::new().create(true).append(true).open(sink)?)
+            }
+        };
+
+        let sink = sink.to_string();
+        let (tx, rx) = mpsc::sync_channel::<String>(QUEUE_SIZE);
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = running.clone();
+        thread::spawn(move || {
+            let mut failing = false;
+            for line in rx {
+                if !running_clone.load(Ordering::Relaxed) {
+                    break;
+                }
+                match target.write(line.as_bytes()) {
+                    Ok(()) => failing = false,
+                    // 只在第一次失败时记录, 避免每个周期刷屏
+                    Err(e) if !failing => {
+                        failing = true;
+                        log::warn!("Failed to write telemetry to {sink}: {e}");
+                    }
+                    Err(_) => {}
+                }
+            }
+        });
+        Ok(Self {
+            tx: Some(tx),
+            running,
+        })
+    }
+
+    /// 输出一行, 写入线程来不及处理时丢弃
+    pub fn emit(&self, json: &serde_json::Value) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.try_send(format!("{json}\n"));
+        }
+    }
+}
+
+impl Drop for TelemetrySink {
+    fn drop(&mut self) {
+        // 输出目标不可达时连接和写入都可能等待数秒, 退出时不等待写入线程,
+        // 线程写完当前一行后丢弃剩余数据并退出
+        self.running.store(false, Ordering::Relaxed);
+        self.tx = None;
+    }
+}
+
+/// 连接 TCP 输出目标
+fn connect(address: &str) -> anyhow::Result<TcpStream> {
+    let socket = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Cannot resolve {address}"))?;
+    let stream = TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    /// 接受连接但从不读取的对端
+    fn stalled_peer() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        (listener, address)
+    }
+
+    #[test]
+    fn write_times_out_when_peer_stops_reading() {
+        let (_listener, address) = stalled_peer();
+        let mut target = Target::Tcp {
+            address,
+            stream: None,
+        };
+        // 没有写超时时对端的接收缓冲区满后会一直阻塞
+        let line = vec![b'x'; 1024 * 1024];
+        let result = (0..64).try_for_each(|_| target.write(&line));
+        assert!(result.is_err());
+        assert!(matches!(target, Target::Tcp { stream: None, .. }));
+    }
+
+    #[test]
+    fn drop_does_not_wait_for_queued_lines() {
+        let (_listener, address) = stalled_peer();
+        let sink = TelemetrySink::start(&format!("tcp://{address}")).unwrap();
+        let payload = serde_json::Value::String("x".repeat(1024 * 1024));
+        for _ in 0..QUEUE_SIZE {
+            sink.emit(&payload);
+        }
+        thread::sleep(Duration::from_millis(100));
+        let start = Instant::now();
+        drop(sink);
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,271 @@
+//! 命令行参数
+//!
+//! 优先级: 命令行参数 > 配置文件 > 默认值. 命令行参数只覆盖本次运行的配置,
+//! 配置文件重新加载或切换档案后仍然生效; 保存配置时被覆盖的项保持配置文件中原来的值
+
+use crate::app::config::AppConfig;
+use crate::robot::{ServoState, TransportKind, SERVO_COUNT};
+use std::path::PathBuf;
+
+/// 帮助信息
+const USAGE: &str = "\
+用法: ele_bot [选项]
+
+选项:
+  --config <path>   使用指定的配置文件, 不读取配置档案
+  --model <path>    语音识别模型目录
+  --mic <name>      麦克风名称
+  --no-voice        不启动语音功能
+  --stdin-control   从管道读取控制命令, 每行一条: mood/servo/image/quit
+  --port <serial>   通过 CDC 虚拟串口连接设备
+  --simulate        连接模拟设备, 不访问硬件
+  --no-connect      启动时不自动连接, 不影响插入设备后的自动连接
+  --headless        不进入界面, 发送 --image 和/或 --pose 后退出
+  --image <path>    无界面模式下显示的图片
+  --pose <a,..,f>   无界面模式下的舵机角度, 6 个整数, 逗号分隔
+  -h, --help        显示帮助信息
+
+优先级: 命令行参数 > 配置文件 > 默认值";
+
+/// 解析后的命令行参数
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    /// 配置文件路径
+    pub config: Option<PathBuf>,
+    /// 语音识别模型目录
+    pub model: Option<String>,
+    /// 麦克风名称
+    pub mic: Option<String>,
+    /// 不启动语音功能
+    pub no_voice: bool,
+    /// 从标准输入读取控制命令
+    pub stdin_control: bool,
+    /// CDC 串口
+    pub port: Option<String>,
+    /// 连接模拟设备
+    pub simulate: bool,
+    /// 启动时不自动连接
+    pub no_connect: bool,
+    /// 无界面模式
+    pub headless: bool,
+    /// 无界面模式下显示的图片
+    pub image: Option<String>,
+    /// 无界面模式下的舵机角度
+    pub pose: Option<[i16; SERVO_COUNT]>,
+}
+
+impl Args {
+    /// 解析进程的命令行参数, 参数错误时打印帮助并退出
+    pub fn parse() -> Self {
+        match Self::parse_from(std::env::args().skip(1)) {
+            Ok(Some(args)) => args,
+            Ok(None) => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("{e}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// 从参数列表解析, 请求帮助时返回 `None`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - 不含程序名的参数列表, 支持 `--name value` 和 `--name=value` 两种写法
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> anyhow::Result<Option<Self>> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow::anyhow!("参数 {name} 缺少值"))
+            };
+            match name.as_str() {
+                "--config" => parsed.config = Some(PathBuf::from(value()?)),
+                "--model" => parsed.model = Some(value()?),
+                "--mic" => parsed.mic = Some(value()?),
+                "--port" => parsed.port = Some(value()?),
+                "--image" => parsed.image = Some(value()?),
+                "--pose" => parsed.pose = Some(parse_pose(&value()?)?),
+                "--no-voice" | "--stdin-control" | "--headless" | "--simulate" | "--no-connect"
+                | "-h" | "--help"
+                    if inline_value.is_some() =>
+                {
+                    anyhow::bail!("参数 {name} 不接受值")
+                }
+                "--no-voice" => parsed.no_voice = true,
+                "--stdin-control" => parsed.stdin_control = true,
+                "--headless" => parsed.headless = true,
+                "--simulate" => parsed.simulate = true,
+                "--no-connect" => parsed.no_connect = true,
+                "-h" | "--help" => return Ok(None),
+                _ => anyhow::bail!("未知参数: {name}"),
+            }
+        }
+        anyhow::ensure!(
+            parsed.headless || (parsed.image.is_none() && parsed.pose.is_none()),
+            "--image 和 --pose 需要配合 --headless 使用"
+        );
+        Ok(Some(parsed))
+    }
+
+    /// 加载配置并应用命令行覆盖
+    pub fn load_config(&self) -> AppConfig {
+        let mut config = match &self.config {
+            Some(path) => AppConfig::load_file(path),
+            None => AppConfig::load(),
+        };
+        self.apply(&mut config);
+        config
+    }
+
+    /// 用命令行参数覆盖配置中的对应项
+    pub fn apply(&self, config: &mut AppConfig) {
+        if let Some(model) = &self.model {
+            config.model_path = model.clone();
+        }
+        if let Some(mic) = &self.mic {
+            config.speech_name = mic.clone();
+        }
+        if let Some(port) = &self.port {
+            config.transport = TransportKind::Cdc;
+            config.cdc_port = port.clone();
+        }
+        if self.simulate {
+            config.transport = TransportKind::Sim;
+        }
+        if self.no_connect {
+            config.connect_on_startup = false;
+        }
+    }
+
+    /// 把被命令行参数覆盖的项恢复为 `saved` 中的值, 保存配置前调用,
+    /// 命令行参数只覆盖本次运行, 不写入配置文件
+    pub fn restore(&self, config: &mut AppConfig, saved: &AppConfig) {
+        if self.model.is_some() {
+            config.model_path = saved.model_path.clone();
+        }
+        if self.mic.is_some() {
+            config.speech_name = saved.speech_name.clone();
+        }
+        if self.port.is_some() {
+            config.cdc_port = saved.cdc_port.clone();
+        }
+        if self.port.is_some() || self.simulate {
+            config.transport = saved.transport;
+        }
+        if self.no_connect {
+            config.connect_on_startup = saved.connect_on_startup;
+        }
+    }
+}
+
+/// 解析 `a,b,c,d,e,f` 格式的舵机角度, 数量不对或超出舵机范围时返回错误
+fn parse_pose(text: &str) -> anyhow::Result<[i16; SERVO_COUNT]> {
+    let values = text
+        .split(',')
+        .map(|v| v.trim().parse::<i16>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("--pose 格式错误: {e}"))?;
+    let pose: [i16; SERVO_COUNT] = values
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("--pose 需要 {SERVO_COUNT} 个角度"))?;
+    for (i, &value) in pose.iter().enumerate() {
+        anyhow::ensure!(
+            ServoState::clamp_angle(i, value) == value,
+            "--pose {} 超出范围 {}",
+            ServoState::name(i),
+            ServoState::range_str(i)
+        );
+    }
+    Ok(pose)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> anyhow::Result<Option<Args>> {
+        Args::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn value_in_separate_or_inline_form() {
+        for args in [
+            &["--port", "COM3", "--model", "m"][..],
+            &["--port=COM3", "--model=m"][..],
+        ] {
+            let parsed = parse(args).unwrap().unwrap();
+            assert_eq!(parsed.port.as_deref(), Some("COM3"));
+            assert_eq!(parsed.model.as_deref(), Some("m"));
+        }
+        // 值中的 = 保留
+        let parsed = parse(&["--mic=USB=1"]).unwrap().unwrap();
+        assert_eq!(parsed.mic.as_deref(), Some("USB=1"));
+    }
+
+    #[test]
+    fn flags_set_fields() {
+        let parsed = parse(&[
+            "--no-voice",
+            "--simulate",
+            "--no-connect",
+            "--stdin-control",
+        ])
+        .unwrap()
+        .unwrap();
+        assert!(parsed.no_voice && parsed.simulate && parsed.no_connect && parsed.stdin_control);
+        assert!(!parsed.headless);
+    }
+
+    #[test]
+    fn missing_value_is_error() {
+        let e = parse(&["--port"]).unwrap_err();
+        assert!(e.to_string().contains("--port"), "{e}");
+    }
+
+    #[test]
+    fn flag_with_value_is_error() {
+        assert!(parse(&["--no-voice=1"]).is_err());
+        assert!(parse(&["--help=yes"]).is_err());
+    }
+
+    #[test]
+    fn unknown_argument_is_error() {
+        let e = parse(&["--nope"]).unwrap_err();
+        assert!(e.to_string().contains("--nope"), "{e}");
+        assert!(parse(&["image.png"]).is_err());
+    }
+
+    #[test]
+    fn help_returns_none() {
+        assert!(parse(&["-h"]).unwrap().is_none());
+        assert!(parse(&["--simulate", "--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn image_and_pose_need_headless() {
+        assert!(parse(&["--image", "a.png"]).is_err());
+        assert!(parse(&["--pose", "0,0,0,0,0,0"]).is_err());
+        let parsed = parse(&["--headless", "--image", "a.png", "--pose=0,0,0,0,0,0"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.image.as_deref(), Some("a.png"));
+        assert_eq!(parsed.pose, Some([0; SERVO_COUNT]));
+    }
+
+    #[test]
+    fn invalid_pose_is_error() {
+        assert!(parse(&["--headless", "--pose", "0,0,0"]).is_err());
+        assert!(parse(&["--headless", "--pose", "a,0,0,0,0,0"]).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! 无界面模式
+//!
+//! 打开设备后发送一张图片和/或一个姿势, 重复几次确保送达后退出, 供脚本和定时任务使用
+
+use crate::app::config::AppConfig;
+use crate::robot::{self, DisplayMode, Joint, Lcd, SERVO_COUNT};
+use std::thread;
+use std::time::Duration;
+
+/// 重复发送的次数, 设备偶尔会丢掉刚连上时的第一帧
+const SEND_REPEAT: usize = 3;
+
+/// 发送一帧后退出
+///
+/// 只指定图片时舵机保持掉电, 不会移动; 只指定姿势时屏幕显示眼睛
+///
+/// # Arguments
+///
+/// * `config` - 已应用命令行覆盖的配置, 使用其中的通信方式、校准偏移和发送间隔
+/// * `image` - 要显示的图片路径
+/// * `pose` - 各舵机目标角度
+pub fn run(
+    config: &AppConfig,
+    image: Option<&str>,
+    pose: Option<[i16; SERVO_COUNT]>,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        image.is_some() || pose.is_some(),
+        "--headless requires --image and/or --pose"
+    );
+
+    let mut lcd = Lcd::new();
+    lcd.set_tone(config.brightness, config.gamma);
+    lcd.set_orientation(config.orientation());
+    lcd.set_pixel_format(config.pixel_format);
+    if let Some(path) = image {
+        lcd.load_image(path)?;
+        lcd.set_mode(DisplayMode::Static);
+    }
+    let mut joint = Joint::new();
+    joint.set_calibration(config.calibration);
+    match pose {
+        Some(values) => {
+            joint.set_targets(&values);
+            // 不做插值, 直接发送目标角度
+            joint.step(0.0);
+        }
+        None => joint.set_enabled(false),
+    }
+
+    let mut transport = robot::transport::open_transport(&config.transport_config())?;
+    lcd.generate_pixels();
+    let mut pixels = Vec::new();
+    lcd.write_output(&mut pixels);
+    let joint_config = joint.config();
+    for _ in 0..SEND_REPEAT {
+        transport.send_frame(&pixels, &joint_config)?;
+        thread::sleep(Duration::from_millis(config.frame_interval_ms));
+    }
+    log::info!("Headless frame sent {SEND_REPEAT} times");
+    Ok(())
+}
@@ -11,7 +11,11 @@ pub enum DeviceEvent {
     Prev,
     Increase,
     Decrease,
+    IncreaseBig,
+    DecreaseBig,
     Screenshot,
+    ToggleTorque,
+    Home,
 }
 
 /// 处理设备控制事件
@@ -26,9 +30,24 @@ pub fn handle(app: &mut App, event: DeviceEvent) {
         }
         DeviceEvent::Next => app.joint.next_servo(),
         DeviceEvent::Prev => app.joint.prev_servo(),
-        DeviceEvent::Increase => app.joint.increase(),
-        DeviceEvent::Decrease => app.joint.decrease(),
+        DeviceEvent::Increase => app.step_servo(1),
+        DeviceEvent::Decrease => app.step_servo(-1),
+        DeviceEvent::IncreaseBig => app.joint.increase_big(),
+        DeviceEvent::DecreaseBig => app.joint.decrease_big(),
+        DeviceEvent::Home => {
+            app.joint.home(&app.config.home_pose);
+            app.log_queue.info("舵机回到零位");
+        }
+        DeviceEvent::ToggleTorque => {
+            let enabled = !app.joint.is_enabled();
+            app.joint.set_enabled(enabled);
+            log::info!(
+                "Servo torque {}",
+                if enabled { "enabled" } else { "disabled" }
+            );
+        }
         DeviceEvent::Screenshot => {
+            // 保存路径由 take_screenshot 写入日志
             if let Err(e) = app.take_screenshot() {
                 log::error!("Screenshot failed: {}", e);
             }
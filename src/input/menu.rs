@@ -1,34 +1,37 @@
 //! 菜单事件
 
-use crate::app::App;
+use crate::app::{App, ConnectionState};
 
 /// 菜单事件
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuEvent {
     Up,
     Down,
+    /// 跳转到第 n 个菜单项 (从 0 开始)
+    Select(usize),
     ConnectDevice,
     EnterServoMode,
     EnterSettingMode,
+    EnterLogView,
 }
 
 /// 处理菜单事件
 pub fn handle(app: &mut App, event: MenuEvent) {
     // 如果在舵机模式或设置模式中，不处理菜单事件
-    if app.in_servo_mode || app.in_settings {
+    if app.in_servo_mode || app.in_settings || app.in_log_view {
         return;
     }
 
     match event {
         MenuEvent::Up => app.prev_menu(),
         MenuEvent::Down => app.next_menu(),
-        MenuEvent::ConnectDevice => {
-            if app.is_connected() {
-                app.stop_comm_thread();
-            } else {
-                app.connect_robot();
-            }
-        }
+        MenuEvent::Select(index) => app.select_menu(index),
+        MenuEvent::ConnectDevice => match app.connection_state() {
+            ConnectionState::Connected => app.stop_comm_thread(),
+            ConnectionState::Disconnected => app.connect_robot(),
+            // 连接中由弹窗处理, 按 Esc 取消
+            ConnectionState::Connecting => {}
+        },
         MenuEvent::EnterServoMode => {
             if matches!(app.selected_menu, crate::app::MenuItem::DeviceControl) {
                 app.in_servo_mode = true;
@@ -36,6 +39,14 @@ pub fn handle(app: &mut App, event: MenuEvent) {
                 app.left_focused = false;
             }
         }
+        MenuEvent::EnterLogView => {
+            if app.selected_menu == crate::app::MenuItem::Log {
+                app.in_log_view = true;
+                app.log_view.scroll = 0;
+                // 进入日志页面时，焦点切换到右侧
+                app.left_focused = false;
+            }
+        }
         MenuEvent::EnterSettingMode => {
             if app.selected_menu == crate::app::MenuItem::Settings {
                 app.in_settings = true;
@@ -9,7 +9,9 @@ pub use menu::MenuEvent;
 pub use settings::SettingsEvent;
 
 use crate::app::{App, MenuItem};
-use crossterm::event::{KeyCode, KeyModifiers};
+use crate::robot::ServoState;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
 
 /// 通用事件
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,7 +57,7 @@ impl From<SettingsEvent> for AppEvent {
 pub fn handle_event(app: &mut App, event: AppEvent) {
     match event {
         AppEvent::Common(CommonEvent::Quit) => {
-            app.quit();
+            app.request_quit();
         }
         AppEvent::Common(CommonEvent::None) => {}
         AppEvent::Menu(e) => menu::handle(app, e),
@@ -80,6 +82,38 @@ pub fn handle_by_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         handle_popup_mode(app, code);
         return;
     }
+    if app.select_popup.is_visible() {
+        handle_select_popup_mode(app, code);
+        return;
+    }
+    if app.in_pose_name_mode {
+        handle_pose_name_mode(app, code);
+        return;
+    }
+    if app.in_angle_edit_mode {
+        handle_angle_edit_mode(app, code);
+        return;
+    }
+    if app.in_lcd_text_mode {
+        handle_lcd_text_mode(app, code);
+        return;
+    }
+
+    if app.log_popup.is_searching() {
+        handle_log_search_mode(app, code);
+        return;
+    }
+    // 日志弹窗显示时按 / 搜索, 编辑设置时 / 作为普通字符
+    if code == KeyCode::Char('/') && app.log_popup.is_visible() && !app.in_edit_settings_mode {
+        app.log_popup.begin_search();
+        return;
+    }
+    if app.in_log_view {
+        handle_log_view_mode(app, code);
+        return;
+    }
+
+    let nav_code = nav_code(code, app.config.vim_keys, app.in_edit_settings_mode);
 
     // 使用模式元组进行模式匹配
     match (
@@ -88,20 +122,190 @@ pub fn handle_by_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         app.in_settings,
     ) {
         // 编辑模式：处理设置项内容编辑
-        (true, _, _) => handle_edit_settings_mode(app, code),
+        (true, _, _) => handle_edit_settings_mode(app, code, modifiers),
         // 设备控制模式：处理舵机角度调整
-        (_, true, _) => handle_servo_mode(app, code),
+        (_, true, _) => handle_servo_mode(app, nav_code),
         // 设置模式：处理配置项选择
-        (_, _, true) => handle_settings_mode(app, code),
+        (_, _, true) => handle_settings_mode(app, nav_code, modifiers),
         // 菜单模式：处理侧边栏导航
-        _ => handle_menu_mode(app, code, modifiers),
+        _ => handle_menu_mode(app, nav_code, modifiers),
+    }
+}
+
+/// 导航模式下实际生效的按键
+///
+/// 开启 vim 按键时 h/j/k/l 转为方向键; 编辑文字时不转换, h/j/k/l 作为普通字符输入
+///
+/// # Arguments
+///
+/// * `code` - 按键代码
+/// * `vim_keys` - 是否开启 vim 按键
+/// * `text_entry` - 是否正在编辑文字
+fn nav_code(code: KeyCode, vim_keys: bool, text_entry: bool) -> KeyCode {
+    if vim_keys && !text_entry {
+        vim_to_arrow(code)
+    } else {
+        code
+    }
+}
+
+/// Vim 风格按键转换为方向键: h/j/k/l 对应 ←/↓/↑/→, 其他按键不变
+///
+/// # Arguments
+///
+/// * `code` - 按键代码
+fn vim_to_arrow(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char('h') => KeyCode::Left,
+        KeyCode::Char('j') => KeyCode::Down,
+        KeyCode::Char('k') => KeyCode::Up,
+        KeyCode::Char('l') => KeyCode::Right,
+        _ => code,
     }
 }
 
+/// 当前模式的按键提示, 每个元素为一行
+///
+/// 判断顺序与 [handle_by_mode] 一致, 保证提示与实际生效的按键对应
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+pub fn key_hints(app: &App) -> &'static [&'static str] {
+    if app.popup.is_visible() {
+        if app.in_quit_confirm {
+            return &["[Y] 确定退出  [N/Esc] 取消"];
+        }
+        return &["[Esc] 取消"];
+    }
+    if app.select_popup.is_visible() {
+        return &["[↑/↓] 选择  [Enter] 确认  [Esc] 取消"];
+    }
+    if app.in_pose_name_mode {
+        return &["输入姿势名称: [Enter] 保存  [Esc] 取消  [Backspace] 删除字符"];
+    }
+    if app.in_angle_edit_mode {
+        return &["输入角度: [Enter] 确定  [Esc] 取消  [Backspace] 删除字符"];
+    }
+    if app.in_lcd_text_mode {
+        return &["输入屏幕文字: [Enter] 显示  [Esc] 取消  [Backspace] 删除字符"];
+    }
+    if app.log_popup.is_searching() {
+        return &["搜索日志: [Enter] 确定  [Esc] 清除  [Backspace] 删除字符"];
+    }
+    if app.in_log_view {
+        return &[
+            "[↑/↓] 滚动  [PgUp/PgDn] 翻页  [Home/End] 最旧/最新  [f] 过滤  [c] 清空  [e] 导出  [Esc] 返回",
+        ];
+    }
+
+    match (
+        app.in_edit_settings_mode,
+        app.in_servo_mode,
+        app.in_settings,
+    ) {
+        (true, _, _) => &["[Enter] 保存  [Esc] 取消  [Backspace] 删除字符  [Ctrl+R] 显示/隐藏密码"],
+        (_, true, _) if app.config.vim_keys => &[
+            "[k/j] 选择舵机  [h/l] ±1°  [a] -5°  [d] +5°  [1-9/-] 输入角度  [0] 回零  [s] 截图  [e] 使能/掉电  [p] 姿势  [n] 保存姿势  [Esc] 返回",
+            "动作: [r] 录制/停止  [L] 播放  [o] 循环  [b] 脚本  [x] 停止  屏幕: [m] 模式  [g] 动图/眼睛  [t] 文字  [c] 录屏  眼睛: [f] 表情  [K] 注视",
+        ],
+        (_, true, _) => &[
+            "[↑/↓] 选择舵机  [←/→] ±1°  [a] -5°  [d] +5°  [1-9/-] 输入角度  [0] 回零  [s] 截图  [e] 使能/掉电  [p] 姿势  [n] 保存姿势  [Esc] 返回",
+            "动作: [r] 录制/停止  [l] 播放  [o] 循环  [b] 脚本  [x] 停止  屏幕: [m] 模式  [g] 动图/眼睛  [t] 文字  [c] 录屏  眼睛: [f] 表情  [k] 注视",
+        ],
+        (_, _, true) if app.config.vim_keys => {
+            &["[k/j] 选择  [Enter] 编辑  [Ctrl+R] 显示/隐藏密码  [Esc] 退出"]
+        }
+        (_, _, true) => &["[↑/↓] 选择  [Enter] 编辑  [Ctrl+R] 显示/隐藏密码  [Esc] 退出"],
+        _ if app.config.vim_keys => {
+            &["[k/j] 选择菜单  [Enter] 进入  [Ctrl+L] 日志  [Ctrl+S] 保存设置  [Esc] 退出"]
+        }
+        _ => &["[↑/↓] 选择菜单  [Enter] 进入  [Ctrl+L] 日志  [Ctrl+S] 保存设置  [Esc] 退出"],
+    }
+}
+
+/// 鼠标输入处理
+///
+/// - 左键点击侧边栏：选中对应菜单项
+/// - 左键点击或拖动设备控制页的进度条：按位置设置舵机角度
+///
+/// 弹窗或文字输入期间忽略鼠标
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `event` - 鼠标事件
+pub fn handle_mouse(app: &mut App, event: MouseEvent) {
+    if app.popup.is_visible()
+        || app.select_popup.is_visible()
+        || app.in_pose_name_mode
+        || app.in_angle_edit_mode
+        || app.in_lcd_text_mode
+        || app.in_edit_settings_mode
+        || app.log_popup.is_searching()
+    {
+        app.dragging_servo = None;
+        return;
+    }
+
+    let position = Position::new(event.column, event.row);
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let areas = app.mouse_areas;
+            if areas.menu.contains(position) {
+                let index = (event.row - areas.menu.y) as usize + app.menu_state.offset();
+                app.in_servo_mode = false;
+                app.in_settings = false;
+                app.in_log_view = false;
+                app.left_focused = true;
+                app.select_menu(index);
+                return;
+            }
+            if app.selected_menu != MenuItem::DeviceControl {
+                return;
+            }
+            if let Some(index) = areas
+                .gauge_bars
+                .iter()
+                .position(|bar| bar.contains(position))
+            {
+                // 点击进度条进入设备控制模式
+                app.in_servo_mode = true;
+                app.left_focused = false;
+                app.joint.select_servo(index);
+                app.dragging_servo = Some(index);
+                drag_servo(app, index, event.column);
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some(index) = app.dragging_servo {
+                drag_servo(app, index, event.column);
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => app.dragging_servo = None,
+        _ => {}
+    }
+}
+
+/// 按鼠标在进度条上的横向位置设置舵机角度, 超出进度条时取两端
+fn drag_servo(app: &mut App, index: usize, column: u16) {
+    let bar: Rect = app.mouse_areas.gauge_bars[index];
+    if bar.width < 2 || app.joint.selected() != index {
+        return;
+    }
+    let offset = column.clamp(bar.x, bar.x + bar.width - 1) - bar.x;
+    let min = ServoState::min_angle(index) as i32;
+    let max = ServoState::max_angle(index) as i32;
+    let span = (bar.width - 1) as i32;
+    let value = min + ((max - min) * offset as i32 + span / 2) / span;
+    app.joint.set_selected_value(value as i16);
+}
+
 /// 菜单模式输入处理
 ///
 /// 处理侧边栏导航相关的按键输入：
 /// - 上/下方向键：切换菜单项
+/// - 1~5 数字键：直接跳转到对应菜单项
 /// - 回车键：进入对应功能页面
 /// - ESC键：退出程序
 /// - Ctrl+S：保存设置
@@ -112,18 +316,30 @@ pub fn handle_by_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
 /// * `code` - 按键代码
 /// * `modifiers` - 修饰键状态
 fn handle_menu_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
-    let evt = match code {
+    let evt = menu_event(code, modifiers, app.selected_menu);
+    handle_event(app, evt);
+}
+
+/// 菜单模式下按键对应的事件
+///
+/// # Arguments
+///
+/// * `code` - 按键代码
+/// * `modifiers` - 修饰键状态
+/// * `selected` - 当前选中的菜单项, 决定回车键的事件
+fn menu_event(code: KeyCode, modifiers: KeyModifiers, selected: MenuItem) -> AppEvent {
+    match code {
         KeyCode::Esc => CommonEvent::Quit.into(),
         KeyCode::Up => MenuEvent::Up.into(),
         KeyCode::Down => MenuEvent::Down.into(),
-        KeyCode::Enter => handle_menu_enter(app),
+        KeyCode::Char(c @ '1'..='9') => MenuEvent::Select(c as usize - '1' as usize).into(),
+        KeyCode::Enter => menu_enter_event(selected),
         KeyCode::Char('s') if modifiers == KeyModifiers::CONTROL => SettingsEvent::Save.into(),
         _ => CommonEvent::None.into(),
-    };
-    handle_event(app, evt);
+    }
 }
 
-/// 处理菜单模式下的回车键
+/// 菜单模式下回车键对应的事件
 ///
 /// 根据当前选中的菜单项触发相应的事件：
 /// - 设备控制：进入伺服模式
@@ -132,15 +348,16 @@ fn handle_menu_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
 ///
 /// # Arguments
 ///
-/// * `app` - 应用状态
+/// * `selected` - 当前选中的菜单项
 ///
 /// # Returns
 ///
 /// 对应的事件
-fn handle_menu_enter(app: &mut App) -> AppEvent {
-    match app.selected_menu {
+fn menu_enter_event(selected: MenuItem) -> AppEvent {
+    match selected {
         MenuItem::DeviceControl => MenuEvent::EnterServoMode.into(),
         MenuItem::Settings => MenuEvent::EnterSettingMode.into(),
+        MenuItem::Log => MenuEvent::EnterLogView.into(),
         _ => MenuEvent::ConnectDevice.into(),
     }
 }
@@ -151,7 +368,23 @@ fn handle_menu_enter(app: &mut App) -> AppEvent {
 /// - 焦点在左侧时：退出伺服模式
 /// - 上/下方向键：切换选中关节
 /// - 左/右方向键：减小/增大关节角度
+/// - A/D键、PageDown/PageUp：角度 -5°/+5°
 /// - S键：截图保存
+/// - 0键：所有舵机回到零位
+/// - 1~9/负号键：直接输入当前舵机角度
+/// - E键：舵机使能/掉电
+/// - P键：选择保存的姿势
+/// - N键：保存当前姿势
+/// - R键：开始/停止动作录制
+/// - L键：选择并播放动作
+/// - O键：切换循环播放
+/// - X键：停止播放
+/// - G键：播放动图/切回眼睛
+/// - T键：在屏幕上显示文字
+/// - M键：选择显示模式 (眼睛/时钟/测试图案)
+/// - C键：开始/停止录屏 (GIF)
+/// - F键：切换表情
+/// - K键：切换注视方向
 /// - ESC/回车键：退出伺服模式
 ///
 /// # Arguments
@@ -163,6 +396,10 @@ fn handle_servo_mode(app: &mut App, code: KeyCode) {
         app.in_servo_mode = false;
         return;
     }
+    if let Some(evt) = servo_event(code) {
+        handle_event(app, evt.into());
+        return;
+    }
 
     match code {
         KeyCode::Esc => {
@@ -173,15 +410,50 @@ fn handle_servo_mode(app: &mut App, code: KeyCode) {
             app.toggle_focus();
             app.in_servo_mode = false;
         }
-        KeyCode::Up => handle_event(app, DeviceEvent::Prev.into()),
-        KeyCode::Down => handle_event(app, DeviceEvent::Next.into()),
-        KeyCode::Left => handle_event(app, DeviceEvent::Decrease.into()),
-        KeyCode::Right => handle_event(app, DeviceEvent::Increase.into()),
-        KeyCode::Char('s') => handle_event(app, DeviceEvent::Screenshot.into()),
+        KeyCode::Char(c @ ('1'..='9' | '-')) => app.begin_angle_edit(c),
+        KeyCode::Char('p') => app.open_pose_select(),
+        KeyCode::Char('n') => app.begin_pose_name(),
+        KeyCode::Char('r') => {
+            if let Err(e) = app.toggle_motion_recording() {
+                app.log_queue.error(format!("保存动作失败: {e}"));
+            }
+        }
+        // 开启 vim 按键时 l/k 用于方向, 大写 L/K 始终可用
+        KeyCode::Char('l' | 'L') => app.open_motion_select(),
+        KeyCode::Char('o') => app.toggle_motion_loop(),
+        KeyCode::Char('x') => app.stop_motion(),
+        KeyCode::Char('b') => app.open_script_select(),
+        KeyCode::Char('g') => app.toggle_gif(),
+        KeyCode::Char('t') => app.begin_lcd_text(),
+        KeyCode::Char('m') => app.open_display_mode_select(),
+        KeyCode::Char('c') => app.toggle_capture(),
+        KeyCode::Char('f') => app.next_eyes_mood(),
+        KeyCode::Char('k' | 'K') => app.next_eyes_position(),
         _ => {}
     }
 }
 
+/// 设备控制模式下直接对应 [DeviceEvent] 的按键, 其他按键返回 `None`
+///
+/// # Arguments
+///
+/// * `code` - 按键代码
+fn servo_event(code: KeyCode) -> Option<DeviceEvent> {
+    let evt = match code {
+        KeyCode::Up => DeviceEvent::Prev,
+        KeyCode::Down => DeviceEvent::Next,
+        KeyCode::Left => DeviceEvent::Decrease,
+        KeyCode::Right => DeviceEvent::Increase,
+        KeyCode::Char('a') | KeyCode::PageDown => DeviceEvent::DecreaseBig,
+        KeyCode::Char('d') | KeyCode::PageUp => DeviceEvent::IncreaseBig,
+        KeyCode::Char('s') => DeviceEvent::Screenshot,
+        KeyCode::Char('e') => DeviceEvent::ToggleTorque,
+        KeyCode::Char('0') => DeviceEvent::Home,
+        _ => return None,
+    };
+    Some(evt)
+}
+
 /// 设置模式输入处理
 ///
 /// 处理设置界面的按键输入：
@@ -189,31 +461,122 @@ fn handle_servo_mode(app: &mut App, code: KeyCode) {
 /// - 上/下方向键：切换设置项
 /// - 回车键：进入编辑模式
 /// - ESC键：退出设置模式
+/// - Ctrl+R：切换密码等敏感内容的明文显示
 ///
 /// # Arguments
 ///
 /// * `app` - 应用状态
 /// * `code` - 按键代码
-fn handle_settings_mode(app: &mut App, code: KeyCode) {
+/// * `modifiers` - 修饰键状态
+fn handle_settings_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
     if app.left_focused {
         app.in_settings = false;
         return;
     }
 
+    let Some(evt) = settings_event(code, modifiers) else {
+        return;
+    };
+    if evt == SettingsEvent::Exit {
+        app.toggle_focus();
+        app.in_settings = false;
+    }
+    handle_event(app, evt.into());
+}
+
+/// 设置模式下按键对应的事件, 没有对应事件时返回 `None`
+///
+/// # Arguments
+///
+/// * `code` - 按键代码
+/// * `modifiers` - 修饰键状态
+fn settings_event(code: KeyCode, modifiers: KeyModifiers) -> Option<SettingsEvent> {
     let evt = match code {
-        KeyCode::Esc => {
+        KeyCode::Esc => SettingsEvent::Exit,
+        KeyCode::Enter => SettingsEvent::EnterEdit,
+        KeyCode::Up => SettingsEvent::Up,
+        KeyCode::Down => SettingsEvent::Down,
+        KeyCode::Char('r') if modifiers == KeyModifiers::CONTROL => SettingsEvent::ToggleReveal,
+        _ => return None,
+    };
+    Some(evt)
+}
+
+/// 日志弹窗搜索输入处理
+///
+/// 输入时实时过滤弹窗中的日志：
+/// - ESC键：清除搜索词
+/// - 回车键：保留搜索词并结束输入
+/// - 退格键：删除最后一个字符
+/// - 普通字符：追加到搜索词
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_log_search_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.log_popup.end_search(false),
+        KeyCode::Enter => app.log_popup.end_search(true),
+        KeyCode::Backspace => {
+            app.log_popup.filter.pop();
+        }
+        KeyCode::Char(c) => {
+            app.log_popup.filter.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// 日志页面输入处理
+///
+/// - 上/下方向键：滚动一行
+/// - PageUp/PageDown：滚动一页
+/// - Home/End：跳到最旧/最新
+/// - F键：切换过滤级别
+/// - C键：清空日志
+/// - E键：导出日志到文件
+/// - ESC/回车键：退出日志页面
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_log_view_mode(app: &mut App, code: KeyCode) {
+    if app.left_focused {
+        app.in_log_view = false;
+        return;
+    }
+
+    let total = app
+        .log_queue
+        .entries()
+        .filter(|entry| entry.level >= app.log_view.min_level)
+        .count();
+    match code {
+        KeyCode::Esc | KeyCode::Enter => {
             app.toggle_focus();
-            app.in_settings = false;
-            SettingsEvent::Exit.into()
+            app.in_log_view = false;
         }
-        KeyCode::Enter => SettingsEvent::EnterEdit.into(),
-        KeyCode::Up => SettingsEvent::Up.into(),
-        KeyCode::Down => SettingsEvent::Down.into(),
-        _ => CommonEvent::None.into(),
-    };
-    handle_event(app, evt);
+        KeyCode::Up => app.log_view.scroll_up(1, total),
+        KeyCode::Down => app.log_view.scroll_down(1),
+        KeyCode::PageUp => app.log_view.scroll_up(LOG_PAGE_LINES, total),
+        KeyCode::PageDown => app.log_view.scroll_down(LOG_PAGE_LINES),
+        KeyCode::Home => app.log_view.scroll_up(total, total),
+        KeyCode::End => app.log_view.scroll = 0,
+        KeyCode::Char('f') => app.log_view.cycle_filter(),
+        KeyCode::Char('c') => {
+            app.log_queue.clear();
+            app.log_view.scroll = 0;
+        }
+        KeyCode::Char('e') => app.export_logs(),
+        _ => {}
+    }
 }
 
+/// 日志页面翻页行数
+const LOG_PAGE_LINES: usize = 10;
+
 /// 编辑模式输入处理
 ///
 /// 处理设置项内容编辑的按键输入：
@@ -221,12 +584,18 @@ fn handle_settings_mode(app: &mut App, code: KeyCode) {
 /// - 回车键：确认保存修改
 /// - 退格键：删除最后一个字符
 /// - 普通字符：追加到编辑缓冲区
+/// - Ctrl+R：切换密码等敏感内容的明文显示
 ///
 /// # Arguments
 ///
 /// * `app` - 应用状态
 /// * `code` - 按键代码
-fn handle_edit_settings_mode(app: &mut App, code: KeyCode) {
+/// * `modifiers` - 修饰键状态
+fn handle_edit_settings_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    if code == KeyCode::Char('r') && modifiers == KeyModifiers::CONTROL {
+        app.reveal_secrets = !app.reveal_secrets;
+        return;
+    }
     match code {
         KeyCode::Esc => app.cancel_settings_edit(),
         KeyCode::Enter => app.save_settings_edit(),
@@ -240,16 +609,199 @@ fn handle_edit_settings_mode(app: &mut App, code: KeyCode) {
     }
 }
 
+/// 姿势名称输入处理
+///
+/// - ESC键：取消保存
+/// - 回车键：保存当前姿势
+/// - 退格键：删除最后一个字符
+/// - 普通字符：追加到名称
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_pose_name_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.finish_pose_name(false),
+        KeyCode::Enter => app.finish_pose_name(true),
+        KeyCode::Backspace => {
+            app.edit_buffer.pop();
+        }
+        KeyCode::Char(c) => {
+            app.edit_buffer.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// 屏幕文字输入处理
+///
+/// - ESC键：取消输入
+/// - 回车键：在屏幕上显示文字
+/// - 退格键：删除最后一个字符
+/// - 普通字符：追加到文字
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_lcd_text_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.finish_lcd_text(false),
+        KeyCode::Enter => app.finish_lcd_text(true),
+        KeyCode::Backspace => {
+            app.edit_buffer.pop();
+        }
+        KeyCode::Char(c) => {
+            app.edit_buffer.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// 舵机角度输入处理
+///
+/// - ESC键：取消输入
+/// - 回车键：设置当前舵机角度
+/// - 退格键：删除最后一个字符
+/// - 数字/负号：追加到输入
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_angle_edit_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc => app.finish_angle_edit(false),
+        KeyCode::Enter => app.finish_angle_edit(true),
+        KeyCode::Backspace => {
+            app.edit_buffer.pop();
+        }
+        KeyCode::Char(c @ ('0'..='9' | '-')) => {
+            app.edit_buffer.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// 选择弹窗输入处理
+///
+/// - 上/下方向键：切换选项
+/// - 回车键：确认选择
+/// - ESC键：关闭弹窗
+///
+/// # Arguments
+///
+/// * `app` - 应用状态
+/// * `code` - 按键代码
+fn handle_select_popup_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Up => app.select_popup.prev(),
+        KeyCode::Down => app.select_popup.next(),
+        KeyCode::Enter => app.confirm_select(),
+        KeyCode::Esc => app.select_popup.close(),
+        _ => {}
+    }
+}
+
 /// 弹窗模式输入处理
 ///
-/// 处理模态弹窗的按键输入，目前仅响应ESC键关闭弹窗
+/// 处理模态弹窗的按键输入：
+/// - 退出确认弹窗：Y 键断开设备并退出，N/ESC 键取消
+/// - 其他弹窗：ESC 键断开连接并关闭弹窗
 ///
 /// # Arguments
 ///
 /// * `app` - 应用状态
 /// * `code` - 按键代码
 fn handle_popup_mode(app: &mut App, code: KeyCode) {
+    if app.in_quit_confirm {
+        match code {
+            KeyCode::Char('y' | 'Y') => app.confirm_quit(true),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => app.confirm_quit(false),
+            _ => {}
+        }
+        return;
+    }
     if matches!(code, KeyCode::Esc) {
         app.stop_comm_thread();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+
+    /// 导航模式下按键经 vim 转换后的实际按键
+    fn nav(c: char, vim_keys: bool) -> KeyCode {
+        nav_code(KeyCode::Char(c), vim_keys, false)
+    }
+
+    #[test]
+    fn vim_keys_map_to_arrows() {
+        assert_eq!(nav('h', true), KeyCode::Left);
+        assert_eq!(nav('j', true), KeyCode::Down);
+        assert_eq!(nav('k', true), KeyCode::Up);
+        assert_eq!(nav('l', true), KeyCode::Right);
+        assert_eq!(nav('x', true), KeyCode::Char('x'));
+    }
+
+    #[test]
+    fn vim_keys_off_keeps_letters() {
+        for c in ['h', 'j', 'k', 'l'] {
+            assert_eq!(nav(c, false), KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn text_entry_keeps_letters() {
+        for c in ['h', 'j', 'k', 'l'] {
+            assert_eq!(nav_code(KeyCode::Char(c), true, true), KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn servo_mode_vim_keys() {
+        let event = |c| servo_event(nav(c, true));
+        assert_eq!(event('h'), Some(DeviceEvent::Decrease));
+        assert_eq!(event('j'), Some(DeviceEvent::Next));
+        assert_eq!(event('k'), Some(DeviceEvent::Prev));
+        assert_eq!(event('l'), Some(DeviceEvent::Increase));
+    }
+
+    #[test]
+    fn servo_mode_without_vim_keys() {
+        // l/k 打开动作选择和切换注视方向, h/j 无作用, 都不产生舵机事件
+        for c in ['h', 'j', 'k', 'l'] {
+            assert_eq!(servo_event(nav(c, false)), None);
+        }
+        assert_eq!(servo_event(KeyCode::Left), Some(DeviceEvent::Decrease));
+        assert_eq!(servo_event(KeyCode::Right), Some(DeviceEvent::Increase));
+    }
+
+    #[test]
+    fn settings_mode_vim_keys() {
+        let event = |c, vim_keys| settings_event(nav(c, vim_keys), NONE);
+        assert_eq!(event('j', true), Some(SettingsEvent::Down));
+        assert_eq!(event('k', true), Some(SettingsEvent::Up));
+        assert_eq!(event('h', true), None);
+        assert_eq!(event('l', true), None);
+        for c in ['h', 'j', 'k', 'l'] {
+            assert_eq!(event(c, false), None);
+        }
+    }
+
+    #[test]
+    fn menu_mode_vim_keys() {
+        let event = |c, vim_keys| menu_event(nav(c, vim_keys), NONE, MenuItem::DeviceControl);
+        assert_eq!(event('j', true), MenuEvent::Down.into());
+        assert_eq!(event('k', true), MenuEvent::Up.into());
+        assert_eq!(event('h', true), CommonEvent::None.into());
+        assert_eq!(event('l', true), CommonEvent::None.into());
+        for c in ['h', 'j', 'k', 'l'] {
+            assert_eq!(event(c, false), CommonEvent::None.into());
+        }
+    }
+}
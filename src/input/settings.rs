@@ -1,5 +1,6 @@
 //! 设置事件
 
+use crate::app::settings::{self, SettingKind};
 use crate::app::App;
 
 /// 设置事件
@@ -10,6 +11,7 @@ pub enum SettingsEvent {
     Down,
     EnterEdit,
     Save,
+    ToggleReveal,
 }
 
 /// 处理设置事件
@@ -29,14 +31,20 @@ pub fn handle(app: &mut App, event: SettingsEvent) {
         SettingsEvent::Up => app.settings_prev(),
         SettingsEvent::Down => app.settings_next(),
         SettingsEvent::EnterEdit => {
-            app.in_edit_settings_mode = true;
-            app.edit_buffer = match app.settings_selected {
-                0 => app.config.wifi_ssid.clone(),
-                1 => app.config.wifi_password.clone(),
-                2 => app.config.speech_name.clone(),
-                _ => String::new(),
+            let items = settings::items();
+            let Some(item) = items.get(app.settings_selected) else {
+                return;
             };
+            match &item.kind {
+                SettingKind::Action(action) => action(app),
+                SettingKind::Edit(_) => {
+                    app.in_edit_settings_mode = true;
+                    app.settings_error = None;
+                    app.edit_buffer = (item.value)(&app.config);
+                }
+            }
         }
+        SettingsEvent::ToggleReveal => app.reveal_secrets = !app.reveal_secrets,
         SettingsEvent::Save => {
             log::info!("Saving settings");
         }
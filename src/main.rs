@@ -1,68 +1,121 @@
 extern crate log;
 
 mod app;
+mod cli;
+mod headless;
 mod input;
 mod robot;
 mod ui;
 mod ui_components;
 mod voice;
 
+use crate::app::log_queue::{LogRecord, QueueLogger};
 use crate::voice::VoiceManager;
 use crossterm::event::KeyModifiers;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::prelude::*;
-use simplelog::{CombinedLogger, Config, WriteLogger};
+use simplelog::{CombinedLogger, Config, SharedLogger, WriteLogger};
 use std::fs::File;
 use std::io::{self, Stdout};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 fn main() -> anyhow::Result<()> {
-    let log_file = File::create("ele_bot.log").ok();
-    if let Some(f) = log_file {
-        CombinedLogger::init(vec![WriteLogger::new(
+    // 先解析参数, 参数错误时不进入界面
+    let args = cli::Args::parse();
+    // 日志同时写入文件和界面的日志队列
+    let (queue_logger, log_rx) = QueueLogger::new(simplelog::LevelFilter::Info);
+    let mut loggers: Vec<Box<dyn SharedLogger>> = vec![queue_logger];
+    if let Ok(f) = File::create("ele_bot.log") {
+        loggers.push(WriteLogger::new(
             simplelog::LevelFilter::Trace,
             Config::default(),
             f,
-        )])
-        .ok();
+        ));
     }
-    let voice_manager =
-        VoiceManager::new("assets/module/vosk-model-small-cn-0.22", "麦克风阵列").ok();
+    CombinedLogger::init(loggers).ok();
+    let config = args.load_config();
+    if args.headless {
+        // 错误由 main 返回, 退出码非 0
+        return headless::run(&config, args.image.as_deref(), args.pose);
+    }
+    let voice_manager = if args.no_voice {
+        log::info!("Voice disabled by --no-voice");
+        None
+    } else {
+        VoiceManager::new(&config.voice_config()).ok()
+    };
     let mut stdout = io::stdout();
     enable_raw_mode()?;
     stdout.execute(EnterAlternateScreen)?;
+    stdout.execute(EnableMouseCapture)?;
+    // 主线程 panic 时先恢复终端再打印信息, 否则信息留在备用屏幕上且终端仍处于 raw 模式;
+    // 之后展开过程中 App 被销毁, 通信和语音线程随之停止. 后台线程 panic 时界面仍在运行, 不恢复
+    let main_thread = std::thread::current().id();
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().id() == main_thread {
+            let _ = restore_terminal();
+        }
+        default_hook(info);
+    }));
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
-    run(&mut terminal, voice_manager)?;
+    let result = run(&mut terminal, config, voice_manager, log_rx, args);
+    restore_terminal()?;
+    result
+}
+
+/// 退出 raw 模式并离开备用屏幕
+fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
+    io::stdout().execute(DisableMouseCapture)?;
     io::stdout().execute(LeaveAlternateScreen)?;
-
     Ok(())
 }
 
 /// 主运行循环，负责应用的生命周期管理
 ///
 /// 循环执行以下步骤：
+/// 1. 处理后台线程事件
+/// 2. 到达发送间隔时发送一帧
+/// 3. 到达刷新间隔时渲染界面
+/// 4. 处理输入
 fn run(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    config: app::config::AppConfig,
     voice_manager: Option<VoiceManager>,
+    log_rx: std::sync::mpsc::Receiver<LogRecord>,
+    args: cli::Args,
 ) -> anyhow::Result<()> {
-    let mut app = app::App::new(voice_manager);
-    let tick_rate = Duration::from_millis(20);
+    let mut app = app::App::new(config, voice_manager, log_rx, args);
+    let render_interval = Duration::from_millis(20);
+    let mut last_frame = Instant::now();
+    let mut last_render: Option<Instant> = None;
     while app.running {
-        if app.is_connected() {
+        app.tick();
+        // 发送间隔独立于界面刷新, 由配置决定; 未连接时也生成帧, 用于预览和录屏
+        let frame_interval = Duration::from_millis(app.config.frame_interval_ms);
+        if last_frame.elapsed() >= frame_interval {
+            last_frame = Instant::now();
             let _ = app.send_frame();
         }
 
-        render(terminal, &mut app)?;
+        if last_render.is_none_or(|t| t.elapsed() >= render_interval) {
+            last_render = Some(Instant::now());
+            render(terminal, &mut app)?;
+        }
+        // 无输入时最多等待 10ms, 兼作主循环节拍
         handle_input(&mut app)?;
-        std::thread::sleep(tick_rate);
     }
 
     app.stop_comm_thread();
+    if let Some(vm) = &mut app.voice_manager {
+        vm.stop();
+    }
+    app.finish_captures();
     Ok(())
 }
 
@@ -79,25 +132,49 @@ fn handle_input(app: &mut app::App) -> io::Result<()> {
         return Ok(());
     }
 
-    if let Event::Key(key) = event::read()? {
-        if key.kind != KeyEventKind::Press {
+    let key = match event::read()? {
+        Event::Key(key) => key,
+        Event::Mouse(mouse) => {
+            app.note_input();
+            input::handle_mouse(app, mouse);
             return Ok(());
         }
+        _ => return Ok(()),
+    };
+    if key.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    app.note_input();
 
-        // 全局快捷键
-        if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('q') {
-            app.quit();
-            return Ok(());
-        }
-        if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('s') {
-            if let Err(e) = app.config.save() {
-                log::error!("Failed to save settings: {e}");
-            }
-            return Ok(());
+    // 全局快捷键
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('q') {
+        app.request_quit();
+        return Ok(());
+    }
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('s') {
+        app.save_session();
+        return Ok(());
+    }
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('t') {
+        app.toggle_listening();
+        return Ok(());
+    }
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('u') {
+        app.toggle_mute();
+        return Ok(());
+    }
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('l') {
+        app.log_popup.toggle();
+        return Ok(());
+    }
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('w') {
+        if let Err(e) = app.toggle_recording() {
+            log::error!("Failed to toggle recording: {e}");
         }
-
-        // 分发到输入模块处理
-        input::handle_by_mode(app, key.code, key.modifiers);
+        return Ok(());
     }
+
+    // 分发到输入模块处理
+    input::handle_by_mode(app, key.code, key.modifiers);
     Ok(())
 }
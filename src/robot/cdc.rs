@@ -0,0 +1,140 @@
+//! USB CDC 串口后端
+//!
+//! 部分固件把设备枚举为虚拟串口, 帧格式与 USB 批量传输相同:
+//! 每轮发送 60 行像素, 结尾包附带关节配置, 之后读取 32 字节回包.
+//! 串口是字节流, 每轮的像素和结尾包拼成一块连续数据一次写入.
+//! 回包超时不算发送失败, 本帧余下各轮不再等待回包, 返回上一次的角度并标记为过期
+
+use super::transport::{
+    check_frame_size, Feedback, PixelFormat, Transport, RESPONSE_SIZE, ROUND_COUNT,
+};
+use super::{DeviceInfo, DeviceNotFound, JointConfig, UsbIds, SERVO_COUNT};
+use anyhow::Context;
+use serialport::{ClearBuffer, SerialPort, SerialPortType};
+use std::io::ErrorKind;
+use std::time::Duration;
+
+/// 默认串口波特率
+///
+/// 固件通过 USB CDC 收发, 传输速率由 USB 决定, 打开串口时设置的波特率只是占位;
+/// 接在 USB 转串口芯片上的固件才需要与其实际波特率一致
+pub const DEFAULT_BAUD_RATE: u32 = 115_200;
+/// 读写超时
+const CDC_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// CDC 串口传输
+pub struct CdcTransport {
+    port: Box<dyn SerialPort>,
+    info: DeviceInfo,
+    pixel_format: PixelFormat,
+    round_buffer: Vec<u8>,                     // 每轮发送的数据, 打开时分配一次
+    last_feedback: Option<[f32; SERVO_COUNT]>, // 上一次完整收到的舵机角度
+}
+
+impl CdcTransport {
+    /// 打开串口
+    ///
+    /// # Arguments
+    ///
+    /// * `port_name` - 串口名, 为空时查找 VID/PID 匹配的第一个串口
+    /// * `ids` - 设备 VID/PID
+    /// * `baud_rate` - 波特率
+    /// * `pixel_format` - 像素格式, 决定每轮的像素字节数
+    pub fn open(
+        port_name: &str,
+        ids: UsbIds,
+        baud_rate: u32,
+        pixel_format: PixelFormat,
+    ) -> anyhow::Result<Self> {
+        let (name, info) = if port_name.is_empty() {
+            find_port(ids)?
+        } else {
+            (port_name.to_string(), DeviceInfo::default())
+        };
+        log::info!("Opening CDC port: {name} at {baud_rate} baud");
+        let port = serialport::new(&name, baud_rate)
+            .timeout(CDC_TIMEOUT)
+            .open()
+            .with_context(|| format!("Failed to open {name}"))?;
+        Ok(Self {
+            port,
+            info,
+            pixel_format,
+            round_buffer: vec![0xFF; pixel_format.round_packet_size()],
+            last_feedback: None,
+        })
+    }
+}
+
+impl Transport for CdcTransport {
+    fn send_frame(
+        &mut self,
+        pixels: &[u8],
+        config: &JointConfig,
+    ) -> anyhow::Result<Option<Feedback>> {
+        check_frame_size(pixels, self.pixel_format)?;
+        let bytes_per_round = self.pixel_format.bytes_per_round();
+        let config_bytes = config.as_bytes();
+        let mut response = [0u8; RESPONSE_SIZE];
+        // 关节配置位于每轮末尾, 各轮相同, 只需写入一次
+        self.round_buffer[bytes_per_round..bytes_per_round + config_bytes.len()]
+            .copy_from_slice(&config_bytes);
+        let mut valid = true;
+        for round in pixels.chunks_exact(bytes_per_round).take(ROUND_COUNT) {
+            // 丢弃超时后才到达的回包或残缺回包, 避免与本轮回包错位
+            self.port.clear(ClearBuffer::Input)?;
+            self.round_buffer[..bytes_per_round].copy_from_slice(round);
+            self.port.write_all(&self.round_buffer)?;
+            if valid {
+                valid = read_response(self.port.as_mut(), &mut response)?;
+            }
+        }
+        if valid {
+            self.last_feedback = Some(JointConfig::from_bytes(&response).angles);
+        }
+        Ok(self.last_feedback.map(|angles| Feedback {
+            angles,
+            feedback_valid: valid,
+        }))
+    }
+
+    fn device_info(&self) -> DeviceInfo {
+        self.info.clone()
+    }
+}
+
+/// 读取一个完整回包, 超时返回 `false`, 此时缓冲区内容不可用
+fn read_response(port: &mut dyn SerialPort, response: &mut [u8]) -> anyhow::Result<bool> {
+    match port.read_exact(response) {
+        Ok(()) => Ok(true),
+        Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock) => {
+            log::debug!("CDC response timed out, feedback is stale");
+            Ok(false)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 按 VID/PID 查找设备串口
+fn find_port(ids: UsbIds) -> anyhow::Result<(String, DeviceInfo)> {
+    log::info!("Searching for CDC port {ids}");
+    serialport::available_ports()?
+        .into_iter()
+        .find_map(|port| match port.port_type {
+            SerialPortType::UsbPort(usb) if usb.vid == ids.vid && usb.pid == ids.pid => {
+                let mut info = DeviceInfo::default();
+                if let Some(s) = usb.manufacturer {
+                    info.manufacturer = s;
+                }
+                if let Some(s) = usb.product {
+                    info.product = s;
+                }
+                if let Some(s) = usb.serial_number {
+                    info.serial = s;
+                }
+                Some((port.port_name, info))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| DeviceNotFound(format!("CDC port for {ids}")).into())
+}
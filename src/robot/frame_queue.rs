@@ -0,0 +1,290 @@
+//! 有界帧队列
+//!
+//! 界面线程放入帧, 通信线程按顺序取出. 队列满时按 [QueuePolicy] 处理:
+//! 默认深度为 1 并丢弃最旧帧, 即只保留最新一帧, 通信线程忙时界面不会被阻塞或报错;
+//! 动画等需要保持顺序的场景可以加大深度
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// 队列满时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QueuePolicy {
+    /// 丢弃最旧的一帧, 放入新帧
+    #[default]
+    DropOldest,
+    /// 丢弃新帧
+    DropNewest,
+    /// 阻塞发送端直到有空位
+    Block,
+}
+
+impl QueuePolicy {
+    /// 显示名称
+    pub fn label(self) -> &'static str {
+        match self {
+            QueuePolicy::DropOldest => "丢弃最旧",
+            QueuePolicy::DropNewest => "丢弃最新",
+            QueuePolicy::Block => "阻塞",
+        }
+    }
+}
+
+/// 队列状态快照
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// 队列满时的处理方式
+    pub policy: QueuePolicy,
+    /// 待取帧数
+    pub len: usize,
+    /// 队列深度
+    pub capacity: usize,
+    /// 累计丢弃帧数
+    pub dropped: u64,
+}
+
+struct QueueState<T> {
+    frames: VecDeque<T>,
+    dropped: u64,
+    closed: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<QueueState<T>>,
+    /// 有新帧或已关闭
+    ready: Condvar,
+    /// 有空位或已关闭
+    space: Condvar,
+    capacity: usize,
+    policy: QueuePolicy,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, QueueState<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.ready.notify_all();
+        self.space.notify_all();
+    }
+}
+
+/// 创建一对有界帧队列的发送端和接收端
+///
+/// # Arguments
+///
+/// * `capacity` - 队列深度, 小于 1 时按 1 处理
+/// * `policy` - 队列满时的处理方式
+pub fn frame_queue<T>(capacity: usize, policy: QueuePolicy) -> (FrameSender<T>, FrameReceiver<T>) {
+    let capacity = capacity.max(1);
+    let shared = Arc::new(Shared {
+        state: Mutex::new(QueueState {
+            frames: VecDeque::with_capacity(capacity),
+            dropped: 0,
+            closed: false,
+        }),
+        ready: Condvar::new(),
+        space: Condvar::new(),
+        capacity,
+        policy,
+    });
+    (
+        FrameSender {
+            shared: shared.clone(),
+        },
+        FrameReceiver { shared },
+    )
+}
+
+/// 发送端, 销毁时接收端取完剩余帧后结束
+pub struct FrameSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+#[allow(dead_code)]
+impl<T> FrameSender<T> {
+    /// 放入一帧, 队列满时按策略丢弃或等待
+    ///
+    /// 返回是否丢弃了一帧 (旧帧或本帧); 接收端已销毁时原样返回该帧
+    pub fn send(&self, frame: T) -> Result<bool, T> {
+        let mut state = self.shared.lock();
+        let mut dropped = false;
+        loop {
+            if state.closed {
+                return Err(frame);
+            }
+            if state.frames.len() < self.shared.capacity {
+                break;
+            }
+            match self.shared.policy {
+                QueuePolicy::DropOldest => {
+                    state.frames.pop_front();
+                    state.dropped += 1;
+                    dropped = true;
+                }
+                QueuePolicy::DropNewest => {
+                    state.dropped += 1;
+                    return Ok(true);
+                }
+                QueuePolicy::Block => {
+                    state = self
+                        .shared
+                        .space
+                        .wait(state)
+                        .unwrap_or_else(|e| e.into_inner());
+                }
+            }
+        }
+        state.frames.push_back(frame);
+        drop(state);
+        self.shared.ready.notify_one();
+        Ok(dropped)
+    }
+
+    /// 当前队列状态
+    pub fn stats(&self) -> QueueStats {
+        let state = self.shared.lock();
+        QueueStats {
+            policy: self.shared.policy,
+            len: state.frames.len(),
+            capacity: self.shared.capacity,
+            dropped: state.dropped,
+        }
+    }
+}
+
+impl<T> Drop for FrameSender<T> {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+/// 接收端
+pub struct FrameReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+#[allow(dead_code)]
+impl<T> FrameReceiver<T> {
+    /// 阻塞等待下一帧, 发送端销毁且没有待取帧时返回 `None`
+    pub fn recv(&self) -> Option<T> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(frame) = self.pop(&mut state) {
+                return Some(frame);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self
+                .shared
+                .ready
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// 最多等待 `timeout` 获取下一帧
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(frame) = self.pop(&mut state) {
+                return Ok(frame);
+            }
+            if state.closed {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = self
+                .shared
+                .ready
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// 取出最旧的一帧并唤醒等待空位的发送端
+    fn pop(&self, state: &mut QueueState<T>) -> Option<T> {
+        let frame = state.frames.pop_front()?;
+        self.shared.space.notify_one();
+        Some(frame)
+    }
+}
+
+impl<T> Drop for FrameReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const FRAMES: u32 = 50;
+
+    /// 发送端一次放入所有帧, 接收端每取一帧休眠一会, 返回收到的帧和发送完成时的队列状态
+    fn run_slow_consumer(capacity: usize, policy: QueuePolicy) -> (Vec<u32>, QueueStats) {
+        let (tx, rx) = frame_queue(capacity, policy);
+        let producer = thread::spawn(move || {
+            for frame in 0..FRAMES {
+                assert!(tx.send(frame).is_ok(), "send must not fail");
+            }
+            tx.stats()
+        });
+        let mut received = Vec::new();
+        while let Some(frame) = rx.recv() {
+            received.push(frame);
+            thread::sleep(Duration::from_millis(2));
+        }
+        (received, producer.join().unwrap())
+    }
+
+    fn assert_increasing(frames: &[u32]) {
+        assert!(frames.windows(2).all(|w| w[0] < w[1]), "{frames:?}");
+    }
+
+    #[test]
+    fn drop_oldest_keeps_newest_frame() {
+        let (received, stats) = run_slow_consumer(1, QueuePolicy::DropOldest);
+        assert_eq!(received.last(), Some(&(FRAMES - 1)));
+        assert_increasing(&received);
+        assert_eq!(received.len() as u64 + stats.dropped, FRAMES as u64);
+        assert!(stats.dropped > 0);
+    }
+
+    #[test]
+    fn drop_newest_keeps_oldest_frames() {
+        let (received, stats) = run_slow_consumer(2, QueuePolicy::DropNewest);
+        assert_eq!(received.first(), Some(&0));
+        assert_increasing(&received);
+        assert_eq!(received.len() as u64 + stats.dropped, FRAMES as u64);
+        assert!(stats.dropped > 0);
+    }
+
+    #[test]
+    fn block_delivers_every_frame() {
+        let (received, stats) = run_slow_consumer(2, QueuePolicy::Block);
+        assert_eq!(received, (0..FRAMES).collect::<Vec<_>>());
+        assert_eq!(stats.dropped, 0);
+    }
+
+    #[test]
+    fn send_after_receiver_dropped_returns_frame() {
+        let (tx, rx) = frame_queue(1, QueuePolicy::DropOldest);
+        drop(rx);
+        assert_eq!(tx.send(7), Err(7));
+    }
+}
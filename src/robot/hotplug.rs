@@ -0,0 +1,163 @@
+//! USB 热插拔检测
+//!
+//! 优先使用 libusb 的热插拔回调, 平台不支持时退化为定时轮询 [is_device_present](super::is_device_present)
+
+use super::UsbIds;
+use rusb::{Context, Device, Hotplug, HotplugBuilder, UsbContext};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// 热插拔事件, 附带插拔设备的 VID/PID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotplugEvent {
+    /// 设备插入
+    Arrived(UsbIds),
+    /// 设备拔出
+    Left(UsbIds),
+}
+
+/// 热插拔监听器, 销毁时停止后台线程
+pub struct HotplugWatcher {
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+    rx: mpsc::Receiver<HotplugEvent>,
+}
+
+#[allow(dead_code)]
+impl HotplugWatcher {
+    /// 启动热插拔监听线程
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - 监听的设备 VID/PID
+    /// * `poll_interval` - 轮询间隔, 同时作为回调模式下处理事件的超时时间
+    pub fn start(ids: UsbIds, poll_interval: Duration) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let (tx, rx) = mpsc::channel();
+        let running_clone = running.clone();
+        let handle = thread::spawn(move || {
+            if rusb::has_hotplug() {
+                match watch_callback(&running_clone, &tx, ids, poll_interval) {
+                    Ok(()) => return,
+                    Err(e) => log::warn!("Hotplug callback unavailable, fall back to polling: {e}"),
+                }
+            }
+            watch_polling(&running_clone, &tx, ids, poll_interval);
+        });
+
+        Self {
+            running,
+            handle: Some(handle),
+            rx,
+        }
+    }
+
+    /// 非阻塞获取热插拔事件
+    pub fn try_recv(&self) -> Option<HotplugEvent> {
+        self.rx.try_recv().ok()
+    }
+
+    /// 停止监听线程
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for HotplugWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// libusb 回调, 将设备插拔转发到通道
+struct HotplugHandler {
+    tx: mpsc::Sender<HotplugEvent>,
+    ids: UsbIds, // 注册时的过滤条件, 读不到设备描述符时使用
+}
+
+impl HotplugHandler {
+    /// 读取设备的 VID/PID
+    fn device_ids<T: UsbContext>(&self, device: &Device<T>) -> UsbIds {
+        device
+            .device_descriptor()
+            .map(|d| UsbIds {
+                vid: d.vendor_id(),
+                pid: d.product_id(),
+            })
+            .unwrap_or(self.ids)
+    }
+}
+
+impl<T: UsbContext> Hotplug<T> for HotplugHandler {
+    fn device_arrived(&mut self, device: Device<T>) {
+        let ids = self.device_ids(&device);
+        log::info!("ElectronBot plugged in: {ids}");
+        let _ = self.tx.send(HotplugEvent::Arrived(ids));
+    }
+
+    fn device_left(&mut self, device: Device<T>) {
+        let ids = self.device_ids(&device);
+        log::info!("ElectronBot unplugged: {ids}");
+        let _ = self.tx.send(HotplugEvent::Left(ids));
+    }
+}
+
+/// 使用 libusb 热插拔回调监听
+fn watch_callback(
+    running: &AtomicBool,
+    tx: &mpsc::Sender<HotplugEvent>,
+    ids: UsbIds,
+    timeout: Duration,
+) -> rusb::Result<()> {
+    let context = Context::new()?;
+    let _registration: rusb::Registration<Context> = HotplugBuilder::new()
+        .vendor_id(ids.vid)
+        .product_id(ids.pid)
+        .enumerate(true)
+        .register(
+            &context,
+            Box::new(HotplugHandler {
+                tx: tx.clone(),
+                ids,
+            }),
+        )?;
+
+    log::info!("Hotplug watcher started for {ids} (callback)");
+    while running.load(Ordering::Relaxed) {
+        context.handle_events(Some(timeout))?;
+    }
+    Ok(())
+}
+
+/// 定时检查设备是否存在, 状态变化时发送事件
+fn watch_polling(
+    running: &AtomicBool,
+    tx: &mpsc::Sender<HotplugEvent>,
+    ids: UsbIds,
+    interval: Duration,
+) {
+    log::info!("Hotplug watcher started for {ids} (polling every {interval:?})");
+    let mut present = false;
+    while running.load(Ordering::Relaxed) {
+        let now_present = super::is_device_present(ids);
+        if now_present != present {
+            present = now_present;
+            let event = if present {
+                HotplugEvent::Arrived(ids)
+            } else {
+                HotplugEvent::Left(ids)
+            };
+            log::info!("Hotplug event: {event:?}");
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+        thread::sleep(interval);
+    }
+}
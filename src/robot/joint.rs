@@ -4,6 +4,9 @@
 
 pub const SERVO_COUNT: usize = 6;
 
+/// 粗调步长 (度)
+pub const BIG_STEP: i16 = 5;
+
 // 舵机配置结构体
 struct ServoConfig {
     name: &'static str,
@@ -49,8 +52,14 @@ const SERVOS: [ServoConfig; SERVO_COUNT] = [
 
 /// 关节配置数据结构
 ///
-/// 包含使能标志和 6 个舵机角度，序列化为 32 字节
-#[derive(Clone, Copy, Debug)]
+/// 包含使能标志和 6 个舵机角度，序列化为 32 字节:
+///
+/// | 偏移 | 长度 | 内容 |
+/// |------|------|------|
+/// | 0 | 1 | 使能标志, 0 为舵机掉电 |
+/// | 1 + 4i | 4 | 第 i 个舵机角度, 小端 f32 |
+/// | 25 | 7 | 保留, 填 0 |
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct JointConfig {
     pub enable: u8,
     pub angles: [f32; SERVO_COUNT],
@@ -65,6 +74,7 @@ impl Default for JointConfig {
     }
 }
 
+#[allow(dead_code)]
 impl JointConfig {
     /// 转换为 32 字节格式
     pub fn as_bytes(self) -> [u8; 32] {
@@ -76,6 +86,22 @@ impl JointConfig {
         }
         bytes
     }
+
+    /// 从 32 字节数据解析, 与 [JointConfig::as_bytes] 格式相同
+    ///
+    /// 设备回包使用同样的布局, 可用于解析舵机反馈角度
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let mut angles = [0.0; SERVO_COUNT];
+        for (i, angle) in angles.iter_mut().enumerate() {
+            let mut b = [0u8; 4];
+            b.copy_from_slice(&bytes[1 + i * 4..1 + i * 4 + 4]);
+            *angle = f32::from_le_bytes(b);
+        }
+        Self {
+            enable: bytes[0],
+            angles,
+        }
+    }
 }
 
 // ==================== ServoState ====================
@@ -85,6 +111,9 @@ impl JointConfig {
 pub struct ServoState {
     pub values: [i16; SERVO_COUNT],
     pub selected: usize,
+    pub disabled: bool,                  // 舵机掉电, 可以手动摆姿势
+    pub calibration: [f32; SERVO_COUNT], // 各舵机零点校准偏移
+    pub current: [f32; SERVO_COUNT],     // 插值过程中实际发送的角度, values 为目标角度
 }
 
 #[allow(dead_code)]
@@ -104,6 +133,22 @@ impl ServoState {
         SERVOS.get(index).map(|s| s.max).unwrap_or(125)
     }
 
+    /// 把角度限制在舵机自身范围内
+    pub fn clamp_angle(index: usize, value: i16) -> i16 {
+        value.clamp(Self::min_angle(index), Self::max_angle(index))
+    }
+
+    /// 角度在舵机自身范围内所处的百分比 (0~100)
+    pub fn percent(index: usize, value: i16) -> u16 {
+        let min = Self::min_angle(index) as f32;
+        let total_range = Self::max_angle(index) as f32 - min;
+        if total_range > 0.0 {
+            (((value as f32 - min) / total_range).clamp(0.0, 1.0) * 100.0) as u16
+        } else {
+            0
+        }
+    }
+
     /// 获取舵机范围字符串
     pub fn range_str(index: usize) -> String {
         let s = SERVOS.get(index).unwrap();
@@ -122,21 +167,72 @@ impl ServoState {
 
     /// 增加当前舵机角度
     pub fn increase(&mut self) {
-        let max = Self::max_angle(self.selected);
-        self.values[self.selected] = (self.values[self.selected] + 1).min(max);
+        self.adjust(1);
+    }
+
+    /// 当前舵机角度加上 `delta`, 超出范围时截断
+    pub fn adjust(&mut self, delta: i16) {
+        let i = self.selected;
+        self.values[i] = Self::clamp_angle(i, self.values[i].saturating_add(delta));
     }
 
     /// 减少当前舵机角度
     pub fn decrease(&mut self) {
-        let min = Self::min_angle(self.selected);
-        self.values[self.selected] = (self.values[self.selected] - 1).max(min);
+        let i = self.selected;
+        self.values[i] = Self::clamp_angle(i, self.values[i].saturating_sub(1));
+    }
+
+    /// 当前舵机角度增加一大步
+    pub fn increase_big(&mut self) {
+        let i = self.selected;
+        self.values[i] = Self::clamp_angle(i, self.values[i].saturating_add(BIG_STEP));
+    }
+
+    /// 当前舵机角度减少一大步
+    pub fn decrease_big(&mut self) {
+        let i = self.selected;
+        self.values[i] = Self::clamp_angle(i, self.values[i].saturating_sub(BIG_STEP));
     }
 
-    /// 转换为 JointConfig
+    /// 按反馈角度更新目标角度, 扣除校准偏移后超出范围时截断
+    pub fn track(&mut self, angles: &[f32; SERVO_COUNT]) {
+        for (i, value) in self.values.iter_mut().enumerate() {
+            let raw = (angles[i] - self.calibration[i]).round() as i16;
+            *value = Self::clamp_angle(i, raw);
+            self.current[i] = *value as f32;
+        }
+    }
+
+    /// 当前角度向目标角度前进一步, 返回是否仍在运动
+    ///
+    /// # Arguments
+    ///
+    /// * `max_step` - 每帧最大转动角度, 不大于 0 时直接到达目标
+    pub fn step(&mut self, max_step: f32) -> bool {
+        let mut moving = false;
+        for (current, &target) in self.current.iter_mut().zip(self.values.iter()) {
+            let target = target as f32;
+            let delta = target - *current;
+            if max_step <= 0.0 || delta.abs() <= max_step {
+                *current = target;
+            } else {
+                *current += max_step.copysign(delta);
+                moving = true;
+            }
+        }
+        moving
+    }
+
+    /// 转换为 JointConfig, 角度加上校准偏移后重新限制在舵机范围内
     pub fn as_config(&self) -> JointConfig {
+        let mut angles = [0.0; SERVO_COUNT];
+        for (i, angle) in angles.iter_mut().enumerate() {
+            *angle = (self.current[i] + self.calibration[i])
+                .clamp(Self::min_angle(i) as f32, Self::max_angle(i) as f32);
+        }
         JointConfig {
-            enable: 1,
-            angles: self.values.map(|x| x as f32),
+            enable: u8::from(!self.disabled),
+            angles,
         }
     }
 }
@@ -168,6 +264,13 @@ impl Joint {
         self.state.selected
     }
 
+    /// 选中指定舵机, 超出范围时忽略
+    pub fn select_servo(&mut self, index: usize) {
+        if index < SERVO_COUNT {
+            self.state.selected = index;
+        }
+    }
+
     /// 切换到下一个舵机
     pub fn next_servo(&mut self) {
         self.state.next();
@@ -183,13 +286,146 @@ impl Joint {
         self.state.increase();
     }
 
+    /// 当前舵机角度加上 `delta`, 超出范围时截断
+    pub fn adjust(&mut self, delta: i16) {
+        self.state.adjust(delta);
+    }
+
     /// 减少当前舵机角度
     pub fn decrease(&mut self) {
         self.state.decrease();
     }
 
+    /// 当前舵机角度增加一大步
+    pub fn increase_big(&mut self) {
+        self.state.increase_big();
+    }
+
+    /// 当前舵机角度减少一大步
+    pub fn decrease_big(&mut self) {
+        self.state.decrease_big();
+    }
+
+    /// 设置当前选中舵机的目标角度, 超出范围时截断, 返回实际设置的角度
+    pub fn set_selected_value(&mut self, value: i16) -> i16 {
+        let i = self.state.selected;
+        self.state.values[i] = ServoState::clamp_angle(i, value);
+        self.state.values[i]
+    }
+
+    /// 设置所有舵机的目标角度, 超出范围时截断
+    ///
+    /// 实际角度由 [Joint::step] 插值过去
+    pub fn set_targets(&mut self, values: &[i16; SERVO_COUNT]) {
+        for (i, value) in self.state.values.iter_mut().enumerate() {
+            *value = ServoState::clamp_angle(i, values[i]);
+        }
+    }
+
+    /// 所有舵机回到零位姿势, 超出范围时截断
+    ///
+    /// # Arguments
+    ///
+    /// * `home` - 零位姿势, 一般全为 0
+    pub fn home(&mut self, home: &[i16; SERVO_COUNT]) {
+        self.set_targets(home);
+    }
+
+    /// 实际角度向目标角度插值一步, 每次发送帧前调用, 返回是否仍在运动
+    pub fn step(&mut self, max_step: f32) -> bool {
+        self.state.step(max_step)
+    }
+
+    /// 设置各舵机的校准偏移
+    pub fn set_calibration(&mut self, calibration: [f32; SERVO_COUNT]) {
+        self.state.calibration = calibration;
+    }
+
+    /// 设置舵机使能 (上电保持 / 掉电)
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.state.disabled = !enabled;
+    }
+
+    /// 舵机是否使能
+    pub fn is_enabled(&self) -> bool {
+        !self.state.disabled
+    }
+
+    /// 掉电时目标角度跟随反馈, 重新使能时不会跳变
+    pub fn track_feedback(&mut self, angles: &[f32; SERVO_COUNT]) {
+        if !self.is_enabled() {
+            self.state.track(angles);
+        }
+    }
+
     /// 获取当前关节配置
     pub fn config(&self) -> JointConfig {
         self.state.as_config()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enable_byte_is_first() {
+        let config = JointConfig {
+            enable: 1,
+            angles: [0.0; SERVO_COUNT],
+        };
+        let bytes = config.as_bytes();
+        assert_eq!(bytes[0], 1);
+        assert!(bytes[1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn angles_are_little_endian_f32() {
+        let mut angles = [0.0; SERVO_COUNT];
+        angles[0] = 1.0;
+        angles[5] = -90.5;
+        let bytes = JointConfig { enable: 0, angles }.as_bytes();
+        assert_eq!(&bytes[1..5], &[0x00, 0x00, 0x80, 0x3F]);
+        assert_eq!(&bytes[21..25], &(-90.5f32).to_le_bytes());
+        // 角度之后的保留字节为 0
+        assert!(bytes[1 + SERVO_COUNT * 4..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let config = JointConfig {
+            enable: 1,
+            angles: [12.5, -30.0, 180.0, 0.25, -180.0, 45.0],
+        };
+        assert_eq!(JointConfig::from_bytes(&config.as_bytes()), config);
+    }
+
+    const HEAD: usize = 0;
+    const LEFT_ARM: usize = 2;
+    const RIGHT_ARM: usize = 4;
+
+    #[test]
+    fn clamp_angle_uses_each_servo_range() {
+        assert_eq!(ServoState::clamp_angle(HEAD, 40), 15);
+        assert_eq!(ServoState::clamp_angle(HEAD, -40), -15);
+        assert_eq!(ServoState::clamp_angle(HEAD, 10), 10);
+        assert_eq!(ServoState::clamp_angle(LEFT_ARM, 250), 180);
+        assert_eq!(ServoState::clamp_angle(LEFT_ARM, -250), -180);
+        assert_eq!(ServoState::clamp_angle(RIGHT_ARM, 250), 180);
+        assert_eq!(ServoState::clamp_angle(RIGHT_ARM, -250), -180);
+    }
+
+    #[test]
+    fn adjust_saturates_at_servo_limit() {
+        let mut state = ServoState {
+            selected: HEAD,
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            state.increase_big();
+        }
+        assert_eq!(state.values[HEAD], 15);
+        state.adjust(-100);
+        assert_eq!(state.values[HEAD], -15);
+    }
+}
@@ -8,15 +8,24 @@
 use anyhow::Result;
 use boteyes::{Mood, Position, RoboEyes, RoboEyesConfig};
 use electron_bot::ImageBuffer;
-use image::GrayImage;
+use image::codecs::gif::GifDecoder;
+use image::imageops::{self, FilterType};
+use image::{AnimationDecoder, GrayImage, Rgb, RgbaImage};
+use std::fs::File;
+use std::io::BufReader;
+use std::time::{Duration, Instant};
+
+use super::text::TextRenderer;
+use super::transport::PixelFormat;
 // ==================== 常量 ====================
 
 pub const LCD_WIDTH: usize = 240;
 pub const LCD_HEIGHT: usize = 240;
+/// 整帧 RGB 像素字节数 (172800), 发送时再切分为每轮的数据包, 见 [super::transport::ROUND_PACKET_SIZE]
 pub const FRAME_SIZE: usize = LCD_WIDTH * LCD_HEIGHT * 3;
 
 /// 计算数据的 FNV-1a 哈希值（用于检测内容变化）
-fn compute_hash(data: &[u8]) -> u64 {
+pub fn compute_hash(data: &[u8]) -> u64 {
     let mut hash = 0xcbf29ce484222325;
     for &byte in data {
         hash ^= byte as u64;
@@ -25,8 +34,66 @@ fn compute_hash(data: &[u8]) -> u64 {
     hash
 }
 
+// ==================== 表情名称 ====================
+
+/// 所有表情名称, 与 [mood_from_name] 对应
+pub const MOOD_NAMES: [&str; 4] = ["default", "happy", "tired", "angry"];
+
+/// 所有注视方向名称, 与 [position_from_name] 对应
+pub const POSITION_NAMES: [&str; 9] = [
+    "center",
+    "up",
+    "up_right",
+    "right",
+    "down_right",
+    "down",
+    "down_left",
+    "left",
+    "up_left",
+];
+
+/// 根据名称获取表情
+pub fn mood_from_name(name: &str) -> Option<Mood> {
+    match name {
+        "default" => Some(Mood::Default),
+        "happy" => Some(Mood::Happy),
+        "tired" => Some(Mood::Tired),
+        "angry" => Some(Mood::Angry),
+        _ => None,
+    }
+}
+
+/// 根据名称获取注视方向
+pub fn position_from_name(name: &str) -> Option<Position> {
+    match name {
+        "center" => Some(Position::Center),
+        "up" => Some(Position::N),
+        "up_right" => Some(Position::NE),
+        "right" => Some(Position::E),
+        "down_right" => Some(Position::SE),
+        "down" => Some(Position::S),
+        "down_left" => Some(Position::SW),
+        "left" => Some(Position::W),
+        "up_left" => Some(Position::NW),
+        _ => None,
+    }
+}
+
 // ==================== DisplayMode ====================
 
+/// 可在设备控制页直接切换的显示模式, 名称 -> 模式
+///
+/// 静态图片、动图和文字需要额外输入, 由各自的按键进入
+pub const SELECTABLE_MODES: [(&str, DisplayMode); 4] = [
+    ("眼睛", DisplayMode::Eyes),
+    ("时钟", DisplayMode::Clock),
+    ("幻灯片", DisplayMode::Slideshow),
+    ("测试图案", DisplayMode::TestPattern),
+];
+
+/// 幻灯片支持的图片扩展名
+const SLIDESHOW_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "bmp"];
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[allow(dead_code)]
 pub enum DisplayMode {
@@ -34,10 +101,103 @@ pub enum DisplayMode {
     #[default]
     Eyes,
     TestPattern,
+    Gif,
+    Text,
+    Clock,
+    Slideshow,
+}
+
+// ==================== TestPattern ====================
+
+/// 棋盘格边长 (像素)
+const CHECKER_SIZE: usize = 20;
+
+/// 测试图案, 用于检查坏点和颜色顺序
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TestPattern {
+    /// 六色彩条
+    #[default]
+    ColorBars,
+    /// RGB 渐变
+    Gradient,
+    /// 黑白棋盘格
+    Checkerboard,
+    /// 全白
+    SolidWhite,
+    /// 中心十字和边框
+    Crosshair,
+}
+
+/// 所有测试图案, 名称 -> 图案
+pub const TEST_PATTERNS: [(&str, TestPattern); 5] = [
+    ("彩条", TestPattern::ColorBars),
+    ("渐变", TestPattern::Gradient),
+    ("棋盘格", TestPattern::Checkerboard),
+    ("全白", TestPattern::SolidWhite),
+    ("十字", TestPattern::Crosshair),
+];
+
+// ==================== GIF ====================
+
+/// GIF 动画帧
+struct GifFrame {
+    /// 240x240 RGB 像素
+    pixels: Vec<u8>,
+    /// 该帧显示时长
+    delay: Duration,
+}
+
+/// 图片目录, GIF 从这里选择
+pub const IMAGE_DIR: &str = "./assets/images";
+
+/// 列出图片目录下的 GIF 文件名
+pub fn list_gif_files() -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(IMAGE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.to_lowercase().ends_with(".gif"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// GIF 帧最短显示时长, 部分 GIF 把延时写成 0
+const GIF_MIN_DELAY: Duration = Duration::from_millis(20);
+
+/// 解码内存中的图片文件, 居中裁剪缩放为 240x240 RGB 像素
+pub fn decode_image(data: &[u8]) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(data)?;
+    Ok(fit_to_lcd(&image.to_rgba8()))
+}
+
+/// 居中裁剪为正方形并缩放到 LCD 尺寸, 转为 RGB 像素
+fn fit_to_lcd(image: &RgbaImage) -> Vec<u8> {
+    let (w, h) = image.dimensions();
+    let side = w.min(h);
+    let cropped = imageops::crop_imm(image, (w - side) / 2, (h - side) / 2, side, side).to_image();
+    let resized = imageops::resize(
+        &cropped,
+        LCD_WIDTH as u32,
+        LCD_HEIGHT as u32,
+        FilterType::Triangle,
+    );
+    resized
+        .pixels()
+        .flat_map(|p| [p.0[0], p.0[1], p.0[2]])
+        .collect()
 }
 
 // ==================== Lcd ====================
 
+/// 屏幕亮度倍数范围
+pub const BRIGHTNESS_RANGE: std::ops::RangeInclusive<f32> = 0.1..=2.0;
+/// 屏幕伽马范围
+pub const GAMMA_RANGE: std::ops::RangeInclusive<f32> = 0.2..=3.0;
+
 pub struct Lcd {
     buffer: ImageBuffer,
     mode: DisplayMode,
@@ -45,6 +205,67 @@ pub struct Lcd {
     eyes: RoboEyes,
     eyes_timer: u64,
     last_eyes_hash: Option<u64>, // 缓存上一帧的哈希值
+    eye_tint: (u8, u8, u8),      // 眼睛颜色, 灰度值按通道乘以该颜色
+    gif_frames: Vec<GifFrame>,
+    gif_index: usize,
+    gif_frame_start: Instant, // 当前 GIF 帧开始显示的时间
+    text: String,
+    text_color: Rgb<u8>,
+    text_renderer: TextRenderer,
+    clock_color: Rgb<u8>,
+    test_pattern: TestPattern,
+    slides: Vec<Vec<u8>>,
+    slide_index: usize,
+    slide_interval: Duration,
+    slide_start: Instant, // 当前图片开始显示的时间
+    transition: Option<Transition>,
+    transition_frames: u32,           // 切换模式的过渡帧数, 0 表示直接切换
+    tone: (f32, f32),                 // 亮度和伽马
+    tone_lut: Option<Box<[u8; 256]>>, // 按亮度和伽马生成的查找表, 默认值时为 None
+    orientation: Orientation,
+    orientation_map: Option<Vec<u32>>, // 输出像素对应的原像素序号, 不翻转不旋转时为 None
+    pixel_format: PixelFormat,         // 发送给设备的像素格式
+    rgb_scratch: Vec<u8>,              // 打包为其他格式前的 RGB888 数据, 复用避免每帧分配
+}
+
+/// 屏幕方向, 用于匹配不同的装配方式
+///
+/// 先顺时针旋转, 再水平、垂直翻转
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Orientation {
+    pub flip_h: bool,
+    pub flip_v: bool,
+    /// 顺时针旋转角度: 0, 90, 180 或 270
+    pub rotate: u16,
+}
+
+impl Orientation {
+    /// 原像素坐标变换后的坐标
+    fn map(&self, x: usize, y: usize) -> (usize, usize) {
+        // 屏幕为正方形, 旋转后尺寸不变
+        let last = LCD_WIDTH - 1;
+        let (mut x, mut y) = match self.rotate {
+            90 => (last - y, x),
+            180 => (last - x, last - y),
+            270 => (y, last - x),
+            _ => (x, y),
+        };
+        if self.flip_h {
+            x = last - x;
+        }
+        if self.flip_v {
+            y = last - y;
+        }
+        (x, y)
+    }
+}
+
+/// 模式切换过渡
+struct Transition {
+    /// 切换前最后一帧
+    from: Vec<u8>,
+    /// 已输出的过渡帧数
+    frame: u32,
 }
 
 #[allow(dead_code)]
@@ -72,40 +293,382 @@ impl Lcd {
             eyes,
             eyes_timer: 0,
             last_eyes_hash: None,
+            eye_tint: (255, 255, 255),
+            gif_frames: Vec::new(),
+            gif_index: 0,
+            gif_frame_start: Instant::now(),
+            text: String::new(),
+            text_color: Rgb([255, 255, 255]),
+            text_renderer: TextRenderer::new(),
+            clock_color: Rgb([255, 255, 255]),
+            test_pattern: TestPattern::default(),
+            slides: Vec::new(),
+            slide_index: 0,
+            slide_interval: Duration::from_secs(5),
+            slide_start: Instant::now(),
+            transition: None,
+            transition_frames: 0,
+            tone: (1.0, 1.0),
+            tone_lut: None,
+            orientation: Orientation::default(),
+            orientation_map: None,
+            pixel_format: PixelFormat::default(),
+            rgb_scratch: Vec::new(),
         }
     }
 
     pub fn generate_pixels(&mut self) {
+        // 过渡期间每帧都会被混合覆盖, 眼睛必须每帧重新绘制
+        if self.transition.is_some() {
+            self.last_eyes_hash = None;
+        }
         match self.mode {
             DisplayMode::Static => self.render_static_image(),
             DisplayMode::Eyes => self.render_eyes(),
             DisplayMode::TestPattern => self.render_test_pattern(),
+            DisplayMode::Gif => self.render_gif(),
+            DisplayMode::Text => self.render_text(),
+            DisplayMode::Clock => self.render_clock(),
+            DisplayMode::Slideshow => self.render_slideshow(),
+        }
+        self.blend_transition();
+    }
+
+    /// 把新模式的画面与切换前的画面按进度线性混合
+    fn blend_transition(&mut self) {
+        let Some(transition) = &mut self.transition else {
+            return;
+        };
+        transition.frame += 1;
+        if transition.frame >= self.transition_frames {
+            self.transition = None;
+            return;
+        }
+        let t = transition.frame * 256 / self.transition_frames;
+        for (dst, &src) in self
+            .buffer
+            .as_mut_data()
+            .iter_mut()
+            .zip(transition.from.iter())
+        {
+            *dst = ((src as u32 * (256 - t) + *dst as u32 * t) / 256) as u8;
         }
     }
 
-    /// 获取帧数据向量
+    /// 设置模式切换的过渡帧数, 0 表示直接切换
+    pub fn set_transition_frames(&mut self, frames: u32) {
+        self.transition_frames = frames;
+    }
+
+    /// 生成当前帧并复制为新的 RGB888 向量, 已按屏幕方向、亮度和伽马调整, 用于截图等需要持有帧数据的场合
     pub fn frame_vec(&mut self) -> Vec<u8> {
         self.generate_pixels();
-        self.buffer.as_data().to_vec()
+        let mut pixels = Vec::new();
+        self.write_rgb(&mut pixels);
+        pixels
+    }
+
+    /// 把最近一次生成的帧写入 `out`, 即实际发送给设备的数据:
+    /// 按屏幕方向、亮度和伽马调整后再按像素格式打包
+    pub fn write_output(&mut self, out: &mut Vec<u8>) {
+        if self.pixel_format == PixelFormat::Rgb888 {
+            self.write_rgb(out);
+            return;
+        }
+        let mut rgb = std::mem::take(&mut self.rgb_scratch);
+        self.write_rgb(&mut rgb);
+        self.pixel_format.pack(&rgb, out);
+        self.rgb_scratch = rgb;
+    }
+
+    /// 设置发送给设备的像素格式, 需与通信后端一致
+    pub fn set_pixel_format(&mut self, format: PixelFormat) {
+        self.pixel_format = format;
+    }
+
+    /// 把最近一次生成的帧按屏幕方向、亮度和伽马调整后以 RGB888 写入 `out`
+    fn write_rgb(&self, out: &mut Vec<u8>) {
+        let pixels = self.frame_bytes();
+        out.clear();
+        match &self.orientation_map {
+            Some(map) => {
+                out.reserve(pixels.len());
+                for &src in map {
+                    let i = src as usize * 3;
+                    out.extend_from_slice(&pixels[i..i + 3]);
+                }
+            }
+            None => out.extend_from_slice(pixels),
+        }
+        self.apply_tone_map(out);
+    }
+
+    /// 设置屏幕方向, 变化时重新生成坐标映射表, 返回是否有变化
+    ///
+    /// 不是 90 的倍数的旋转角度按 0 处理
+    pub fn set_orientation(&mut self, mut orientation: Orientation) -> bool {
+        orientation.rotate %= 360;
+        if !orientation.rotate.is_multiple_of(90) {
+            orientation.rotate = 0;
+        }
+        if orientation == self.orientation {
+            return false;
+        }
+        self.orientation = orientation;
+        self.orientation_map = if orientation == Orientation::default() {
+            None
+        } else {
+            let mut map = vec![0u32; LCD_WIDTH * LCD_HEIGHT];
+            for y in 0..LCD_HEIGHT {
+                for x in 0..LCD_WIDTH {
+                    let (dx, dy) = orientation.map(x, y);
+                    map[dy * LCD_WIDTH + dx] = (y * LCD_WIDTH + x) as u32;
+                }
+            }
+            Some(map)
+        };
+        true
+    }
+
+    /// 设置亮度和伽马, 值变化时才重新生成查找表, 返回是否有变化
+    ///
+    /// 每个通道按 `255 * brightness * (v / 255) ^ (1 / gamma)` 计算, 结果截断到 0..=255,
+    /// 两者都为 1.0 时不做调整
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - 亮度倍数, 截断到 [BRIGHTNESS_RANGE]
+    /// * `gamma` - 伽马值, 大于 1 时提亮暗部, 截断到 [GAMMA_RANGE]
+    pub fn set_tone(&mut self, brightness: f32, gamma: f32) -> bool {
+        // 配置文件中的非法值按默认值处理
+        let clamp = |value: f32, range: &std::ops::RangeInclusive<f32>| {
+            if value.is_finite() {
+                value.clamp(*range.start(), *range.end())
+            } else {
+                1.0
+            }
+        };
+        let (brightness, gamma) = (
+            clamp(brightness, &BRIGHTNESS_RANGE),
+            clamp(gamma, &GAMMA_RANGE),
+        );
+        if (brightness, gamma) == self.tone {
+            return false;
+        }
+        self.tone = (brightness, gamma);
+        self.tone_lut = if brightness == 1.0 && gamma == 1.0 {
+            None
+        } else {
+            Some(Box::new(std::array::from_fn(|v| {
+                let level = (v as f32 / 255.0).powf(1.0 / gamma) * brightness;
+                (level * 255.0).round().clamp(0.0, 255.0) as u8
+            })))
+        };
+        true
+    }
+
+    /// 按亮度和伽马查表调整像素, 用于发送前的帧副本
+    ///
+    /// 内部缓冲区会被下一帧复用 (眼睛模式不变时不重绘, 过渡从中取旧画面), 不能原地调整
+    pub fn apply_tone_map(&self, pixels: &mut [u8]) {
+        if let Some(lut) = &self.tone_lut {
+            for value in pixels.iter_mut() {
+                *value = lut[*value as usize];
+            }
+        }
+    }
+
+    /// 借用内部缓冲区中的帧数据, 内容为最近一次 [Lcd::generate_pixels] 的结果
+    pub fn frame_bytes(&self) -> &[u8] {
+        self.buffer.as_data()
     }
 
     pub fn set_mode(&mut self, mode: DisplayMode) {
+        if mode != self.mode && self.transition_frames > 0 {
+            // 从当前画面 (可能是上一次过渡的中间帧) 开始过渡
+            self.transition = Some(Transition {
+                from: self.buffer.as_data().to_vec(),
+                frame: 0,
+            });
+        }
         self.mode = mode;
+        // 其他模式会覆盖缓冲区, 切回眼睛时需要重新绘制
+        self.last_eyes_hash = None;
     }
 
+    /// 当前显示模式
+    pub fn mode(&self) -> DisplayMode {
+        self.mode
+    }
+
+    /// 加载静态图片, 数据大小不是 240x240 RGB 时返回错误, 原有图片保持不变
     pub fn load_image(&mut self, path: &str) -> Result<()> {
-        self.buffer
+        let mut buffer = ImageBuffer::new();
+        buffer
             .load_from_file(path)
             .map_err(|e| anyhow::anyhow!("Failed to load image {}: {}", path, e))?;
-        self.image_data = Some(self.buffer.as_data().to_vec());
+        let data = buffer.as_data();
+        if data.len() != FRAME_SIZE {
+            anyhow::bail!(
+                "Image {} has {} bytes, expected {} ({}x{} RGB)",
+                path,
+                data.len(),
+                FRAME_SIZE,
+                LCD_WIDTH,
+                LCD_HEIGHT
+            );
+        }
+        self.image_data = Some(data.to_vec());
+        Ok(())
+    }
+
+    /// 设置静态图片像素并切换到静态图片模式, 数据大小不是 240x240 RGB 时返回错误
+    pub fn set_image_data(&mut self, data: Vec<u8>) -> Result<()> {
+        if data.len() != FRAME_SIZE {
+            anyhow::bail!("Image has {} bytes, expected {}", data.len(), FRAME_SIZE);
+        }
+        self.image_data = Some(data);
+        self.set_mode(DisplayMode::Static);
         Ok(())
     }
 
+    /// 加载 GIF 动画并切换到 GIF 模式, 非正方形的 GIF 居中裁剪
+    pub fn load_gif(&mut self, path: &str) -> Result<()> {
+        let file =
+            File::open(path).map_err(|e| anyhow::anyhow!("Failed to open gif {}: {}", path, e))?;
+        let decoder = GifDecoder::new(BufReader::new(file))?;
+        let frames = decoder
+            .into_frames()
+            .map(|frame| {
+                let frame = frame?;
+                let delay = Duration::from(frame.delay()).max(GIF_MIN_DELAY);
+                Ok(GifFrame {
+                    pixels: fit_to_lcd(frame.buffer()),
+                    delay,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if frames.is_empty() {
+            anyhow::bail!("Gif {} has no frames", path);
+        }
+        log::info!("Loaded gif {}: {} frames", path, frames.len());
+        self.gif_frames = frames;
+        self.gif_index = 0;
+        self.gif_frame_start = Instant::now();
+        self.set_mode(DisplayMode::Gif);
+        Ok(())
+    }
+
+    /// 加载目录下的图片并切换到幻灯片模式, 返回图片数量
+    ///
+    /// 图片按文件名排序, 居中裁剪并缩放到 240x240; 无法解码的图片跳过,
+    /// 目录不可读或没有可用图片时返回错误, 显示模式保持不变
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - 图片目录
+    /// * `interval` - 每张图片的显示时长
+    pub fn load_slideshow(&mut self, dir: &str, interval: Duration) -> Result<usize> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", dir, e))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| SLIDESHOW_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            })
+            .collect();
+        paths.sort();
+
+        let slides: Vec<Vec<u8>> = paths
+            .iter()
+            .filter_map(|path| match image::open(path) {
+                Ok(img) => Some(fit_to_lcd(&img.to_rgba8())),
+                Err(e) => {
+                    log::warn!("Skip slide {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect();
+        if slides.is_empty() {
+            anyhow::bail!("No images in {}", dir);
+        }
+
+        let count = slides.len();
+        log::info!("Loaded slideshow {}: {} images", dir, count);
+        self.slides = slides;
+        self.slide_index = 0;
+        self.slide_interval = interval;
+        self.slide_start = Instant::now();
+        self.set_mode(DisplayMode::Slideshow);
+        Ok(count)
+    }
+
+    /// 按间隔循环显示幻灯片
+    fn render_slideshow(&mut self) {
+        if self.slides.is_empty() {
+            self.render_eyes();
+            return;
+        }
+        if self.slide_start.elapsed() >= self.slide_interval {
+            self.slide_start = Instant::now();
+            self.slide_index = (self.slide_index + 1) % self.slides.len();
+        }
+        self.buffer
+            .as_mut_data()
+            .copy_from_slice(&self.slides[self.slide_index]);
+    }
+
+    /// 按每帧延时循环播放 GIF
+    fn render_gif(&mut self) {
+        if self.gif_frames.is_empty() {
+            self.render_eyes();
+            return;
+        }
+        // 发送间隔可能大于帧延时, 一次跳过所有已到期的帧
+        while self.gif_frame_start.elapsed() >= self.gif_frames[self.gif_index].delay {
+            self.gif_frame_start += self.gif_frames[self.gif_index].delay;
+            self.gif_index = (self.gif_index + 1) % self.gif_frames.len();
+        }
+        self.buffer
+            .as_mut_data()
+            .copy_from_slice(&self.gif_frames[self.gif_index].pixels);
+    }
+
+    /// 显示文字并切换到文字模式
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - 要显示的文字, 居中并自动换行
+    /// * `color` - 文字颜色
+    pub fn set_text(&mut self, text: &str, color: Rgb<u8>) {
+        self.text = text.to_string();
+        self.text_color = color;
+        self.set_mode(DisplayMode::Text);
+    }
+
+    fn render_text(&mut self) {
+        self.text_renderer
+            .render(self.buffer.as_mut_data(), &self.text, self.text_color);
+    }
+
+    /// 设置时钟颜色
+    pub fn set_clock_color(&mut self, color: Rgb<u8>) {
+        self.clock_color = color;
+    }
+
+    /// 居中显示当前时间 HH:MM:SS, 内容每秒变化一次, 其余帧因哈希不变不会重复发送
+    fn render_clock(&mut self) {
+        let now = chrono::Local::now().format("%H:%M:%S").to_string();
+        self.text_renderer
+            .render(self.buffer.as_mut_data(), &now, self.clock_color);
+    }
+
     fn render_static_image(&mut self) {
+        // 图片尺寸在 load_image 时已校验
         if let Some(ref img) = self.image_data {
-            if img.len() == FRAME_SIZE {
-                self.buffer.as_mut_data().copy_from_slice(img);
-            }
+            self.buffer.as_mut_data().copy_from_slice(img);
         } else {
             log::info!("Failed to load static image, show eyes");
             self.render_eyes();
@@ -120,16 +683,29 @@ impl Lcd {
         let current_hash = compute_hash(gray_buffer.as_raw());
         if Some(current_hash) != self.last_eyes_hash {
             self.last_eyes_hash = Some(current_hash);
-            for (i, pixel) in gray_buffer.pixels().enumerate() {
-                let gray = pixel.0[0];
-                let rgb_idx = i * 3;
-                self.buffer.as_mut_data()[rgb_idx] = gray; // R
-                self.buffer.as_mut_data()[rgb_idx + 1] = gray; // G
-                self.buffer.as_mut_data()[rgb_idx + 2] = gray; // B
+            let (r, g, b) = self.eye_tint;
+            let tint = |gray: u8, channel: u8| (gray as u16 * channel as u16 / 255) as u8;
+            // 先算出 256 级灰度对应的颜色, 逐像素只需查表复制
+            let palette: [[u8; 3]; 256] = std::array::from_fn(|gray| {
+                let gray = gray as u8;
+                [tint(gray, r), tint(gray, g), tint(gray, b)]
+            });
+            let pixels = self.buffer.as_mut_data().chunks_exact_mut(3);
+            for (rgb, &gray) in pixels.zip(gray_buffer.as_raw()) {
+                rgb.copy_from_slice(&palette[gray as usize]);
             }
         }
     }
 
+    /// 设置眼睛颜色, 白色保持原样
+    pub fn set_eye_tint(&mut self, tint: (u8, u8, u8)) {
+        if tint != self.eye_tint {
+            self.eye_tint = tint;
+            // 灰度帧可能不变, 清除缓存强制重新着色
+            self.last_eyes_hash = None;
+        }
+    }
+
     /// 设置眼睛表情
     pub fn set_eyes_mood(&mut self, mood: Mood) {
         self.eyes.set_mood(mood);
@@ -140,8 +716,49 @@ impl Lcd {
         self.eyes.set_position(position);
     }
 
+    /// 设置测试图案
+    pub fn set_test_pattern(&mut self, pattern: TestPattern) {
+        self.test_pattern = pattern;
+    }
+
+    /// 当前测试图案
+    pub fn test_pattern(&self) -> TestPattern {
+        self.test_pattern
+    }
+
     fn render_test_pattern(&mut self) {
-        // 简单的颜色条测试图案
+        match self.test_pattern {
+            TestPattern::ColorBars => self.render_color_bars(),
+            TestPattern::Gradient => self.fill_with(|x, y| {
+                // 横向红色、纵向绿色渐变, 蓝色沿对角线变化
+                let r = (x * 255 / (LCD_WIDTH - 1)) as u8;
+                let g = (y * 255 / (LCD_HEIGHT - 1)) as u8;
+                let b = ((x + y) * 255 / (LCD_WIDTH + LCD_HEIGHT - 2)) as u8;
+                [r, g, b]
+            }),
+            TestPattern::Checkerboard => self.fill_with(|x, y| {
+                if (x / CHECKER_SIZE + y / CHECKER_SIZE).is_multiple_of(2) {
+                    [255, 255, 255]
+                } else {
+                    [0, 0, 0]
+                }
+            }),
+            TestPattern::SolidWhite => self.fill_with(|_, _| [255, 255, 255]),
+            TestPattern::Crosshair => self.fill_with(|x, y| {
+                // 中心十字 + 一像素边框, 用于检查显示区域是否对齐
+                let center = x == LCD_WIDTH / 2 || y == LCD_HEIGHT / 2;
+                let border = x == 0 || y == 0 || x == LCD_WIDTH - 1 || y == LCD_HEIGHT - 1;
+                if center || border {
+                    [255, 255, 255]
+                } else {
+                    [0, 0, 0]
+                }
+            }),
+        }
+    }
+
+    /// 简单的颜色条测试图案
+    fn render_color_bars(&mut self) {
         let colors = [
             electron_bot::Color::Red,
             electron_bot::Color::Green,
@@ -157,6 +774,13 @@ impl Lcd {
             self.buffer.fill_rect(0, y, LCD_WIDTH, block_height, *color);
         }
     }
+
+    /// 按坐标逐像素填充整个缓冲区
+    fn fill_with(&mut self, pixel: impl Fn(usize, usize) -> [u8; 3]) {
+        for (i, rgb) in self.buffer.as_mut_data().chunks_exact_mut(3).enumerate() {
+            rgb.copy_from_slice(&pixel(i % LCD_WIDTH, i / LCD_WIDTH));
+        }
+    }
 }
 
 impl Default for Lcd {
@@ -164,3 +788,77 @@ impl Default for Lcd {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARK: [u8; 3] = [255, 0, 0];
+
+    /// 在 (10, 20) 放一个标记像素, 按 `orientation` 输出后返回标记所在坐标
+    fn marked_pixel(orientation: Orientation) -> (usize, usize) {
+        let mut image = vec![0u8; FRAME_SIZE];
+        let i = (20 * LCD_WIDTH + 10) * 3;
+        image[i..i + 3].copy_from_slice(&MARK);
+
+        let mut lcd = Lcd::new();
+        lcd.set_image_data(image).unwrap();
+        lcd.set_orientation(orientation);
+        let frame = lcd.frame_vec();
+        let index = frame
+            .chunks_exact(3)
+            .position(|rgb| rgb == MARK)
+            .expect("mark pixel missing");
+        (index % LCD_WIDTH, index / LCD_WIDTH)
+    }
+
+    fn rotate(rotate: u16) -> Orientation {
+        Orientation {
+            rotate,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_orientation_keeps_pixel() {
+        assert_eq!(marked_pixel(Orientation::default()), (10, 20));
+    }
+
+    #[test]
+    fn rotation_moves_pixel_clockwise() {
+        assert_eq!(marked_pixel(rotate(0)), (10, 20));
+        assert_eq!(marked_pixel(rotate(90)), (219, 10));
+        assert_eq!(marked_pixel(rotate(180)), (229, 219));
+        assert_eq!(marked_pixel(rotate(270)), (20, 229));
+    }
+
+    #[test]
+    fn flips_mirror_pixel() {
+        let flip_h = Orientation {
+            flip_h: true,
+            ..Default::default()
+        };
+        let flip_v = Orientation {
+            flip_v: true,
+            ..Default::default()
+        };
+        assert_eq!(marked_pixel(flip_h), (229, 20));
+        assert_eq!(marked_pixel(flip_v), (10, 219));
+    }
+
+    #[test]
+    fn flip_applies_after_rotation() {
+        let orientation = Orientation {
+            flip_h: true,
+            rotate: 90,
+            ..Default::default()
+        };
+        assert_eq!(marked_pixel(orientation), (20, 10));
+    }
+
+    #[test]
+    fn invalid_rotation_is_ignored() {
+        assert_eq!(marked_pixel(rotate(45)), (10, 20));
+        assert_eq!(marked_pixel(rotate(450)), (219, 10));
+    }
+}
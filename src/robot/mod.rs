@@ -1,106 +1,476 @@
 //! Robot 模块 - ElectronBot 机器人抽象
 //!
-//! 使用 [electron_bot](electron_bot/index.html) 库实现 USB 通信
+//! 通过 libusb 批量传输与机器人通信, 也支持 USB CDC 串口
 
+pub mod cdc;
+pub mod frame_queue;
+pub mod hotplug;
 pub mod joint;
 pub mod lcd;
+pub mod motion;
+pub mod text;
+pub mod transport;
 
-use electron_bot::ElectronBot;
+use anyhow::Context;
+pub use frame_queue::{frame_queue, FrameReceiver, FrameSender, QueuePolicy, QueueStats};
+pub use hotplug::{HotplugEvent, HotplugWatcher};
 pub use joint::{Joint, JointConfig, ServoState, SERVO_COUNT};
 pub use lcd::{DisplayMode, Lcd};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
+pub use transport::{Feedback, PixelFormat, Transport, TransportConfig, TransportKind};
+
+/// ElectronBot USB 厂商 ID
+pub const DEVICE_VID: u16 = 0x1001;
+/// ElectronBot USB 产品 ID
+pub const DEVICE_PID: u16 = 0x8023;
+
+/// USB 厂商/产品 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsbIds {
+    pub vid: u16,
+    pub pid: u16,
+}
+
+impl Default for UsbIds {
+    fn default() -> Self {
+        Self {
+            vid: DEVICE_VID,
+            pid: DEVICE_PID,
+        }
+    }
+}
+
+impl std::fmt::Display for UsbIds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vid, self.pid)
+    }
+}
+
+/// 找不到设备, 重试也不会成功, [retry_with_backoff] 遇到时立即返回
+#[derive(Debug)]
+pub struct DeviceNotFound(pub String);
+
+impl std::fmt::Display for DeviceNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} not found", self.0)
+    }
+}
+
+impl std::error::Error for DeviceNotFound {}
+
 // ==================== Robot 结构体 ====================
 
+/// 通信使用的 USB 接口号
+const USB_INTERFACE: u8 = 0;
+/// 批量传输输出端点
+const USB_EP_OUT: u8 = 0x01;
+/// 批量传输输入端点
+const USB_EP_IN: u8 = 0x81;
+/// 单次批量传输超时
+const USB_TIMEOUT: Duration = Duration::from_millis(200);
+
 #[allow(dead_code)]
 pub struct Robot {
-    bot: ElectronBot,
+    handle: rusb::DeviceHandle<rusb::GlobalContext>,
+    ids: UsbIds,
+    tail: [u8; transport::TAIL_SIZE], // 每轮结尾包: 像素尾部 + 关节配置
+    last_feedback: Option<[f32; SERVO_COUNT]>, // 上一次完整收到的舵机角度
 }
 
 #[allow(dead_code)]
 impl Robot {
     /// 打开并初始化机器人连接
-    pub fn open() -> Result<Self, electron_bot::BotError> {
-        let mut bot = ElectronBot::new();
-        bot.connect()?;
-        Ok(Self { bot })
+    ///
+    /// 按配置的 VID/PID 查找并打开设备, 重刷固件后 ID 不同的板子同样可用
+    pub fn open(ids: UsbIds) -> anyhow::Result<Self> {
+        log::info!("Searching for USB device {ids}");
+        let Some(device) = find_device(ids) else {
+            return Err(DeviceNotFound(format!("USB device {ids}")).into());
+        };
+        let handle = device
+            .open()
+            .with_context(|| format!("Failed to open USB device {ids}"))?;
+        // Linux 下接口可能已被内核驱动占用, 不支持自动分离的平台忽略
+        let _ = handle.set_auto_detach_kernel_driver(true);
+        handle
+            .claim_interface(USB_INTERFACE)
+            .with_context(|| format!("Failed to claim interface of USB device {ids}"))?;
+        Ok(Self {
+            handle,
+            ids,
+            tail: [0; transport::TAIL_SIZE],
+            last_feedback: None,
+        })
     }
 
-    /// 检查是否已连接
-    pub fn is_connected(&self) -> bool {
-        self.bot.is_connected()
+    /// 读取设备的厂商、产品和序列号字符串, 读取失败的字段为 "未知"
+    pub fn device_info(&self) -> DeviceInfo {
+        let mut info = DeviceInfo::default();
+        let descriptor = match self.handle.device().device_descriptor() {
+            Ok(descriptor) => descriptor,
+            Err(e) => {
+                log::warn!("Failed to read device descriptor: {e}");
+                return info;
+            }
+        };
+
+        let read = |index: Option<u8>| {
+            index.and_then(|i| self.handle.read_string_descriptor_ascii(i).ok())
+        };
+        if let Some(s) = read(descriptor.manufacturer_string_index()) {
+            info.manufacturer = s;
+        }
+        if let Some(s) = read(descriptor.product_string_index()) {
+            info.product = s;
+        }
+        if let Some(s) = read(descriptor.serial_number_string_index()) {
+            info.serial = s;
+        }
+        info
     }
 
-    /// 发送一帧数据
+    /// 发送一帧数据, 返回设备回传的舵机实际角度, 见 [sync_frame]
+    ///
+    /// 本帧回包不完整时返回上一次收到的角度并标记为过期, 从未收到过时返回 `None`.
+    /// `pixels` 必须正好是 [lcd::FRAME_SIZE] 字节, 由调用方经 [transport::check_frame_size] 检查
     pub fn send_frame(
         &mut self,
         pixels: &[u8],
         config: &[u8; 32],
-    ) -> Result<(), electron_bot::BotError> {
-        self.bot
-            .image_buffer()
-            .as_mut_data()
-            .copy_from_slice(pixels);
-
-        self.bot.extra_data().set_raw(config);
+    ) -> rusb::Result<Option<Feedback>> {
         let start_time = Instant::now();
-        self.bot.sync()?;
+        let angles = sync_frame(&mut self.handle, &mut self.tail, pixels, config)?;
         let elapsed = start_time.elapsed();
         if elapsed > Duration::from_millis(60) {
             log::warn!("Refresh screen took too long, used time: {elapsed:?}");
         }
+        if angles.is_some() {
+            self.last_feedback = angles;
+        }
+        Ok(self.last_feedback.map(|last| Feedback {
+            angles: last,
+            feedback_valid: angles.is_some(),
+        }))
+    }
+}
+
+/// USB 批量端点读写, 测试时替换为内存实现
+trait BulkIo {
+    /// 从输入端点读取, 返回读到的字节数
+    fn read(&mut self, buf: &mut [u8]) -> rusb::Result<usize>;
+    /// 向输出端点写入, 返回写入的字节数
+    fn write(&mut self, data: &[u8]) -> rusb::Result<usize>;
+
+    /// 写入整块数据, 只写入一部分视为超时
+    fn write_all(&mut self, data: &[u8]) -> rusb::Result<()> {
+        if self.write(data)? != data.len() {
+            return Err(rusb::Error::Timeout);
+        }
         Ok(())
     }
 }
 
+impl BulkIo for rusb::DeviceHandle<rusb::GlobalContext> {
+    fn read(&mut self, buf: &mut [u8]) -> rusb::Result<usize> {
+        self.read_bulk(USB_EP_IN, buf, USB_TIMEOUT)
+    }
+
+    fn write(&mut self, data: &[u8]) -> rusb::Result<usize> {
+        self.write_bulk(USB_EP_OUT, data, USB_TIMEOUT)
+    }
+}
+
+/// 按 ElectronBot 固件的批量传输协议同步一帧, 与官方 SDK 的 `ElectronLowLevel::Sync` 相同
+///
+/// 每轮设备先发来 32 字节请求, 布局与 [JointConfig] 相同, 其中是舵机当前角度;
+/// 之后发送 60 行像素去掉最后 192 字节 (84 个 512 字节包), 再把这 192 字节与关节配置
+/// 拼成 224 字节结尾包发送. 读请求超时或请求不完整时仍继续发送画面,
+/// 只要有一轮没收到完整请求就返回 `None`, 否则返回最后一轮请求中的角度
+fn sync_frame(
+    io: &mut impl BulkIo,
+    tail: &mut [u8; transport::TAIL_SIZE],
+    pixels: &[u8],
+    config: &[u8; 32],
+) -> rusb::Result<Option<[f32; SERVO_COUNT]>> {
+    debug_assert_eq!(pixels.len(), lcd::FRAME_SIZE, "Frame size mismatch");
+    let head_size = transport::BYTES_PER_ROUND - transport::PIXELS_IN_TAIL;
+    tail[transport::PIXELS_IN_TAIL..].copy_from_slice(config);
+    let mut response = [0u8; transport::RESPONSE_SIZE];
+    let mut valid = true;
+
+    for round in pixels.chunks_exact(transport::BYTES_PER_ROUND) {
+        match io.read(&mut response) {
+            Ok(received) => valid &= received == transport::RESPONSE_SIZE,
+            Err(rusb::Error::Timeout) => {
+                log::debug!("USB request timed out, feedback is stale");
+                valid = false;
+            }
+            Err(e) => return Err(e),
+        }
+        let (head, pixels_in_tail) = round.split_at(head_size);
+        io.write_all(head)?;
+        tail[..transport::PIXELS_IN_TAIL].copy_from_slice(pixels_in_tail);
+        io.write_all(tail)?;
+    }
+
+    Ok(valid.then(|| JointConfig::from_bytes(&response).angles))
+}
+
+/// 设备描述信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub manufacturer: String,
+    pub product: String,
+    pub serial: String,
+}
+
+impl Default for DeviceInfo {
+    fn default() -> Self {
+        Self {
+            manufacturer: "未知".to_string(),
+            product: "未知".to_string(),
+            serial: "未知".to_string(),
+        }
+    }
+}
+
 // ==================== 通信线程管理 ====================
 
+/// 连续同步失败多少次视为连接断开
+const MAX_SYNC_FAILURES: u32 = 5;
+/// 首次连接的尝试次数, 刚插入的设备可能还未就绪
+const OPEN_RETRY_ATTEMPTS: u32 = 3;
+/// 首次连接重试的初始等待时间
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(200);
+/// 重连初始等待时间
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_millis(200);
+/// 重连最长等待时间
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(3);
+/// 等待新帧的超时, 同时是检查退出标志的间隔
+const FRAME_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 通信链路状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LinkState {
+    Connected = 0,
+    Reconnecting = 1,
+}
+
+impl LinkState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Reconnecting,
+            _ => Self::Connected,
+        }
+    }
+}
+
+/// 通信线程上报给界面的错误
+#[derive(Debug, Clone)]
+pub enum CommError {
+    /// 打开设备失败
+    Open(String),
+    /// 同步帧失败
+    Sync { failures: u32, message: String },
+    /// 连续同步失败, 开始重连
+    LinkLost,
+}
+
+impl std::fmt::Display for CommError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommError::Open(e) => write!(f, "打开设备失败: {e}"),
+            CommError::Sync { failures, message } => {
+                write!(f, "同步失败 ({failures}/{MAX_SYNC_FAILURES}): {message}")
+            }
+            CommError::LinkLost => write!(f, "连接断开, 正在重连"),
+        }
+    }
+}
+
+/// 通信线程累计统计
+#[derive(Debug, Default)]
+pub struct CommStats {
+    pub frames: AtomicU64,
+    pub bytes: AtomicU64,
+    pub failures: AtomicU64,
+}
+
+/// 统计快照
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommStatsSnapshot {
+    pub frames: u64,
+    pub bytes: u64,
+    pub failures: u64,
+}
+
+impl CommStats {
+    /// 读取当前计数
+    pub fn snapshot(&self) -> CommStatsSnapshot {
+        CommStatsSnapshot {
+            frames: self.frames.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// 通信线程状态
 pub struct CommState {
     pub running: Arc<AtomicBool>,
+    pub stats: Arc<CommStats>,
+    link: Arc<AtomicU8>,
+    pub device_info: DeviceInfo,
+    feedback_rx: mpsc::Receiver<Feedback>,
+    recycle_rx: mpsc::Receiver<Vec<u8>>,
+}
+
+impl CommState {
+    /// 当前链路状态
+    pub fn link_state(&self) -> LinkState {
+        LinkState::from_u8(self.link.load(Ordering::Relaxed))
+    }
+
+    /// 非阻塞获取最新的舵机反馈角度
+    pub fn try_recv_feedback(&self) -> Option<Feedback> {
+        self.feedback_rx.try_iter().last()
+    }
+
+    /// 取回通信线程发送完的像素缓冲区, 用于下一帧, 避免每帧重新分配
+    pub fn try_recv_buffer(&self) -> Option<Vec<u8>> {
+        self.recycle_rx.try_recv().ok()
+    }
 }
 
 /// 启动后台通信线程
+///
+/// 连续同步失败时认为连接断开, 释放句柄后按退避时间重新打开传输后端,
+/// 直到重连成功或 `running` 被清除; 断线期间仍持续接收帧, 只保留最新一帧
+///
+/// # Arguments
+///
+/// * `rx` - 帧接收端, 按顺序发送其中的帧
+/// * `error_tx` - 通信过程中的错误上报通道, 界面未及时读取时丢弃; 首次打开失败只作为返回值
+/// * `transport_config` - 传输后端配置, 重连时复用
 pub fn start_comm_thread(
-    rx: std::sync::mpsc::Receiver<(Vec<u8>, JointConfig)>,
+    rx: FrameReceiver<(Vec<u8>, JointConfig)>,
+    error_tx: mpsc::SyncSender<CommError>,
+    transport_config: TransportConfig,
 ) -> anyhow::Result<(CommState, thread::JoinHandle<()>)> {
     let running = Arc::new(AtomicBool::new(true));
+    let link = Arc::new(AtomicU8::new(LinkState::Connected as u8));
+    let stats = Arc::new(CommStats::default());
+    let (feedback_tx, feedback_rx) = mpsc::sync_channel(4);
+    // 界面最多缓存两块, 多余的直接释放
+    let (recycle_tx, recycle_rx) = mpsc::sync_channel(2);
+
+    let transport = match retry_with_backoff(OPEN_RETRY_ATTEMPTS, OPEN_RETRY_DELAY, || {
+        transport::open_transport(&transport_config)
+    }) {
+        Ok(transport) => {
+            log::info!("Robot connected via {:?}", transport_config.kind);
+            transport
+        }
+        // 首次打开失败由调用方决定是否上报
+        Err(e) => return Err(e),
+    };
+    let device_info = transport.device_info();
+    log::info!(
+        "Device info: manufacturer={}, product={}, serial={}",
+        device_info.manufacturer,
+        device_info.product,
+        device_info.serial
+    );
+
     let state = CommState {
         running: running.clone(),
+        stats: stats.clone(),
+        link: link.clone(),
+        device_info,
+        feedback_rx,
+        recycle_rx,
     };
-
-    let mut bot = ElectronBot::new();
-    match bot.connect() {
-        Ok(_) => {
-            log::info!("Robot connected");
-        }
-        Err(e) => {
-            anyhow::bail!("Failed to connect: {e}");
-        }
-    }
     let handle = thread::spawn(move || {
         thread::sleep(Duration::from_millis(100));
 
+        let mut transport = Some(transport);
+        let mut last_pixels = None;
+        let mut failures = 0;
         // 主循环
-        for (pixels, joint) in rx {
-            if !running.load(Ordering::Relaxed) {
-                break;
+        // 定时检查退出标志, 发送端停止发帧时也能立即退出
+        'frames: while running.load(Ordering::Relaxed) {
+            let mut frame = match rx.recv_timeout(FRAME_POLL_INTERVAL) {
+                Ok(frame) => frame,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+            // 断线时先重连, 重连成功后补发期间收到的最新一帧
+            loop {
+                if !running.load(Ordering::Relaxed) {
+                    break 'frames;
+                }
+
+                let bot = match transport.as_mut() {
+                    Some(bot) => bot,
+                    None => {
+                        link.store(LinkState::Reconnecting as u8, Ordering::Relaxed);
+                        let Some(bot) =
+                            reconnect(&running, &rx, &mut frame, &error_tx, &transport_config)
+                        else {
+                            break 'frames;
+                        };
+                        link.store(LinkState::Connected as u8, Ordering::Relaxed);
+                        failures = 0;
+                        transport.insert(bot)
+                    }
+                };
+
+                let (pixels, joint) = &frame;
+                match bot.send_frame(pixels, joint) {
+                    Ok(feedback) => {
+                        failures = 0;
+                        // 像素数据加上每轮附带的 32 字节关节配置
+                        let bytes = pixels.len() + transport::ROUND_COUNT * 32;
+                        stats.frames.fetch_add(1, Ordering::Relaxed);
+                        stats.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+                        if let Some(feedback) = feedback {
+                            // UI 未及时读取时丢弃
+                            let _ = feedback_tx.try_send(feedback);
+                        }
+                        break;
+                    }
+                    Err(e) => {
+                        failures += 1;
+                        stats.failures.fetch_add(1, Ordering::Relaxed);
+                        log::error!("Sync failed ({failures}/{MAX_SYNC_FAILURES}): {e:#}");
+                        let _ = error_tx.try_send(CommError::Sync {
+                            failures,
+                            message: format!("{e:#}"),
+                        });
+                        if failures < MAX_SYNC_FAILURES {
+                            break;
+                        }
+                        log::warn!("Robot link lost, reconnecting...");
+                        let _ = error_tx.try_send(CommError::LinkLost);
+                        transport = None;
+                    }
+                }
             }
-            bot.image_buffer().as_mut_data().copy_from_slice(&pixels);
-            bot.extra_data().set_raw(&joint.as_bytes());
-            if let Err(e) = bot.sync() {
-                log::error!("Sync failed: {e}");
+            if let Some(pixels) = last_pixels.replace(frame.0) {
+                let _ = recycle_tx.try_send(pixels);
             }
         }
 
-        // 停止舵机
-        let stop_config = JointConfig::default();
-        bot.extra_data().set_raw(&stop_config.as_bytes());
-        let _ = bot.sync();
-
-        bot.disconnect();
+        // 停止舵机, 画面保持最后一帧
+        if let Some(mut bot) = transport {
+            let pixels =
+                last_pixels.unwrap_or_else(|| vec![0; transport_config.pixel_format.frame_size()]);
+            let _ = bot.send_frame(&pixels, &JointConfig::default());
+        }
         log::info!("Communication stopped");
         running.store(false, Ordering::Relaxed);
     });
@@ -108,6 +478,79 @@ pub fn start_comm_thread(
     Ok((state, handle))
 }
 
+/// 按指数退避重试, 全部失败时返回最后一次的错误
+///
+/// 设备不存在 ([DeviceNotFound]) 不是暂时性错误, 不再重试直接返回
+///
+/// # Arguments
+///
+/// * `attempts` - 最多尝试次数, 至少尝试一次
+/// * `base_delay` - 首次重试前的等待时间, 之后每次翻倍
+/// * `open` - 打开操作
+pub fn retry_with_backoff<T>(
+    attempts: u32,
+    base_delay: Duration,
+    mut open: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let attempts = attempts.max(1);
+    let mut delay = base_delay;
+    let mut attempt = 1;
+    loop {
+        match open() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts || e.is::<DeviceNotFound>() => return Err(e),
+            Err(e) => {
+                log::warn!("Open failed ({attempt}/{attempts}), retry in {delay:?}: {e:#}");
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// 断线重连, 等待期间持续取出最新帧以免发送端阻塞
+///
+/// # Arguments
+///
+/// * `running` - 运行标志, 被清除时放弃重连
+/// * `rx` - 帧接收端
+/// * `frame` - 待发送的帧, 重连期间替换为最新一帧
+/// * `error_tx` - 错误上报通道
+/// * `transport_config` - 传输后端配置
+fn reconnect(
+    running: &AtomicBool,
+    rx: &FrameReceiver<(Vec<u8>, JointConfig)>,
+    frame: &mut (Vec<u8>, JointConfig),
+    error_tx: &mpsc::SyncSender<CommError>,
+    transport_config: &TransportConfig,
+) -> Option<Box<dyn Transport>> {
+    let mut backoff = RECONNECT_BACKOFF_MIN;
+    while running.load(Ordering::Relaxed) {
+        match transport::open_transport(transport_config) {
+            Ok(transport) => {
+                log::info!("Robot reconnected");
+                return Some(transport);
+            }
+            Err(e) => {
+                log::debug!("Reconnect failed, retry in {backoff:?}: {e:#}");
+                let _ = error_tx.try_send(CommError::Open(format!("{e:#}")));
+            }
+        }
+
+        let deadline = Instant::now() + backoff;
+        while running.load(Ordering::Relaxed) && Instant::now() < deadline {
+            match rx.recv_timeout(FRAME_POLL_INTERVAL) {
+                Ok(latest) => *frame = latest,
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+        backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
+    }
+    None
+}
+
 /// 停止通信线程
 pub fn stop_comm_thread(state: &CommState) {
     state.running.store(false, Ordering::Relaxed);
@@ -115,17 +558,164 @@ pub fn stop_comm_thread(state: &CommState) {
 
 // ==================== 便捷函数 ====================
 
-#[allow(dead_code)]
-/// 扫描 USB 设备
-pub fn scan_devices() -> Vec<(u16, u16, String)> {
-    ElectronBot::scan_devices()
-        .into_iter()
-        .map(|d| (d.vid, d.pid, d.info))
-        .collect()
+/// 按 VID/PID 查找 USB 设备
+pub fn find_device(ids: UsbIds) -> Option<rusb::Device<rusb::GlobalContext>> {
+    let devices = match rusb::devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            log::warn!("Failed to list USB devices: {e}");
+            return None;
+        }
+    };
+    devices.iter().find(|device| {
+        device
+            .device_descriptor()
+            .is_ok_and(|d| d.vendor_id() == ids.vid && d.product_id() == ids.pid)
+    })
 }
 
-#[allow(dead_code)]
 /// 检查设备是否已连接
-pub fn is_device_present() -> bool {
-    ElectronBot::is_device_present()
+pub fn is_device_present(ids: UsbIds) -> bool {
+    find_device(ids).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// 内存中的 USB 端点, 按顺序返回预设的请求并记录写入的数据
+    #[derive(Default)]
+    struct FakeUsb {
+        requests: VecDeque<Vec<u8>>,
+        writes: Vec<Vec<u8>>,
+        reads: usize,
+        /// 第几次读取超时
+        timeout_at: Option<usize>,
+    }
+
+    impl BulkIo for FakeUsb {
+        fn read(&mut self, buf: &mut [u8]) -> rusb::Result<usize> {
+            // 每轮先读请求再写像素
+            assert_eq!(self.writes.len(), self.reads * 2, "read out of order");
+            self.reads += 1;
+            if self.timeout_at == Some(self.reads - 1) {
+                return Err(rusb::Error::Timeout);
+            }
+            let request = self.requests.pop_front().ok_or(rusb::Error::Timeout)?;
+            buf[..request.len()].copy_from_slice(&request);
+            Ok(request.len())
+        }
+
+        fn write(&mut self, data: &[u8]) -> rusb::Result<usize> {
+            self.writes.push(data.to_vec());
+            Ok(data.len())
+        }
+    }
+
+    fn request(angles: [f32; SERVO_COUNT]) -> Vec<u8> {
+        JointConfig { enable: 1, angles }.as_bytes().to_vec()
+    }
+
+    fn frame() -> Vec<u8> {
+        (0..lcd::FRAME_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn sync_frame_sends_rounds_like_sdk() {
+        let pixels = frame();
+        let config = [7u8; 32];
+        let mut usb = FakeUsb {
+            requests: (0..transport::ROUND_COUNT)
+                .map(|i| request([i as f32; SERVO_COUNT]))
+                .collect(),
+            ..Default::default()
+        };
+        let mut tail = [0u8; transport::TAIL_SIZE];
+        let angles = sync_frame(&mut usb, &mut tail, &pixels, &config).unwrap();
+
+        assert_eq!(usb.reads, transport::ROUND_COUNT);
+        assert_eq!(usb.writes.len(), transport::ROUND_COUNT * 2);
+        for (round, writes) in usb.writes.chunks(2).enumerate() {
+            let start = round * transport::BYTES_PER_ROUND;
+            let split = start + 84 * 512;
+            let end = start + transport::BYTES_PER_ROUND;
+            assert_eq!(writes[0], pixels[start..split]);
+            assert_eq!(writes[1].len(), transport::TAIL_SIZE);
+            assert_eq!(writes[1][..192], pixels[split..end]);
+            assert_eq!(writes[1][192..], config);
+        }
+        // 回传的是最后一轮请求中的角度
+        assert_eq!(angles, Some([3.0; SERVO_COUNT]));
+    }
+
+    #[test]
+    fn sync_frame_without_full_request_has_no_angles() {
+        let mut requests: VecDeque<_> = (0..transport::ROUND_COUNT)
+            .map(|_| request([1.0; SERVO_COUNT]))
+            .collect();
+        requests.back_mut().unwrap().truncate(16);
+        let mut usb = FakeUsb {
+            requests,
+            ..Default::default()
+        };
+        let mut tail = [0u8; transport::TAIL_SIZE];
+        let angles = sync_frame(&mut usb, &mut tail, &frame(), &[0; 32]).unwrap();
+        assert_eq!(angles, None);
+        assert_eq!(usb.writes.len(), transport::ROUND_COUNT * 2);
+    }
+
+    #[test]
+    fn sync_frame_keeps_sending_after_request_timeout() {
+        // 第二轮读请求超时, 最后一轮的请求仍然完整
+        let mut requests: VecDeque<_> = (0..transport::ROUND_COUNT)
+            .map(|_| request([1.0; SERVO_COUNT]))
+            .collect();
+        requests.remove(1);
+        let mut usb = FakeUsb {
+            requests,
+            timeout_at: Some(1),
+            ..Default::default()
+        };
+        let mut tail = [0u8; transport::TAIL_SIZE];
+        let angles = sync_frame(&mut usb, &mut tail, &frame(), &[0; 32]).unwrap();
+        assert_eq!(angles, None);
+        assert_eq!(usb.reads, transport::ROUND_COUNT);
+        assert_eq!(usb.writes.len(), transport::ROUND_COUNT * 2);
+    }
+
+    #[test]
+    fn retry_stops_on_device_not_found() {
+        let mut calls = 0;
+        let result: anyhow::Result<()> = retry_with_backoff(5, Duration::ZERO, || {
+            calls += 1;
+            Err(DeviceNotFound("USB device 1001:8023".to_string()).into())
+        });
+        assert!(result.unwrap_err().is::<DeviceNotFound>());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn retry_transient_errors_until_success() {
+        let mut calls = 0;
+        let result = retry_with_backoff(5, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                anyhow::bail!("pipe error");
+            }
+            Ok(calls)
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn retry_returns_last_error() {
+        let mut calls = 0;
+        let result: anyhow::Result<()> = retry_with_backoff(3, Duration::ZERO, || {
+            calls += 1;
+            anyhow::bail!("attempt {calls}")
+        });
+        assert_eq!(result.unwrap_err().to_string(), "attempt 3");
+        assert_eq!(calls, 3);
+    }
 }
@@ -0,0 +1,202 @@
+//! 动作录制与回放
+//!
+//! 录制时记录目标角度的每次变化及其时间, 保存为 JSON 文件; 回放时按时间依次设置目标角度.
+//! 另有空闲时在当前姿势上叠加正弦摆动的 [IdleMotion]
+
+use super::{ServoState, SERVO_COUNT};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 动作文件目录
+pub const MOTION_DIR: &str = "./motions";
+/// 呼吸动作摆动的舵机: 头部和身体
+const IDLE_MOTION_SERVOS: [usize; 2] = [0, 5];
+
+/// 关键帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// 相对录制开始的时间 (ms)
+    pub t_ms: u64,
+    /// 各舵机目标角度
+    pub values: [i16; SERVO_COUNT],
+}
+
+/// 动作序列
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Motion {
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Motion {
+    /// 从 JSON 文件加载
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存为 JSON 文件, 目录不存在时自动创建
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 总时长
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.keyframes.last().map(|k| k.t_ms).unwrap_or(0))
+    }
+}
+
+/// 列出动作目录下的 JSON 文件名
+pub fn list_motion_files() -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(MOTION_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(".json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// 动作录制器
+#[derive(Debug)]
+pub struct MotionRecorder {
+    start: Instant,
+    motion: Motion,
+}
+
+impl MotionRecorder {
+    /// 开始录制, 以当前姿势作为第一帧
+    pub fn start(values: &[i16; SERVO_COUNT]) -> Self {
+        Self {
+            start: Instant::now(),
+            motion: Motion {
+                keyframes: vec![Keyframe {
+                    t_ms: 0,
+                    values: *values,
+                }],
+            },
+        }
+    }
+
+    /// 记录目标角度, 与上一帧相同时忽略
+    pub fn record(&mut self, values: &[i16; SERVO_COUNT]) {
+        if self.motion.keyframes.last().map(|k| &k.values) == Some(values) {
+            return;
+        }
+        self.motion.keyframes.push(Keyframe {
+            t_ms: self.start.elapsed().as_millis() as u64,
+            values: *values,
+        });
+    }
+
+    /// 结束录制, 以当前时间作为最后一帧, 保证回放时停留到结束
+    pub fn finish(mut self) -> Motion {
+        if let Some(last) = self.motion.keyframes.last().cloned() {
+            self.motion.keyframes.push(Keyframe {
+                t_ms: self.start.elapsed().as_millis() as u64,
+                values: last.values,
+            });
+        }
+        self.motion
+    }
+}
+
+/// 动作播放器
+#[derive(Debug)]
+pub struct MotionPlayer {
+    motion: Motion,
+    start: Instant,
+    index: usize,
+    pub looping: bool,
+}
+
+impl MotionPlayer {
+    pub fn new(motion: Motion, looping: bool) -> Self {
+        Self {
+            motion,
+            start: Instant::now(),
+            index: 0,
+            looping,
+        }
+    }
+
+    /// 返回已到时间的最新关键帧角度, 没有新关键帧时返回 `None`
+    pub fn poll(&mut self) -> Option<[i16; SERVO_COUNT]> {
+        if self.is_finished() && self.looping && !self.motion.keyframes.is_empty() {
+            self.start = Instant::now();
+            self.index = 0;
+        }
+
+        let elapsed = self.start.elapsed().as_millis() as u64;
+        let mut latest = None;
+        while let Some(keyframe) = self.motion.keyframes.get(self.index) {
+            if keyframe.t_ms > elapsed {
+                break;
+            }
+            latest = Some(keyframe.values);
+            self.index += 1;
+        }
+        latest
+    }
+
+    /// 是否已播放完所有关键帧
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.motion.keyframes.len()
+    }
+}
+
+/// 空闲呼吸动作: 在保持的姿势上叠加正弦摆动, 停止后回到原姿势
+#[derive(Debug)]
+pub struct IdleMotion {
+    base: [i16; SERVO_COUNT],
+    start: Instant,
+    amplitude: f32,
+    frequency: f32,
+}
+
+impl IdleMotion {
+    /// 以当前目标角度为基准开始摆动
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - 保持的姿势
+    /// * `amplitude` - 摆动幅度 (度), 非法值按 0 处理
+    /// * `frequency` - 摆动频率 (Hz), 非法值按 0 处理
+    pub fn start(base: &[i16; SERVO_COUNT], amplitude: f32, frequency: f32) -> Self {
+        let valid = |value: f32| if value.is_finite() { value.abs() } else { 0.0 };
+        Self {
+            base: *base,
+            start: Instant::now(),
+            amplitude: valid(amplitude),
+            frequency: valid(frequency),
+        }
+    }
+
+    /// 开始摆动时的姿势
+    pub fn base(&self) -> &[i16; SERVO_COUNT] {
+        &self.base
+    }
+
+    /// 当前时刻的目标角度, 超出舵机范围时截断
+    pub fn poll(&self) -> [i16; SERVO_COUNT] {
+        let t = self.start.elapsed().as_secs_f32();
+        let mut values = self.base;
+        for (k, &i) in IDLE_MOTION_SERVOS.iter().enumerate() {
+            // 身体比头部滞后四分之一周期, 看起来更自然
+            let phase =
+                std::f32::consts::TAU * self.frequency * t - k as f32 * std::f32::consts::FRAC_PI_2;
+            let offset = (self.amplitude * phase.sin()).round() as i16;
+            values[i] = ServoState::clamp_angle(i, self.base[i].saturating_add(offset));
+        }
+        values
+    }
+}
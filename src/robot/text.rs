@@ -0,0 +1,235 @@
+//! LCD 文字渲染
+//!
+//! ASCII 字符使用 [embedded_graphics] 自带的 10x20 点阵字体;
+//! 其他字符 (如中文) 使用 [FALLBACK_FONT_PATH] 处的 TTF 字体, 字体文件不存在或缺字时画占位方框
+
+use super::lcd::{LCD_HEIGHT, LCD_WIDTH};
+use embedded_graphics::mono_font::{ascii::FONT_10X20, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Baseline, Text};
+use image::Rgb;
+use std::convert::Infallible;
+
+/// 备用字体路径, 用于点阵字体不支持的字符
+pub const FALLBACK_FONT_PATH: &str = "./assets/fonts/fallback.ttf";
+
+/// ASCII 字符宽度
+const CHAR_WIDTH: usize = 10;
+/// 宽字符 (中文等) 宽度
+const WIDE_CHAR_WIDTH: usize = 20;
+/// 行高
+const LINE_HEIGHT: usize = 22;
+/// 左右留白
+const MARGIN: usize = 10;
+
+/// 把 RGB 帧缓冲包装为 embedded-graphics 绘图目标
+struct RgbFrame<'a>(&'a mut [u8]);
+
+impl OriginDimensions for RgbFrame<'_> {
+    fn size(&self) -> Size {
+        Size::new(LCD_WIDTH as u32, LCD_HEIGHT as u32)
+    }
+}
+
+impl DrawTarget for RgbFrame<'_> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(i) = pixel_index(point.x, point.y) {
+                self.0[i..i + 3].copy_from_slice(&[color.r(), color.g(), color.b()]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 像素坐标对应的帧缓冲下标, 超出屏幕时返回 `None`
+fn pixel_index(x: i32, y: i32) -> Option<usize> {
+    if x < 0 || y < 0 || x as usize >= LCD_WIDTH || y as usize >= LCD_HEIGHT {
+        return None;
+    }
+    Some((y as usize * LCD_WIDTH + x as usize) * 3)
+}
+
+/// 点阵字体是否包含该字符
+fn is_mono_char(c: char) -> bool {
+    c == ' ' || c.is_ascii_graphic()
+}
+
+/// 字符显示宽度
+fn char_width(c: char) -> usize {
+    if is_mono_char(c) {
+        CHAR_WIDTH
+    } else {
+        WIDE_CHAR_WIDTH
+    }
+}
+
+/// 文字渲染器
+pub struct TextRenderer {
+    fallback: Option<fontdue::Font>,
+}
+
+impl TextRenderer {
+    /// 创建渲染器, 备用字体不存在时只用点阵字体
+    pub fn new() -> Self {
+        let fallback = std::fs::read(FALLBACK_FONT_PATH).ok().and_then(|data| {
+            fontdue::Font::from_bytes(data, fontdue::FontSettings::default())
+                .map_err(|e| log::warn!("Failed to load fallback font: {e}"))
+                .ok()
+        });
+        Self { fallback }
+    }
+
+    /// 在黑色背景上居中绘制文字, 超出宽度时自动换行
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - 240x240 RGB 帧数据
+    /// * `text` - 要显示的文字, `\n` 强制换行
+    /// * `color` - 文字颜色
+    pub fn render(&self, frame: &mut [u8], text: &str, color: Rgb<u8>) {
+        frame.fill(0);
+        let lines = wrap(text, LCD_WIDTH - MARGIN * 2);
+        let block_height = lines.len() * LINE_HEIGHT;
+        let top = LCD_HEIGHT.saturating_sub(block_height) / 2;
+        let rgb = Rgb888::new(color[0], color[1], color[2]);
+        let mut target = RgbFrame(frame);
+
+        for (row, line) in lines.iter().enumerate() {
+            let width: usize = line.chars().map(char_width).sum();
+            let mut x = (LCD_WIDTH.saturating_sub(width) / 2) as i32;
+            let y = (top + row * LINE_HEIGHT) as i32;
+            for c in line.chars() {
+                self.draw_char(&mut target, c, Point::new(x, y), rgb);
+                x += char_width(c) as i32;
+            }
+        }
+    }
+
+    /// 在 `origin` (字符左上角) 处绘制一个字符
+    fn draw_char(&self, target: &mut RgbFrame, c: char, origin: Point, color: Rgb888) {
+        if is_mono_char(c) {
+            let style = MonoTextStyle::new(&FONT_10X20, color);
+            let mut buf = [0u8; 4];
+            let _ = Text::with_baseline(c.encode_utf8(&mut buf), origin, style, Baseline::Top)
+                .draw(target);
+            return;
+        }
+
+        match &self.fallback {
+            Some(font) if font.lookup_glyph_index(c) != 0 => {
+                draw_fallback_glyph(target, font, c, origin, color)
+            }
+            _ => {
+                // 缺字占位方框
+                let _ = Rectangle::new(origin + Point::new(2, 2), Size::new(16, 16))
+                    .into_styled(PrimitiveStyle::with_stroke(color, 1))
+                    .draw(target);
+            }
+        }
+    }
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 用备用字体光栅化字符, 按覆盖率与背景混合
+fn draw_fallback_glyph(
+    target: &mut RgbFrame,
+    font: &fontdue::Font,
+    c: char,
+    origin: Point,
+    color: Rgb888,
+) {
+    let px = WIDE_CHAR_WIDTH as f32;
+    let (metrics, coverage) = font.rasterize(c, px);
+    let ascent = font
+        .horizontal_line_metrics(px)
+        .map(|m| m.ascent)
+        .unwrap_or(px * 0.8);
+    let left = origin.x + metrics.xmin;
+    let top = origin.y + (ascent as i32) - metrics.ymin - metrics.height as i32;
+
+    for (i, &alpha) in coverage.iter().enumerate() {
+        if alpha == 0 {
+            continue;
+        }
+        let x = left + (i % metrics.width) as i32;
+        let y = top + (i / metrics.width) as i32;
+        if let Some(idx) = pixel_index(x, y) {
+            for (channel, value) in [color.r(), color.g(), color.b()].into_iter().enumerate() {
+                let bg = target.0[idx + channel] as u32;
+                let blended = (value as u32 * alpha as u32 + bg * (255 - alpha as u32)) / 255;
+                target.0[idx + channel] = blended as u8;
+            }
+        }
+    }
+}
+
+/// 按显示宽度换行: 英文按单词换行, 宽字符可在任意位置换行, 过长的单词强制截断
+fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for token in tokens(paragraph) {
+            let token_width: usize = token.chars().map(char_width).sum();
+            if token == " " && line.is_empty() {
+                continue;
+            }
+            if line_width + token_width > max_width && !line.is_empty() {
+                lines.push(line.trim_end().to_string());
+                line.clear();
+                line_width = 0;
+                if token == " " {
+                    continue;
+                }
+            }
+            for c in token.chars() {
+                if line_width + char_width(c) > max_width && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += char_width(c);
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// 切分为可换行的片段: 连续的 ASCII 可见字符、单个空白 (统一为空格)、单个宽字符
+fn tokens(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_ascii_graphic() {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            tokens.push(&text[start..i]);
+        }
+        if c.is_whitespace() {
+            tokens.push(" ");
+        } else {
+            tokens.push(&text[i..i + c.len_utf8()]);
+        }
+    }
+    if let Some(start) = word_start {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
@@ -0,0 +1,261 @@
+//! 传输层抽象
+//!
+//! USB 批量传输和 USB CDC 串口两种后端共用同一套帧协议, 通信线程只依赖 [Transport].
+//! 另有不访问硬件的 [SimTransport], 用于没有设备时调试界面和动画
+
+use super::cdc::CdcTransport;
+use super::{DeviceInfo, JointConfig, Robot, UsbIds, SERVO_COUNT};
+use serde::{Deserialize, Serialize};
+
+/// 一帧分几轮发送
+pub const ROUND_COUNT: usize = 4;
+/// 每轮发送的像素字节数 (60 行)
+pub const BYTES_PER_ROUND: usize = super::lcd::FRAME_SIZE / ROUND_COUNT;
+/// 每轮结尾包大小: 像素尾部 + 32 字节关节配置
+pub const TAIL_SIZE: usize = 224;
+/// 结尾包中的像素字节数
+pub const PIXELS_IN_TAIL: usize = TAIL_SIZE - 32;
+/// 每轮发送的总字节数: 60 行像素加 32 字节关节配置 (43232), 不是整帧大小
+pub const ROUND_PACKET_SIZE: usize = BYTES_PER_ROUND - PIXELS_IN_TAIL + TAIL_SIZE;
+/// 设备回包大小
+pub const RESPONSE_SIZE: usize = 32;
+
+// 整帧必须正好分成整数轮, 否则按轮切分会丢掉尾部像素
+const _: () = assert!(BYTES_PER_ROUND * ROUND_COUNT == super::lcd::FRAME_SIZE);
+const _: () = assert!(PixelFormat::Rgb565.frame_size().is_multiple_of(ROUND_COUNT));
+const _: () = assert!(PixelFormat::Rgb888.round_packet_size() == ROUND_PACKET_SIZE);
+
+/// 发送给设备的像素格式
+///
+/// 像素按行从左到右、从上到下排列:
+///
+/// - `rgb888`: 每像素 3 字节, 依次为 R、G、B, 整帧 172800 字节
+/// - `rgb565`: 每像素 2 字节, 高字节在前, 位布局为 `RRRRRGGG GGGBBBBB`, 整帧 115200 字节
+///
+/// 两种格式每轮都发送 60 行像素, 之后是 32 字节关节配置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PixelFormat {
+    #[default]
+    Rgb888,
+    Rgb565,
+}
+
+#[allow(dead_code)]
+impl PixelFormat {
+    /// 每像素字节数
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb888 => 3,
+            PixelFormat::Rgb565 => 2,
+        }
+    }
+
+    /// 整帧字节数
+    pub const fn frame_size(self) -> usize {
+        super::lcd::LCD_WIDTH * super::lcd::LCD_HEIGHT * self.bytes_per_pixel()
+    }
+
+    /// 每轮发送的像素字节数
+    pub const fn bytes_per_round(self) -> usize {
+        self.frame_size() / ROUND_COUNT
+    }
+
+    /// 每轮发送的总字节数: 像素加关节配置
+    pub const fn round_packet_size(self) -> usize {
+        self.bytes_per_round() + (TAIL_SIZE - PIXELS_IN_TAIL)
+    }
+
+    /// 把 RGB888 像素按本格式写入 `out`, 会先清空 `out`
+    pub fn pack(self, rgb: &[u8], out: &mut Vec<u8>) {
+        out.clear();
+        match self {
+            PixelFormat::Rgb888 => out.extend_from_slice(rgb),
+            PixelFormat::Rgb565 => {
+                out.reserve(rgb.len() / 3 * 2);
+                for pixel in rgb.chunks_exact(3) {
+                    let (r, g, b) = (pixel[0] as u16, pixel[1] as u16, pixel[2] as u16);
+                    let value = (r >> 3) << 11 | (g >> 2) << 5 | b >> 3;
+                    out.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// 设备回传的舵机角度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Feedback {
+    pub angles: [f32; SERVO_COUNT],
+    /// 本帧是否收到完整回包, 为 false 时 `angles` 是上一次收到的角度
+    pub feedback_valid: bool,
+}
+
+/// 传输后端类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// USB 批量传输
+    #[default]
+    Usb,
+    /// USB CDC 虚拟串口
+    Cdc,
+    /// 模拟设备, 不访问硬件
+    Sim,
+}
+
+/// 打开传输后端所需的参数
+#[derive(Debug, Clone)]
+pub struct TransportConfig {
+    pub kind: TransportKind,
+    /// 设备 VID/PID
+    pub ids: UsbIds,
+    /// CDC 串口名, 为空时按 VID/PID 自动查找
+    pub cdc_port: String,
+    /// CDC 串口波特率
+    pub baud_rate: u32,
+    /// 像素格式, USB 后端只支持 RGB888
+    pub pixel_format: PixelFormat,
+}
+
+/// 帧传输后端
+pub trait Transport: Send {
+    /// 发送一帧数据, 返回设备回传的舵机实际角度, 后端拿不到回包时返回 `None`
+    fn send_frame(
+        &mut self,
+        pixels: &[u8],
+        config: &JointConfig,
+    ) -> anyhow::Result<Option<Feedback>>;
+
+    /// 设备描述信息
+    fn device_info(&self) -> DeviceInfo {
+        DeviceInfo::default()
+    }
+}
+
+impl Transport for Robot {
+    fn send_frame(
+        &mut self,
+        pixels: &[u8],
+        config: &JointConfig,
+    ) -> anyhow::Result<Option<Feedback>> {
+        check_frame_size(pixels, PixelFormat::Rgb888)?;
+        Ok(Robot::send_frame(self, pixels, &config.as_bytes())?)
+    }
+
+    fn device_info(&self) -> DeviceInfo {
+        Robot::device_info(self)
+    }
+}
+
+/// 模拟设备: 只检查帧大小, 把发送的关节角度原样作为反馈返回
+pub struct SimTransport {
+    pub pixel_format: PixelFormat,
+}
+
+impl Transport for SimTransport {
+    fn send_frame(
+        &mut self,
+        pixels: &[u8],
+        config: &JointConfig,
+    ) -> anyhow::Result<Option<Feedback>> {
+        check_frame_size(pixels, self.pixel_format)?;
+        Ok(Some(Feedback {
+            angles: config.angles,
+            feedback_valid: true,
+        }))
+    }
+
+    fn device_info(&self) -> DeviceInfo {
+        DeviceInfo {
+            manufacturer: "ElectronBot".to_string(),
+            product: "模拟设备".to_string(),
+            serial: "SIM".to_string(),
+        }
+    }
+}
+
+/// 检查像素数据是否正好是一整帧, 各后端按固定偏移切分, 长度不对时会越界或发送残缺画面
+pub fn check_frame_size(pixels: &[u8], format: PixelFormat) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        pixels.len() == format.frame_size(),
+        "Frame must be {} bytes ({}x{} {:?}), got {}",
+        format.frame_size(),
+        super::lcd::LCD_WIDTH,
+        super::lcd::LCD_HEIGHT,
+        format,
+        pixels.len()
+    );
+    Ok(())
+}
+
+/// 按配置打开传输后端
+pub fn open_transport(config: &TransportConfig) -> anyhow::Result<Box<dyn Transport>> {
+    match config.kind {
+        TransportKind::Usb => {
+            // USB 后端按 RGB888 的每轮字节数切分发送
+            anyhow::ensure!(
+                config.pixel_format == PixelFormat::Rgb888,
+                "USB transport only supports rgb888 pixel format"
+            );
+            Ok(Box::new(Robot::open(config.ids)?))
+        }
+        TransportKind::Cdc => Ok(Box::new(CdcTransport::open(
+            &config.cdc_port,
+            config.ids,
+            config.baud_rate,
+            config.pixel_format,
+        )?)),
+        TransportKind::Sim => Ok(Box::new(SimTransport {
+            pixel_format: config.pixel_format,
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::robot::lcd::FRAME_SIZE;
+
+    #[test]
+    fn full_frames_pass_size_check() {
+        assert!(check_frame_size(&vec![0; FRAME_SIZE], PixelFormat::Rgb888).is_ok());
+        assert!(check_frame_size(&vec![0; 115_200], PixelFormat::Rgb565).is_ok());
+    }
+
+    #[test]
+    fn off_by_one_frames_are_rejected() {
+        for format in [PixelFormat::Rgb888, PixelFormat::Rgb565] {
+            let size = format.frame_size();
+            assert!(check_frame_size(&vec![0; size - 1], format).is_err());
+            assert!(check_frame_size(&vec![0; size + 1], format).is_err());
+        }
+        // 每轮的数据包大小不是整帧
+        assert!(check_frame_size(&vec![0; ROUND_PACKET_SIZE], PixelFormat::Rgb888).is_err());
+    }
+
+    #[test]
+    fn round_packets_cover_full_frame() {
+        assert_eq!(FRAME_SIZE, 172_800);
+        assert_eq!(ROUND_PACKET_SIZE, 43_232);
+        // 每轮附带 32 字节关节配置
+        assert_eq!(
+            ROUND_COUNT * ROUND_PACKET_SIZE,
+            FRAME_SIZE + ROUND_COUNT * 32
+        );
+        assert_eq!(
+            ROUND_COUNT * PixelFormat::Rgb565.round_packet_size(),
+            PixelFormat::Rgb565.frame_size() + ROUND_COUNT * 32
+        );
+    }
+
+    #[test]
+    fn full_frame_passes_send_path() {
+        let mut sim = SimTransport {
+            pixel_format: PixelFormat::Rgb888,
+        };
+        let config = JointConfig::default();
+        assert!(sim.send_frame(&vec![0; FRAME_SIZE], &config).is_ok());
+        assert!(sim.send_frame(&vec![0; FRAME_SIZE - 1], &config).is_err());
+    }
+}
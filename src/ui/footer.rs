@@ -0,0 +1,45 @@
+//! 底部状态栏: 当前模式的按键提示、连接状态和发送帧率
+
+use crate::app::{App, ConnectionState};
+use crate::input;
+use crate::ui_components::Theme;
+use ratatui::{prelude::*, widgets::Paragraph};
+
+/// 状态栏高度, 与按键提示行数相同
+pub fn height(app: &App) -> u16 {
+    input::key_hints(app).len() as u16
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let (status, status_color) = match app.connection_state() {
+        ConnectionState::Connected => (
+            format!(" ● 已连接 {:.1} fps ", app.send_rate.rate()),
+            theme.success,
+        ),
+        ConnectionState::Connecting => (" ◌ 连接中... ".to_string(), theme.warning),
+        ConnectionState::Disconnected => (" ○ 未连接 ".to_string(), theme.muted),
+    };
+
+    let chunks = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Min(0),
+            Constraint::Length(Span::raw(&status).width() as u16),
+        ],
+    )
+    .split(area);
+
+    let hints: Vec<Line> = input::key_hints(app)
+        .iter()
+        .map(|line| Line::styled(format!(" {line}"), Style::new().fg(theme.text)))
+        .collect();
+    frame.render_widget(
+        Paragraph::new(hints).style(Style::new().bg(theme.panel_bg)),
+        chunks[0],
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(status, Style::new().fg(status_color).bold()))
+            .style(Style::new().bg(theme.panel_bg)),
+        chunks[1],
+    );
+}
@@ -1,47 +1,58 @@
+mod footer;
 mod pages;
 mod sidebar;
 
 use crate::app::{App, MenuItem};
-use crate::ui_components::PopupWidget;
+use crate::ui_components::{LogPopupWidget, PopupWidget, SelectPopupWidget, Theme};
 use ratatui::prelude::*;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
+    let theme = Theme::preset(app.config.theme);
+    let rows = Layout::new(
+        Direction::Vertical,
+        [Constraint::Min(0), Constraint::Length(footer::height(app))],
+    )
+    .split(frame.area());
     let chunks = Layout::new(
         Direction::Horizontal,
         [Constraint::Length(20), Constraint::Min(0)],
     )
-    .split(frame.area());
+    .split(rows[0]);
 
     // 渲染侧边栏，传入焦点状态
-    sidebar::render(frame, chunks[0], &mut app.menu_state, app.left_focused);
+    app.mouse_areas.menu = sidebar::render(
+        frame,
+        chunks[0],
+        &mut app.menu_state,
+        app.left_focused,
+        &theme,
+    );
 
     // 根据焦点状态选择右侧内容的边框颜色
-    let right_border_color = if app.left_focused {
-        Color::LightBlue
-    } else {
-        Color::Green
-    };
+    let right_border_color = theme.border_color(!app.left_focused);
 
     match app.selected_menu {
         MenuItem::DeviceStatus => {
-            pages::device_status::render(frame, chunks[1], app, right_border_color)
+            pages::device_status::render(frame, chunks[1], app, &theme, right_border_color)
         }
         MenuItem::DeviceControl => {
-            pages::device_control::render(frame, chunks[1], app, right_border_color)
+            app.mouse_areas.gauge_bars =
+                pages::device_control::render(frame, chunks[1], app, &theme, right_border_color)
         }
-        MenuItem::Settings => pages::settings::render(
-            frame,
-            chunks[1],
-            app.settings_selected,
-            &app.config,
-            app.in_edit_settings_mode,
-            &app.edit_buffer,
-            right_border_color,
-        ),
-        MenuItem::About => pages::about::render(frame, chunks[1], right_border_color),
+        MenuItem::Settings => {
+            pages::settings::render(frame, chunks[1], app, &theme, right_border_color)
+        }
+        MenuItem::Log => pages::log_view::render(frame, chunks[1], app, &theme, right_border_color),
+        MenuItem::About => pages::about::render(frame, chunks[1], &theme, right_border_color),
     }
 
+    footer::render(frame, rows[1], app, &theme);
+
     // 渲染弹窗
+    let mut log_popup_widget = LogPopupWidget::new();
+    log_popup_widget.render(frame, frame.area(), &app.log_popup, &app.log_queue, &theme);
     let mut popup_widget = PopupWidget::new();
     popup_widget.render(frame, frame.area(), &mut app.popup);
+    let mut select_popup_widget = SelectPopupWidget::new();
+    select_popup_widget.render(frame, frame.area(), &mut app.select_popup, &theme);
 }
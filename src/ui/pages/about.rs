@@ -1,11 +1,11 @@
-use crate::ui_components::create_block;
+use crate::ui_components::{create_block, Theme};
 use ratatui::{prelude::*, widgets::Paragraph};
 
 fn get_app_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
-pub fn render(frame: &mut Frame, area: Rect, border_color: Color) {
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, border_color: Color) {
     let version = get_app_version();
 
     let text = vec![
@@ -17,23 +17,28 @@ pub fn render(frame: &mut Frame, area: Rect, border_color: Color) {
         Line::raw(""),
         Line::from_iter([Span::styled(
             format!("  版本: {version}"),
-            Style::new().fg(Color::White),
+            Style::new().fg(theme.text),
         )]),
         Line::raw("  作者: TOTHTOT"),
         Line::raw(""),
         Line::from_iter([Span::styled(
             "  ElectronBot 是一个桌面小机器人",
-            Style::new().fg(Color::Gray),
+            Style::new().fg(theme.secondary),
         )]),
         Line::from_iter([Span::styled(
             "  本程序用于控制和配置设备",
-            Style::new().fg(Color::Gray),
+            Style::new().fg(theme.secondary),
         )]),
         Line::raw(""),
         Line::raw("  快捷键:"),
         Line::raw("    Enter   进入/切换焦点"),
         Line::raw("    ↑/↓    选择菜单/设置项"),
+        Line::raw("    1~5     跳转到设备状态/设备控制/设置/日志/关于"),
         Line::raw("    ←/→    调整舵机角度"),
+        Line::raw("    Ctrl+T  切换语音监听"),
+        Line::raw("    Ctrl+U  麦克风静音/取消静音"),
+        Line::raw("    Ctrl+W  开始/停止麦克风录音"),
+        Line::raw("    Ctrl+L  显示/隐藏日志"),
         Line::raw("    Esc/q   退出"),
     ];
     let outer_block = create_block("关于".to_string(), border_color, border_color);
@@ -1,9 +1,17 @@
+use crate::app::preview::{LcdPreview, PREVIEW_SIZE};
 use crate::app::App;
-use crate::robot::{ServoState, SERVO_COUNT};
-use crate::ui_components::{create_block, get_indicator};
+use crate::robot::{lcd, ServoState, SERVO_COUNT};
+use crate::ui_components::{create_block, get_indicator, Theme};
 use ratatui::{prelude::*, widgets::Paragraph};
 
-pub fn render(frame: &mut Frame, area: Rect, app: &App, border_color: Color) {
+/// 渲染设备控制页, 返回各舵机进度条区域供鼠标拖动定位
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    app: &mut App,
+    theme: &Theme,
+    border_color: Color,
+) -> [Rect; SERVO_COUNT] {
     let outer_block = create_block("设备控制".to_string(), border_color, border_color);
 
     let inner_area = outer_block.inner(area);
@@ -11,29 +19,213 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, border_color: Color) {
 
     let chunks = Layout::new(
         Direction::Vertical,
-        [Constraint::Length(3), Constraint::Min(0)],
+        [
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ],
     )
     .split(inner_area);
 
-    render_info_bar(frame, chunks[0], border_color);
-    render_joint_gauges(frame, chunks[1], app, border_color);
+    // 预览区宽度: 每个预览像素一列, 加左右边框
+    let body = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Min(0),
+            Constraint::Length(PREVIEW_SIZE as u16 + 2),
+        ],
+    )
+    .split(chunks[2]);
+
+    render_info_bar(frame, chunks[0], app, theme, border_color);
+    render_eyes_bar(frame, chunks[1], app, theme, border_color);
+    let bars = render_joint_gauges(frame, body[0], app, theme, border_color);
+    render_lcd_preview(frame, body[1], app, theme, border_color);
+    bars
+}
+
+/// ASCII 预览的灰度字符, 从暗到亮
+const ASCII_SHADES: [char; 4] = [' ', '.', ':', '#'];
+
+/// 显示当前屏幕内容, 与连接后发送给设备的画面相同
+///
+/// 字符单元高约为宽的两倍, 每个字符对应上下两个像素以保持宽高比:
+/// 真彩色模式用 ▀ 的前景色/背景色分别显示两个像素, ASCII 模式取两者平均亮度
+fn render_lcd_preview(
+    frame: &mut Frame,
+    area: Rect,
+    app: &mut App,
+    theme: &Theme,
+    border_color: Color,
+) {
+    let outer_block = create_block("屏幕预览".to_string(), border_color, border_color);
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let preview = &app.lcd_preview;
+    if preview.is_empty() {
+        let widget = Paragraph::new("等待第一帧").style(Style::new().fg(theme.muted));
+        frame.render_widget(widget, inner_area);
+        return;
+    }
+
+    // 帧、显示模式和区域都不变时复用上次生成的文本
+    let color = app.config.preview_color;
+    let key = (preview.hash(), color, inner_area);
+    let lines = app
+        .preview_cache
+        .get_or_build(key, || preview_lines(&app.lcd_preview, color));
+    frame.render_widget(Paragraph::new(lines.to_vec()), inner_area);
+}
+
+/// 生成预览文本, 每行对应上下两个预览像素
+fn preview_lines(preview: &LcdPreview, color: bool) -> Vec<Line<'static>> {
+    let rgb = |[r, g, b]: [u8; 3]| Color::Rgb(r, g, b);
+    (0..PREVIEW_SIZE / 2)
+        .map(|row| {
+            let (top, bottom) = (row * 2, row * 2 + 1);
+            if color {
+                Line::from_iter((0..PREVIEW_SIZE).map(|x| {
+                    let style = Style::new()
+                        .fg(rgb(preview.pixel(x, top)))
+                        .bg(rgb(preview.pixel(x, bottom)));
+                    Span::styled("▀", style)
+                }))
+            } else {
+                Line::raw(
+                    (0..PREVIEW_SIZE)
+                        .map(|x| {
+                            let luma = (preview.luma(x, top) as usize
+                                + preview.luma(x, bottom) as usize)
+                                / 2;
+                            ASCII_SHADES[luma * ASCII_SHADES.len() / 256]
+                        })
+                        .collect::<String>(),
+                )
+            }
+        })
+        .collect()
+}
+
+fn render_info_bar(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
+    let outer_block = create_block("状态".to_string(), border_color, border_color);
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    if app.in_pose_name_mode {
+        let text = vec![Line::from_iter([
+            Span::styled("姿势名称: ", Style::new().fg(theme.text)),
+            Span::styled(
+                format!("{}_", app.edit_buffer),
+                Style::new().fg(theme.input_fg).bg(theme.input_bg),
+            ),
+            Span::styled("  [Enter] 保存  [Esc] 取消", Style::new().fg(theme.text)),
+        ])];
+        let widget = Paragraph::new(text).style(Style::new().bg(theme.panel_bg));
+        frame.render_widget(widget, inner_area);
+        return;
+    }
+
+    if app.in_lcd_text_mode {
+        let text = vec![Line::from_iter([
+            Span::styled("屏幕文字: ", Style::new().fg(theme.text)),
+            Span::styled(
+                format!("{}_", app.edit_buffer),
+                Style::new().fg(theme.input_fg).bg(theme.input_bg),
+            ),
+            Span::styled("  [Enter] 显示  [Esc] 取消", Style::new().fg(theme.text)),
+        ])];
+        let widget = Paragraph::new(text).style(Style::new().bg(theme.panel_bg));
+        frame.render_widget(widget, inner_area);
+        return;
+    }
+
+    if app.in_angle_edit_mode {
+        let index = app.joint.selected();
+        let text = vec![Line::from_iter([
+            Span::styled(
+                format!("{} 角度: ", ServoState::name(index)),
+                Style::new().fg(theme.text),
+            ),
+            Span::styled(
+                format!("{}_", app.edit_buffer),
+                Style::new().fg(theme.input_fg).bg(theme.input_bg),
+            ),
+            Span::styled(
+                format!(
+                    "  范围 {}  [Enter] 确定  [Esc] 取消",
+                    ServoState::range_str(index)
+                ),
+                Style::new().fg(theme.text),
+            ),
+        ])];
+        let widget = Paragraph::new(text).style(Style::new().bg(theme.panel_bg));
+        frame.render_widget(widget, inner_area);
+        return;
+    }
+
+    let (torque, torque_color) = if app.idle {
+        ("空闲掉电", theme.warning)
+    } else if app.joint.is_enabled() {
+        ("使能", theme.success)
+    } else {
+        ("掉电", theme.error)
+    };
+    let motion_state = match (&app.motion_recorder, &app.motion_player) {
+        (Some(_), _) => Span::styled(" ● 录制中", Style::new().fg(theme.error).bold()),
+        (_, Some(player)) if player.looping => {
+            Span::styled(" ▶ 循环播放", Style::new().fg(theme.success).bold())
+        }
+        (_, Some(_)) => Span::styled(" ▶ 播放中", Style::new().fg(theme.success).bold()),
+        _ => Span::raw(""),
+    };
+    let text = vec![Line::from_iter([
+        Span::styled("舵机: ", Style::new().fg(theme.text)),
+        Span::styled(format!("[{torque}]"), Style::new().fg(torque_color).bold()),
+        motion_state,
+    ])];
+
+    let widget = Paragraph::new(text).style(Style::new().bg(theme.panel_bg));
+    frame.render_widget(widget, inner_area);
 }
 
-fn render_info_bar(frame: &mut Frame, area: Rect, border_color: Color) {
-    let outer_block = create_block("操作说明".to_string(), border_color, border_color);
+/// 表情和注视方向列表, 高亮当前选择
+fn render_eyes_bar(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
+    let outer_block = create_block("眼睛".to_string(), border_color, border_color);
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
-    let text = vec![Line::from_iter([Span::styled(
-        "操作: [↑] 上一舵机  [↓] 下一舵机  [←] -1°  [→] +1°  [s] 截图保存  [Esc] 返回",
-        Style::new().fg(Color::White),
-    )])];
+    let options_line = |label: &'static str, names: &[&'static str], current: &str| {
+        let mut spans = vec![Span::styled(label, Style::new().fg(theme.text))];
+        for name in names {
+            let style = if *name == current {
+                Style::new()
+                    .fg(theme.highlight_fg)
+                    .bg(theme.highlight)
+                    .bold()
+            } else {
+                Style::new().fg(theme.secondary)
+            };
+            spans.push(Span::styled(format!(" {name} "), style));
+        }
+        Line::from(spans)
+    };
+    let text = vec![
+        options_line("表情 [f]: ", &lcd::MOOD_NAMES, &app.eyes_mood),
+        options_line("注视 [k]: ", &lcd::POSITION_NAMES, &app.eyes_position),
+    ];
 
-    let widget = Paragraph::new(text).style(Style::new().bg(Color::DarkGray));
+    let widget = Paragraph::new(text).style(Style::new().bg(theme.panel_bg));
     frame.render_widget(widget, inner_area);
 }
 
-fn render_joint_gauges(frame: &mut Frame, area: Rect, app: &App, border_color: Color) {
+fn render_joint_gauges(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    theme: &Theme,
+    border_color: Color,
+) -> [Rect; SERVO_COUNT] {
     let outer_block = create_block("关节控制".to_string(), border_color, border_color);
 
     let servo_height = (area.height as usize) / SERVO_COUNT;
@@ -41,7 +233,8 @@ fn render_joint_gauges(frame: &mut Frame, area: Rect, app: &App, border_color: C
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
-    for i in 0..SERVO_COUNT {
+    let mut bars = [Rect::default(); SERVO_COUNT];
+    for (i, bar) in bars.iter_mut().enumerate() {
         let row_height = if i < extra_rows {
             servo_height + 1
         } else {
@@ -55,11 +248,19 @@ fn render_joint_gauges(frame: &mut Frame, area: Rect, app: &App, border_color: C
             row_height as u16,
         );
 
-        render_single_joint(frame, row_area, app, i);
+        *bar = render_single_joint(frame, row_area, app, theme, i);
     }
+    bars
 }
 
-fn render_single_joint(frame: &mut Frame, area: Rect, app: &App, index: usize) {
+/// 渲染单个舵机的进度条, 返回进度条填充区域
+fn render_single_joint(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    theme: &Theme,
+    index: usize,
+) -> Rect {
     let values = app.joint.values();
     let is_selected = index == app.joint.selected() && app.in_servo_mode;
     let value = values[index];
@@ -69,42 +270,69 @@ fn render_single_joint(frame: &mut Frame, area: Rect, app: &App, index: usize) {
     let indicator = get_indicator(is_selected, is_selected); // 选中时作为编辑状态显示 ▶
 
     let color = if is_selected && app.in_servo_mode {
-        Color::Cyan
+        theme.highlight
     } else {
-        Color::White
+        theme.text
     };
 
     // 计算进度条
-    let min = ServoState::min_angle(index);
-    let max = ServoState::max_angle(index);
-    let total_range = (max - min) as f32;
-    let value_offset = (value - min) as f32;
-    let percent = if total_range > 0.0 {
-        ((value_offset / total_range) * 100.0) as u16
-    } else {
-        0
-    };
+    let percent = ServoState::percent(index, value);
 
-    let bar_width = (area.width as usize).saturating_sub(35);
+    let bar_width = (area.width as usize).saturating_sub(48);
     let filled = percent * bar_width as u16 / 100;
     let empty = bar_width as u16 - filled;
 
     let bar = format!(
-        "▏{}▎",
-        "█".repeat(filled as usize) + &"░".repeat(empty as usize)
+        "▏{}{}▎",
+        "█".repeat(filled as usize),
+        "░".repeat(empty as usize)
     );
 
-    let text = vec![Line::from_iter([
+    let prefix = [
         Span::styled(
             indicator.to_string(),
             Style::new().fg(color).add_modifier(Modifier::BOLD),
         ),
         Span::styled(format!(" {name}:"), Style::new().fg(color)),
+    ];
+    // 进度条填充区域从 ▏ 之后开始
+    let prefix_width: usize = prefix.iter().map(Span::width).sum::<usize>() + 1;
+    let bar_area = Rect::new(
+        area.x + prefix_width as u16,
+        area.y,
+        bar_width as u16,
+        area.height.min(1),
+    )
+    .intersection(area);
+
+    let text = vec![Line::from_iter(prefix.into_iter().chain([
         Span::styled(bar, Style::new().fg(color)),
         Span::styled(format!(" {value}°"), Style::new().fg(color)),
-        Span::styled(format!(" [{range_str}]"), Style::new().fg(Color::DarkGray)),
-    ])];
+        feedback_span(app.feedback, app.feedback_valid, index, theme),
+        Span::styled(format!(" [{range_str}]"), Style::new().fg(theme.muted)),
+    ]))];
 
-    let widget = Paragraph::new(text).style(Style::new().fg(Color::White));
+    let widget = Paragraph::new(text).style(Style::new().fg(theme.text));
     frame.render_widget(widget, area);
+    bar_area
+}
+
+/// 实际角度显示, 无反馈、回包过期或舵机未上电 (全部为 0) 时灰显
+fn feedback_span(
+    feedback: Option<[f32; SERVO_COUNT]>,
+    valid: bool,
+    index: usize,
+    theme: &Theme,
+) -> Span<'static> {
+    match feedback {
+        Some(angles) if valid && angles.iter().any(|&a| a != 0.0) => Span::styled(
+            format!(" 实际角度 {:>6.1}°", angles[index]),
+            Style::new().fg(theme.success),
+        ),
+        Some(angles) => Span::styled(
+            format!(" 实际角度 {:>6.1}°", angles[index]),
+            Style::new().fg(theme.muted),
+        ),
+        None => Span::styled(" 实际角度     --", Style::new().fg(theme.muted)),
+    }
 }
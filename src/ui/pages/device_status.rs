@@ -1,87 +1,307 @@
-use crate::app::App;
-use crate::ui_components::create_block;
+use crate::app::{App, ConnectionState};
+use crate::robot::TransportKind;
+use crate::ui_components::{create_block, Theme};
 use ratatui::{prelude::*, widgets::*};
 
-fn get_pc_battery() -> u32 {
-    85
-}
-
-fn get_network_status() -> &'static str {
-    "已连接"
-}
-
-fn status_color(ok: bool) -> Color {
+fn status_color(ok: bool, theme: &Theme) -> Color {
     if ok {
-        Color::Green
+        theme.success
     } else {
-        Color::Red
+        theme.error
     }
 }
 
-pub fn render(frame: &mut Frame, area: Rect, app: &App, border_color: Color) {
+/// 音量条最大宽度 (字符)
+const VOLUME_BAR_MAX_WIDTH: usize = 20;
+
+/// 生成音量条: 平滑音量为实心部分, 峰值位置显示标记
+///
+/// # Arguments
+///
+/// * `volume` - 平滑音量, 超出 0~100 时截断
+/// * `peak` - 峰值音量, 超出 0~100 时截断
+/// * `width` - 音量条宽度 (字符), 不超过所在列的宽度
+fn volume_bar(volume: i32, peak: i32, width: usize) -> String {
+    let volume = volume.clamp(0, 100);
+    let peak = peak.clamp(0, 100);
+    let filled = volume as usize * width / 100;
+    let peak_idx = (peak as usize * width / 100).saturating_sub(1);
+    (0..width)
+        .map(|i| {
+            if i < filled {
+                '│'
+            } else if i == peak_idx && peak > volume {
+                '┃'
+            } else {
+                '─'
+            }
+        })
+        .collect()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
     let is_connected = app.is_connected();
-    let volume = app.voice_manager.as_ref().map(|v| v.volume()).unwrap_or(0);
+    let volume = app
+        .voice_manager
+        .as_ref()
+        .map(|v| v.volume_smoothed())
+        .unwrap_or(0);
+    let peak = app.voice_manager.as_ref().map(|v| v.peak()).unwrap_or(0);
+    let mic_enabled = app
+        .voice_manager
+        .as_ref()
+        .map(|v| v.is_enabled())
+        .unwrap_or(false);
+    let listening = app
+        .voice_manager
+        .as_ref()
+        .map(|v| v.is_listening())
+        .unwrap_or(false);
+    let muted = app
+        .voice_manager
+        .as_ref()
+        .map(|v| v.is_muted())
+        .unwrap_or(false);
+    let recording = app
+        .voice_manager
+        .as_ref()
+        .map(|v| v.is_recording())
+        .unwrap_or(false);
+    // 中间结果灰色显示, 最终结果白色显示
+    let (transcript, transcript_color) = match &app.transcript {
+        Some(event) if event.is_final => (event.text.as_str(), theme.text),
+        Some(event) => (event.text.as_str(), theme.secondary),
+        None => ("", theme.secondary),
+    };
+
+    // 两列等宽, 中间间隔 2 列
+    let inner = area.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let bar_width = ((inner.width.saturating_sub(2) / 2) as usize).min(VOLUME_BAR_MAX_WIDTH);
 
     // 使用 Table 实现网格布局
     let table = Table::new(
         vec![
             Row::new(vec![
-                Cell::from(Span::styled("连接状态", Style::new().fg(Color::Yellow))),
+                Cell::from(Span::styled("连接状态", Style::new().fg(theme.label))),
                 Cell::from(Span::styled(
-                    if is_connected {
-                        "已连接"
-                    } else {
-                        "未连接"
+                    match (is_connected, app.config.transport == TransportKind::Sim) {
+                        (true, true) => "已连接 (模拟设备)",
+                        (true, false) => "已连接",
+                        (false, true) => "未连接 (模拟模式)",
+                        (false, false) => "未连接",
                     },
-                    Style::new().fg(status_color(is_connected)).bold(),
+                    Style::new().fg(status_color(is_connected, theme)).bold(),
                 )),
             ]),
             Row::new(vec![
-                Cell::from(Span::styled("上位机电量", Style::new().fg(Color::Yellow))),
+                Cell::from(Span::styled("舵机状态", Style::new().fg(theme.label))),
+                match (is_connected, app.idle) {
+                    (false, _) => Cell::from(Span::styled("--", Style::new().fg(theme.muted))),
+                    (true, true) => Cell::from(Span::styled(
+                        "空闲掉电 (按任意键恢复)",
+                        Style::new().fg(theme.warning),
+                    )),
+                    (true, false) => Cell::from(Span::styled(
+                        if app.joint.is_enabled() {
+                            "使能"
+                        } else {
+                            "掉电"
+                        },
+                        Style::new().fg(status_color(app.joint.is_enabled(), theme)),
+                    )),
+                },
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("设备信息", Style::new().fg(theme.label))),
+                Cell::from(Span::styled(
+                    match app.device_info() {
+                        Some(info) => format!(
+                            "{} {} (SN: {})",
+                            info.manufacturer, info.product, info.serial
+                        ),
+                        None => "--".to_string(),
+                    },
+                    Style::new().fg(theme.secondary),
+                )),
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("发送帧率", Style::new().fg(theme.label))),
+                Cell::from(Span::styled(
+                    format!(
+                        "{:.1} fps (间隔 {}ms, 发送 {} / 跳过 {})",
+                        app.send_rate.rate(),
+                        app.config.frame_interval_ms,
+                        app.frames_sent,
+                        app.frames_skipped
+                    ),
+                    Style::new().fg(theme.accent),
+                )),
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("设备吞吐", Style::new().fg(theme.label))),
                 Cell::from(Span::styled(
-                    format!("{}%", get_pc_battery()),
-                    Style::new().fg(status_color(get_pc_battery() > 50)),
+                    if is_connected {
+                        format!(
+                            "{:.1} FPS / {:.1} KB/s (失败 {})",
+                            app.comm_rate.fps, app.comm_rate.kbps, app.comm_rate.failures
+                        )
+                    } else {
+                        "--".to_string()
+                    },
+                    Style::new().fg(theme.accent),
                 )),
             ]),
             Row::new(vec![
-                Cell::from(Span::styled("网络状态", Style::new().fg(Color::Yellow))),
+                Cell::from(Span::styled("帧队列", Style::new().fg(theme.label))),
                 Cell::from(Span::styled(
-                    get_network_status(),
-                    Style::new().fg(status_color(get_network_status() == "已连接")),
+                    match app.frame_queue_stats() {
+                        Some(stats) => format!(
+                            "{}/{} ({}, 丢弃 {})",
+                            stats.len,
+                            stats.capacity,
+                            stats.policy.label(),
+                            stats.dropped
+                        ),
+                        None => "--".to_string(),
+                    },
+                    Style::new().fg(theme.accent),
                 )),
             ]),
             Row::new(vec![
-                Cell::from(Span::styled("输入音量", Style::new().fg(Color::Yellow))),
+                Cell::from(Span::styled("上位机电量", Style::new().fg(theme.label))),
+                match app.system_status.battery {
+                    Some(battery) => Cell::from(Span::styled(
+                        format!("{battery}%"),
+                        Style::new().fg(status_color(battery > 50, theme)),
+                    )),
+                    None => Cell::from(Span::styled("未知", Style::new().fg(theme.muted))),
+                },
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("网络状态", Style::new().fg(theme.label))),
+                match app.system_status.network {
+                    Some(online) => Cell::from(Span::styled(
+                        if online { "已连接" } else { "未连接" },
+                        Style::new().fg(status_color(online, theme)),
+                    )),
+                    None => Cell::from(Span::styled("未知", Style::new().fg(theme.muted))),
+                },
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("输入音量", Style::new().fg(theme.label))),
                 // 音量条
+                if muted {
+                    Cell::from(Span::styled(
+                        "🔇 已静音 [Ctrl+U]",
+                        Style::new().fg(theme.error),
+                    ))
+                } else {
+                    Cell::from(Span::styled(
+                        volume_bar(volume, peak, bar_width),
+                        Style::new().fg(theme.accent),
+                    ))
+                },
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("语音监听", Style::new().fg(theme.label))),
                 Cell::from(Span::styled(
-                    format!("{:─<20}", "│".repeat((volume / 5) as usize)),
-                    Style::new().fg(Color::Cyan),
+                    if !mic_enabled {
+                        "麦克风未连接"
+                    } else if listening {
+                        "监听中 [Ctrl+T]"
+                    } else {
+                        "已暂停 [Ctrl+T]"
+                    },
+                    Style::new().fg(status_color(mic_enabled && listening, theme)),
                 )),
             ]),
             Row::new(vec![
+                Cell::from(Span::styled("麦克风录音", Style::new().fg(theme.label))),
                 Cell::from(Span::styled(
-                    "按 [Enter] 连接设备",
-                    Style::new().fg(Color::Gray),
+                    if recording {
+                        "● 录音中 [Ctrl+W]"
+                    } else {
+                        "未录音 [Ctrl+W]"
+                    },
+                    Style::new().fg(if recording {
+                        theme.error
+                    } else {
+                        theme.secondary
+                    }),
                 )),
+            ]),
+            Row::new(vec![
+                Cell::from(Span::styled("识别文本", Style::new().fg(theme.label))),
+                Cell::from(Span::styled(transcript, Style::new().fg(transcript_color))),
+            ]),
+            Row::new(vec![
+                match app.connection_state() {
+                    ConnectionState::Disconnected => Cell::from(Span::styled(
+                        "按 [Enter] 连接设备",
+                        Style::new().fg(theme.secondary),
+                    )),
+                    ConnectionState::Connecting => Cell::from(Span::styled(
+                        "正在连接设备...",
+                        Style::new().fg(theme.warning),
+                    )),
+                    ConnectionState::Connected => Cell::from(Span::styled(
+                        "按 [Enter] 断开设备",
+                        Style::new().fg(theme.success),
+                    )),
+                },
                 Cell::from(Span::styled(
                     format!("{}", volume),
-                    Style::new().fg(Color::Cyan),
+                    Style::new().fg(theme.accent),
                 )),
             ]),
         ],
         &[Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)],
     )
     .column_spacing(2);
-    let outer_block = create_block("操作说明".to_string(), border_color, border_color);
+    let outer_block = create_block("设备状态".to_string(), border_color, border_color);
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
     let widget = Paragraph::new(Line::raw("")).alignment(Alignment::Left);
     frame.render_widget(widget, inner_area);
 
-    let inner = area.inner(Margin {
-        horizontal: 1,
-        vertical: 1,
-    });
     frame.render_widget(table, inner);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 音量条的宽度和实心部分长度
+    fn filled(volume: i32, width: usize) -> (usize, usize) {
+        let bar = volume_bar(volume, 0, width);
+        (
+            bar.chars().count(),
+            bar.chars().filter(|&c| c == '│').count(),
+        )
+    }
+
+    #[test]
+    fn volume_bar_fill() {
+        assert_eq!(filled(0, 20), (20, 0));
+        assert_eq!(filled(50, 20), (20, 10));
+        assert_eq!(filled(100, 20), (20, 20));
+        assert_eq!(filled(50, 7), (7, 3));
+    }
+
+    #[test]
+    fn volume_bar_clamps_out_of_range() {
+        assert_eq!(filled(150, 20), (20, 20));
+        assert_eq!(filled(-5, 20), (20, 0));
+        assert_eq!(volume_bar(0, 150, 10).chars().last(), Some('┃'));
+        assert!(!volume_bar(0, -5, 10).contains('┃'));
+    }
+
+    #[test]
+    fn volume_bar_zero_width() {
+        assert_eq!(volume_bar(100, 100, 0), "");
+    }
+}
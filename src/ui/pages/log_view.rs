@@ -0,0 +1,77 @@
+use crate::app::log_queue::LogLevel;
+use crate::app::App;
+use crate::ui_components::{create_block, log_level_color, Theme};
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
+    let outer_block = create_block("日志".to_string(), border_color, border_color);
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    render_entries(frame, inner_area, app, theme, border_color);
+}
+
+/// 按时间顺序显示日志, 默认停在最新一条, 向上滚动查看旧日志
+fn render_entries(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
+    let entries: Vec<_> = app
+        .log_queue
+        .entries()
+        .filter(|entry| entry.level >= app.log_view.min_level)
+        .collect();
+    let filter = match app.log_view.min_level {
+        LogLevel::Info => "全部",
+        LogLevel::Warn => "警告及以上",
+        LogLevel::Error => "仅错误",
+    };
+    let title = format!(
+        "记录 ({}/{}) [{filter}]",
+        entries.len(),
+        app.log_queue.len()
+    );
+    let outer_block = create_block(title, border_color, border_color);
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    if entries.is_empty() {
+        let widget = Paragraph::new("暂无日志").style(Style::new().fg(theme.muted));
+        frame.render_widget(widget, inner_area);
+        return;
+    }
+
+    let height = inner_area.height as usize;
+    let scroll = app.log_view.scroll.min(entries.len().saturating_sub(1));
+    let end = entries.len() - scroll;
+    let start = end.saturating_sub(height);
+
+    let lines: Vec<Line> = entries[start..end]
+        .iter()
+        .map(|entry| {
+            let color = log_level_color(entry.level, theme);
+            Line::from_iter([
+                Span::styled(
+                    format!("{} ", entry.timestamp.format("%H:%M:%S")),
+                    Style::new().fg(theme.muted),
+                ),
+                Span::styled(
+                    format!("{:<5} ", entry.level.label()),
+                    Style::new().fg(color).bold(),
+                ),
+                Span::styled(entry.with_count(), Style::new().fg(color)),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner_area);
+
+    // 内容超过一屏时显示滚动条
+    if entries.len() > height {
+        let mut state = ScrollbarState::new(entries.len().saturating_sub(height)).position(start);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            inner_area,
+            &mut state,
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod about;
 pub mod device_control;
 pub mod device_status;
+pub mod log_view;
 pub mod settings;
@@ -1,91 +1,93 @@
-use crate::app::config::AppConfig;
-use crate::ui_components::{create_block, get_indicator};
-use ratatui::{prelude::*, widgets::Paragraph};
+use crate::app::{settings, App};
+use crate::ui_components::{create_block, get_indicator, Theme};
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
 
-pub fn render(
-    frame: &mut Frame,
-    area: Rect,
-    selected: usize,
-    config: &AppConfig,
-    in_edit: bool,
-    edit_buffer: &str,
-    border_color: Color,
-) {
+/// 敏感内容的遮盖显示
+const SECRET_MASK: &str = "••••••";
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &Theme, border_color: Color) {
     let outer_block = create_block("设置".to_string(), border_color, border_color);
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
-    let chunks = Layout::new(
-        Direction::Vertical,
-        [Constraint::Length(3), Constraint::Min(0)],
-    )
-    .split(inner_area);
-
-    render_info_bar(frame, chunks[0], in_edit, border_color);
-    render_settings_list(
-        frame,
-        chunks[1],
-        selected,
-        config,
-        in_edit,
-        edit_buffer,
-        border_color,
-    );
-}
-
-fn render_info_bar(frame: &mut Frame, area: Rect, in_edit: bool, border_color: Color) {
-    let outer_block = create_block("操作说明".to_string(), border_color, border_color);
-    let inner_area = outer_block.inner(area);
-    frame.render_widget(outer_block, area);
-
-    let text = if in_edit {
-        "操作: [Enter] 保存  [Esc] 取消  [Backspace] 删除字符"
-    } else {
-        "操作: [↑/↓] 选择  [Enter] 编辑  [Esc] 退出"
-    };
-
-    let line = vec![Line::from_iter([Span::styled(
-        text,
-        Style::new().fg(Color::White),
-    )])];
-
-    let widget = Paragraph::new(line).style(Style::new().bg(Color::DarkGray));
-    frame.render_widget(widget, inner_area);
+    render_settings_list(frame, inner_area, app, theme, border_color);
 }
 
 fn render_settings_list(
     frame: &mut Frame,
     area: Rect,
-    selected: usize,
-    config: &AppConfig,
-    in_edit: bool,
-    edit_buffer: &str,
+    app: &App,
+    theme: &Theme,
     border_color: Color,
 ) {
-    let outer_block = create_block("配置项".to_string(), border_color, Color::Cyan);
+    let config = &app.config;
+    let selected = app.settings_selected;
+    let outer_block = create_block("配置项".to_string(), border_color, theme.title);
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
-    let items = [
-        ("Wifi名称", config.wifi_ssid.as_str()),
-        ("Wifi密码", config.wifi_password.as_str()),
-        ("麦克风名称", config.speech_name.as_str()),
-    ];
-
-    // 渲染每个设置项
-    for (i, (label, value)) in items.iter().enumerate() {
-        let y = inner_area.y + i as u16;
+    let items = settings::items();
+    // 配置项超过可见行数时滚动, 保持选中项可见
+    let height = inner_area.height as usize;
+    let start = (selected + 1).saturating_sub(height);
+
+    for (row, (i, item)) in items
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(height)
+        .enumerate()
+    {
+        let y = inner_area.y + row as u16;
         let item_area = Rect::new(inner_area.x, y, inner_area.width, 1);
 
+        let is_editing = app.in_edit_settings_mode && i == selected;
+        // 编辑时显示输入缓冲区
+        let value = if is_editing {
+            app.edit_buffer.clone()
+        } else {
+            (item.value)(config)
+        };
+        // 敏感内容遮盖显示, 不暴露实际长度
+        let value = if item.secret && !app.reveal_secrets && !value.is_empty() {
+            SECRET_MASK.to_string()
+        } else {
+            value
+        };
         render_setting_item(
             frame,
             item_area,
-            label,
-            value,
+            &item.label,
+            &value,
             i == selected,
-            in_edit && i == selected,
-            edit_buffer,
+            is_editing,
+            theme,
+        );
+
+        // 输入无效的说明显示在行尾
+        if let Some(error) = app.settings_error.as_ref().filter(|_| is_editing) {
+            let error = Span::styled(format!("✗ {error} "), Style::new().fg(theme.error).bold());
+            let width = (error.width() as u16).min(item_area.width);
+            let error_area = Rect::new(
+                item_area.right() - width,
+                item_area.y,
+                width,
+                item_area.height,
+            );
+            frame.render_widget(Paragraph::new(error), error_area);
+        }
+    }
+
+    if items.len() > height {
+        let mut state = ScrollbarState::new(items.len().saturating_sub(height)).position(start);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            inner_area,
+            &mut state,
         );
     }
 }
@@ -98,18 +100,16 @@ fn render_setting_item(
     value: &str,
     is_selected: bool,
     is_editing: bool,
-    edit_buffer: &str,
+    theme: &Theme,
 ) {
     let indicator = get_indicator(is_selected, is_editing);
 
     let color = if is_selected {
-        Color::Cyan
+        theme.highlight
     } else {
-        Color::White
+        theme.text
     };
 
-    let display_value = if is_editing { edit_buffer } else { value };
-
     let text = vec![Line::from_iter([
         Span::styled(
             indicator.to_string(),
@@ -117,17 +117,17 @@ fn render_setting_item(
         ),
         Span::styled(format!(" {label}: "), Style::new().fg(color)),
         Span::styled(
-            display_value,
+            value,
             if is_editing {
-                Style::new().fg(Color::Black).bg(Color::White)
+                Style::new().fg(theme.input_fg).bg(theme.input_bg)
             } else if value.is_empty() {
-                Style::new().fg(Color::DarkGray)
+                Style::new().fg(theme.muted)
             } else {
-                Style::new().fg(Color::Yellow)
+                Style::new().fg(theme.label)
             },
         ),
     ])];
 
-    let widget = Paragraph::new(text).style(Style::new().fg(Color::White));
+    let widget = Paragraph::new(text).style(Style::new().fg(theme.text));
     frame.render_widget(widget, area);
 }
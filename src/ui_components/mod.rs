@@ -1,7 +1,12 @@
-use crate::app::Popup;
+mod theme;
+
+pub use theme::Theme;
+
+use crate::app::log_queue::{LogLevel, LogPopup, LogQueue, LOG_POPUP_ENTRIES};
+use crate::app::{Popup, SelectPopup};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 
 /// 创建带标题的 Block
@@ -77,3 +82,160 @@ impl Default for PopupWidget {
         Self::new()
     }
 }
+
+/// 列表选择弹窗组件
+pub struct SelectPopupWidget;
+
+impl SelectPopupWidget {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        popup: &mut SelectPopup,
+        theme: &Theme,
+    ) {
+        if !popup.is_visible() {
+            return;
+        }
+
+        let width = std::cmp::min(50, area.width.saturating_sub(4));
+        let rows = popup.items.len().max(1) as u16;
+        let height = std::cmp::min(rows + 3, area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(width)) / 2;
+        let y = area.y + (area.height.saturating_sub(height)) / 2;
+        let popup_area = Rect::new(x, y, width, height);
+
+        let block = Block::new()
+            .title(popup.title.clone())
+            .title_style(Style::new().fg(theme.title))
+            .title_bottom(" [↑/↓] 选择 [Enter] 确认 [Esc] 取消 ")
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(theme.focus))
+            .style(Style::new().bg(theme.panel_bg).fg(theme.text));
+        let inner_area = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        if popup.items.is_empty() {
+            let content =
+                Paragraph::new(popup.empty_message.clone()).style(Style::new().fg(theme.warning));
+            frame.render_widget(content, inner_area);
+            return;
+        }
+
+        let items: Vec<ListItem> = popup
+            .items
+            .iter()
+            .map(|item| ListItem::new(item.clone()))
+            .collect();
+        let list = List::new(items)
+            .highlight_style(
+                Style::new()
+                    .bg(theme.highlight)
+                    .fg(theme.highlight_fg)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ");
+        frame.render_stateful_widget(list, inner_area, &mut popup.state);
+    }
+}
+
+impl Default for SelectPopupWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 日志级别对应的颜色
+pub fn log_level_color(level: LogLevel, theme: &Theme) -> Color {
+    match level {
+        LogLevel::Info => theme.text,
+        LogLevel::Warn => theme.warning,
+        LogLevel::Error => theme.error,
+    }
+}
+
+/// 日志弹窗组件, 显示在右下角
+pub struct LogPopupWidget;
+
+impl LogPopupWidget {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        popup: &LogPopup,
+        queue: &LogQueue,
+        theme: &Theme,
+    ) {
+        if !popup.is_visible() || queue.is_empty() {
+            return;
+        }
+
+        let width = std::cmp::min(60, area.width.saturating_sub(2));
+        let height = std::cmp::min(LOG_POPUP_ENTRIES as u16 + 2, area.height);
+        let popup_area = Rect::new(
+            area.x + area.width.saturating_sub(width + 1),
+            area.y + area.height.saturating_sub(height),
+            width,
+            height,
+        );
+
+        let title = if popup.is_searching() {
+            format!(" 日志 /{}_ [Enter] 确定 [Esc] 清除 ", popup.filter)
+        } else if !popup.filter.is_empty() {
+            format!(" 日志 /{} [Ctrl+L] ", popup.filter)
+        } else {
+            " 日志 [Ctrl+L] [/] 搜索 ".to_string()
+        };
+        let block = Block::new()
+            .title(title)
+            .title_style(Style::new().fg(theme.title))
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(theme.warning))
+            .style(Style::new().bg(theme.popup_bg).fg(theme.text));
+        let inner_area = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        // 取匹配搜索词的最新几条, 按时间顺序显示
+        let mut matched: Vec<_> = queue
+            .entries()
+            .rev()
+            .filter(|entry| popup.matches(entry))
+            .take(LOG_POPUP_ENTRIES)
+            .collect();
+        matched.reverse();
+        let lines: Vec<Line> = matched
+            .into_iter()
+            .map(|entry| {
+                let color = log_level_color(entry.level, theme);
+                Line::from_iter([
+                    Span::styled(
+                        format!("{} ", entry.timestamp.format("%H:%M:%S")),
+                        Style::new().fg(theme.muted),
+                    ),
+                    Span::styled(
+                        format!("{:<5} ", entry.level.label()),
+                        Style::new().fg(color).bold(),
+                    ),
+                    Span::styled(entry.with_count(), Style::new().fg(color)),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner_area);
+    }
+}
+
+impl Default for LogPopupWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,138 @@
+//! 界面配色
+//!
+//! 各页面和组件从 [Theme] 读取颜色, 配色方案由 [AppConfig::theme](crate::app::config::AppConfig) 选择
+
+use crate::app::config::ThemeName;
+use ratatui::style::Color;
+
+/// 界面配色方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// 无焦点区域边框
+    pub border: Color,
+    /// 有焦点区域边框
+    pub focus: Color,
+    /// 标题
+    pub title: Color,
+    /// 选中项背景
+    pub highlight: Color,
+    /// 选中项文字
+    pub highlight_fg: Color,
+    /// 数值等强调内容
+    pub accent: Color,
+    /// 正常、已连接等状态
+    pub success: Color,
+    /// 字段名
+    pub label: Color,
+    /// 警告
+    pub warning: Color,
+    /// 错误、断开等状态
+    pub error: Color,
+    /// 普通文字
+    pub text: Color,
+    /// 次要文字
+    pub secondary: Color,
+    /// 提示、不可用内容
+    pub muted: Color,
+    /// 操作说明栏、弹窗背景
+    pub panel_bg: Color,
+    /// 日志弹窗背景
+    pub popup_bg: Color,
+    /// 输入框文字
+    pub input_fg: Color,
+    /// 输入框背景
+    pub input_bg: Color,
+}
+
+impl Theme {
+    /// 深色终端配色, 与最初的硬编码颜色一致
+    pub const DARK: Self = Self {
+        border: Color::LightBlue,
+        focus: Color::Green,
+        title: Color::Cyan,
+        highlight: Color::Cyan,
+        highlight_fg: Color::Black,
+        accent: Color::Cyan,
+        success: Color::Green,
+        label: Color::Yellow,
+        warning: Color::Yellow,
+        error: Color::Red,
+        text: Color::White,
+        secondary: Color::Gray,
+        muted: Color::DarkGray,
+        panel_bg: Color::DarkGray,
+        popup_bg: Color::Black,
+        input_fg: Color::Black,
+        input_bg: Color::White,
+    };
+
+    /// 浅色终端配色, 避免白底上的青色和黄色文字
+    pub const LIGHT: Self = Self {
+        border: Color::Blue,
+        focus: Color::Magenta,
+        title: Color::Blue,
+        highlight: Color::Blue,
+        highlight_fg: Color::White,
+        accent: Color::Blue,
+        success: Color::Green,
+        label: Color::Magenta,
+        warning: Color::Red,
+        error: Color::Red,
+        text: Color::Black,
+        secondary: Color::DarkGray,
+        muted: Color::Gray,
+        panel_bg: Color::Gray,
+        popup_bg: Color::White,
+        input_fg: Color::White,
+        input_bg: Color::Black,
+    };
+
+    /// 高对比度配色
+    pub const HIGH_CONTRAST: Self = Self {
+        border: Color::White,
+        focus: Color::LightYellow,
+        title: Color::LightYellow,
+        highlight: Color::LightYellow,
+        highlight_fg: Color::Black,
+        accent: Color::LightCyan,
+        success: Color::LightGreen,
+        label: Color::LightYellow,
+        warning: Color::LightYellow,
+        error: Color::LightRed,
+        text: Color::White,
+        secondary: Color::White,
+        muted: Color::Gray,
+        panel_bg: Color::Black,
+        popup_bg: Color::Black,
+        input_fg: Color::Black,
+        input_bg: Color::White,
+    };
+
+    /// 按名称获取预设配色
+    pub fn preset(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::DARK,
+            ThemeName::Light => Self::LIGHT,
+            ThemeName::HighContrast => Self::HIGH_CONTRAST,
+        }
+    }
+
+    /// 区域边框颜色
+    ///
+    /// # Arguments
+    ///
+    /// * `focused` - 区域是否有焦点
+    pub fn border_color(&self, focused: bool) -> Color {
+        if focused {
+            self.focus
+        } else {
+            self.border
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
@@ -1,10 +1,17 @@
+mod tts;
+
 use anyhow::{anyhow, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Stream};
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::mpsc::SyncSender;
-use std::sync::{mpsc, Arc};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
 use std::thread;
+use std::time::{Duration, Instant};
 use vosk::{Model, Recognizer};
 
 /// 语音唤醒事件
@@ -13,30 +20,181 @@ pub struct WakeEvent {
     pub text: String,
 }
 
+/// 默认的 Vosk 模型路径
+pub const DEFAULT_MODEL_PATH: &str = "assets/module/vosk-model-small-cn-0.22";
+
+/// 录音文件写入器 (16kHz 单声道 i16)
+type WavRecorder = hound::WavWriter<BufWriter<File>>;
+
+/// 实时识别事件
+///
+/// `is_final` 为 false 时是中间结果, 为 true 时是最终结果 (用于替换之前的中间结果)
+#[derive(Clone, Debug)]
+pub struct PartialEvent {
+    pub text: String,
+    pub is_final: bool,
+}
+
+/// 语音控制命令
+///
+/// 由 [parse_command] 根据配置的命令表从识别文本中解析, 命令表的值格式为:
+/// - `mood:<name>`: 切换表情, name 为 default/happy/tired/angry
+/// - `look:<name>`: 切换注视方向, name 为 center/up/down/left/right 及 up_left 等组合
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VoiceCommand {
+    Mood(String),
+    Look(String),
+}
+
+/// 否定前缀, 紧跟在其后的短语不触发命令 ("不开心" 不会匹配 "开心")
+const NEGATION_PREFIXES: [&str; 4] = ["不", "没", "别", "不要"];
+
+/// 从识别文本中解析语音命令, 未匹配任何短语时返回 None
+///
+/// 同时匹配多个短语时取最长的一个, 前面带否定词的短语不算匹配
+///
+/// # Arguments
+///
+/// * `text`: 识别到的文本
+/// * `commands`: 命令表, 短语 -> 动作
+pub fn parse_command(text: &str, commands: &BTreeMap<String, String>) -> Option<VoiceCommand> {
+    let (_, action) = commands
+        .iter()
+        .filter(|(phrase, _)| !phrase.is_empty())
+        .filter(|(phrase, _)| contains_affirmative(text, phrase))
+        // 长度相同时保留命令表中靠前的短语
+        .min_by_key(|(phrase, _)| std::cmp::Reverse(phrase.chars().count()))?;
+
+    match action.split_once(':') {
+        Some(("mood", name)) => Some(VoiceCommand::Mood(name.trim().to_string())),
+        Some(("look", name)) => Some(VoiceCommand::Look(name.trim().to_string())),
+        _ => {
+            log::warn!("Unknown voice command action: {action}");
+            None
+        }
+    }
+}
+
+/// 文本中是否出现前面没有否定词的短语
+fn contains_affirmative(text: &str, phrase: &str) -> bool {
+    text.match_indices(phrase).any(|(index, _)| {
+        let before = &text[..index];
+        !NEGATION_PREFIXES
+            .iter()
+            .any(|negation| before.ends_with(negation))
+    })
+}
+
+/// 语音管理器配置
+#[derive(Debug, Clone, Default)]
+pub struct VoiceConfig {
+    /// Vosk 模型路径
+    pub model_path: String,
+    /// 麦克风名称
+    pub speech_name: String,
+    /// 唤醒词列表, 为空时不检测唤醒
+    pub wake_words: Vec<String>,
+    /// 唤醒后的语音应答, 为空时不播报
+    pub greeting: String,
+    /// 语音活动检测阈值 (i16 RMS), 0 表示关闭检测
+    pub vad_threshold: f32,
+    /// 音量低于阈值持续多少个分块后结束本段识别
+    pub vad_hangover: u32,
+    /// 按键通话模式, 开启时默认不监听, 需要手动切换
+    pub push_to_talk: bool,
+    /// 识别结果的最低平均置信度, 低于该值的结果被丢弃
+    pub min_confidence: f32,
+    /// 语音命令表, 短语 -> 动作
+    pub commands: BTreeMap<String, String>,
+}
+
 /// 语音管理器
 ///
-/// 封装音频流和 Vosk 识别器
+/// 封装音频流和 Vosk 识别器, 可通过 [VoiceManager::stop] / [VoiceManager::start] 释放和重建音频流
 #[allow(dead_code)]
 pub struct VoiceManager {
-    _stream: Stream,
+    config: VoiceConfig,
+    stream: Option<Stream>,
+    threads: Vec<thread::JoinHandle<()>>,
+    enabled: bool,
     volume: Arc<AtomicI32>,
+    volume_smoothed: Arc<AtomicI32>,
+    peak: Arc<AtomicI32>,
+    wake_words: Arc<RwLock<Vec<String>>>,
+    greeting: Arc<RwLock<String>>,
+    partial_rx: mpsc::Receiver<PartialEvent>,
+    listening: Arc<AtomicBool>,
+    muted: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    recorder: Arc<Mutex<Option<WavRecorder>>>,
+    command_rx: mpsc::Receiver<VoiceCommand>,
+    event_rx: mpsc::Receiver<WakeEvent>,
+    recognizer_tx: SyncSender<SpeechRecognizer>,
 }
 
 #[allow(dead_code)]
 impl VoiceManager {
-    /// 创建语音管理器
-    pub fn new(model_path: &str, speech_name: &str) -> Result<Self> {
+    /// 创建语音管理器并启动音频流
+    ///
+    /// 找不到麦克风时返回未启用的管理器, 模型目录不存在时返回错误
+    pub fn new(voice_config: &VoiceConfig) -> Result<Self> {
+        let (_, partial_rx) = mpsc::sync_channel(0);
+        let (_, command_rx) = mpsc::sync_channel(0);
+        let (_, event_rx) = mpsc::sync_channel(0);
+        let (recognizer_tx, _) = mpsc::sync_channel(0);
+        let mut manager = Self {
+            config: voice_config.clone(),
+            stream: None,
+            threads: Vec::new(),
+            enabled: false,
+            volume: Arc::new(AtomicI32::new(0)),
+            volume_smoothed: Arc::new(AtomicI32::new(0)),
+            peak: Arc::new(AtomicI32::new(0)),
+            wake_words: Arc::new(RwLock::new(voice_config.wake_words.clone())),
+            greeting: Arc::new(RwLock::new(voice_config.greeting.clone())),
+            partial_rx,
+            listening: Arc::new(AtomicBool::new(!voice_config.push_to_talk)),
+            muted: Arc::new(AtomicBool::new(false)),
+            recording: Arc::new(AtomicBool::new(false)),
+            recorder: Arc::new(Mutex::new(None)),
+            command_rx,
+            event_rx,
+            recognizer_tx,
+        };
+        manager.start()?;
+        Ok(manager)
+    }
+
+    /// 按保存的配置启动音频流和识别线程, 已启动时直接返回
+    ///
+    /// 找不到麦克风时保持未启用状态 (没有音频流, 音量始终为 0, 不产生任何事件).
+    /// 模型在分析线程中加载, 不阻塞调用线程; 加载失败时只记录错误, 可以之后用
+    /// [VoiceManager::reload_model] 重新加载
+    pub fn start(&mut self) -> Result<()> {
+        if self.stream.is_some() {
+            return Ok(());
+        }
+
+        let voice_config = self.config.clone();
+        if !Path::new(&voice_config.model_path).is_dir() {
+            return Err(anyhow!(
+                "Model directory not found: {}",
+                voice_config.model_path
+            ));
+        }
+        let speech_name = voice_config.speech_name.as_str();
         // 获取音频设备列表
         let devices = list_devices();
         for (name, _) in &devices {
             log::info!("find speech: {name}");
         }
 
-        // 查找指定麦克风
-        let (device_name, device) = devices
-            .into_iter()
-            .find(|(name, _)| name == speech_name)
-            .ok_or_else(|| anyhow!("No audio input device found: {speech_name}"))?;
+        // 查找指定麦克风, 找不到时进入禁用状态而不是报错
+        let Some((device_name, device)) = devices.into_iter().find(|(name, _)| name == speech_name)
+        else {
+            log::warn!("No audio input device found: {speech_name}, voice disabled");
+            return Ok(());
+        };
 
         log::info!("Using audio device: {device_name}");
 
@@ -54,23 +212,50 @@ impl VoiceManager {
             buffer_size: cpal::BufferSize::Default,
         };
 
-        // 共享状态
-        let volume = Arc::new(AtomicI32::new(0));
+        // 每次启动重建的通道
         let (wake_tx, wake_rx) = mpsc::sync_channel::<WakeEvent>(4);
+        let (partial_tx, partial_rx) = mpsc::sync_channel::<PartialEvent>(16);
+        let (command_tx, command_rx) = mpsc::sync_channel::<VoiceCommand>(4);
+        let (event_tx, event_rx) = mpsc::sync_channel::<WakeEvent>(4);
+        let (recognizer_tx, recognizer_rx) = mpsc::sync_channel::<SpeechRecognizer>(1);
 
-        let recognizer = SpeechRecognizer::new(model_path)?;
-        let (audio_tx, audio_rx) = mpsc::sync_channel::<Vec<i16>>(4);
+        let vad = Vad::new(voice_config.vad_threshold, voice_config.vad_hangover);
+        let (audio_tx, audio_rx) = mpsc::sync_channel::<AudioChunk>(4);
 
-        let volume_clone = volume.clone();
+        let volume_clone = self.volume.clone();
+        let smoothed_clone = self.volume_smoothed.clone();
+        let peak_clone = self.peak.clone();
+        let mut meter = VolumeMeter::new();
+        let listening_clone = self.listening.clone();
+        let muted_clone = self.muted.clone();
+        let recording_clone = self.recording.clone();
         let error_handler = |e| log::error!("Audio stream error: {e}");
         let stream = device.build_input_stream(
             &config,
             move |data: &[f32], _: &_| {
+                // 静音时音量归零, 不录音也不识别, 音频流保持运行
+                if muted_clone.load(Ordering::Relaxed) {
+                    meter.reset();
+                    volume_clone.store(0, Ordering::Relaxed);
+                    smoothed_clone.store(0, Ordering::Relaxed);
+                    peak_clone.store(0, Ordering::Relaxed);
+                    return;
+                }
+
                 // 计算音量
                 let sum: f32 = data.iter().map(|&s| s * s).sum();
                 let rms = (sum / data.len() as f32).sqrt();
                 let volume = (rms * 100.0).min(100.0) as i32;
                 volume_clone.store(volume, Ordering::Relaxed);
+                let (smoothed, peak) = meter.update(volume as f32);
+                smoothed_clone.store(smoothed as i32, Ordering::Relaxed);
+                peak_clone.store(peak as i32, Ordering::Relaxed);
+
+                // 回调在实时音频线程上运行, 只读原子标志, 不加锁也不做文件读写
+                let listening = listening_clone.load(Ordering::Relaxed);
+                if !listening && !recording_clone.load(Ordering::Relaxed) {
+                    return;
+                }
 
                 // 双声道混合成单声道
                 let mono_samples: Vec<f32> = if actual_channels == 2 {
@@ -93,7 +278,12 @@ impl VoiceManager {
                 } else {
                     samples
                 };
-                let _ = audio_tx.send(final_samples);
+
+                // 录音与识别使用同一份数据, 由分析线程写文件; 队列满时丢弃, 不阻塞音频回调
+                let _ = audio_tx.try_send(AudioChunk {
+                    samples: final_samples,
+                    listening,
+                });
             },
             error_handler,
             None,
@@ -101,29 +291,295 @@ impl VoiceManager {
         stream.play()?;
         log::info!("Voice recognition thread started");
 
-        thread::spawn(move || {
-            audio_analysis_thread(wake_tx, recognizer, audio_rx);
-        });
+        // 音频流销毁后 audio_tx 随之释放, 分析线程和分发线程依次退出
+        let recorder_clone = self.recorder.clone();
+        let model_path = voice_config.model_path.clone();
+        let min_confidence = voice_config.min_confidence;
+        self.threads.push(thread::spawn(move || {
+            let recognizer = load_recognizer(&model_path, min_confidence)
+                .inspect_err(|e| log::error!("{e}"))
+                .ok();
+            audio_analysis_thread(
+                wake_tx,
+                partial_tx,
+                recognizer,
+                recognizer_rx,
+                vad,
+                audio_rx,
+                recorder_clone,
+            );
+        }));
 
-        thread::spawn(move || {
+        let wake_words_clone = self.wake_words.clone();
+        let greeting_clone = self.greeting.clone();
+        let commands = voice_config.commands.clone();
+        // 唯一消费识别结果的线程, 处理完后将唤醒事件转发给应用
+        self.threads.push(thread::spawn(move || {
             for event in wake_rx {
                 log::trace!("Wake event: {:?}", event);
-                if SpeechRecognizer::is_wake_word(&event.text) {
+                if let Some(command) = parse_command(&event.text, &commands) {
+                    log::info!("Voice command: {command:?}");
+                    let _ = command_tx.try_send(command);
+                }
+
+                let words = wake_words_clone.read().unwrap_or_else(|e| e.into_inner());
+                if SpeechRecognizer::is_wake_word(&event.text, &words) {
                     log::info!("Wake word detected");
+                    tts::speak(&greeting_clone.read().unwrap_or_else(|e| e.into_inner()));
+                    // 应用未及时处理时丢弃, 不阻塞识别
+                    let _ = event_tx.try_send(event);
+                }
+            }
+        }));
+
+        self.stream = Some(stream);
+        self.partial_rx = partial_rx;
+        self.command_rx = command_rx;
+        self.event_rx = event_rx;
+        self.recognizer_tx = recognizer_tx;
+        self.enabled = true;
+        Ok(())
+    }
+
+    /// 停止音频流并等待识别线程退出, 释放麦克风; 可重复调用
+    pub fn stop(&mut self) {
+        if let Err(e) = self.stop_recording() {
+            log::error!("Failed to finish recording: {e}");
+        }
+        if self.stream.take().is_some() {
+            log::info!("Voice stream stopped");
+        }
+        for handle in self.threads.drain(..) {
+            let _ = handle.join();
+        }
+        self.enabled = false;
+        self.volume.store(0, Ordering::Relaxed);
+        self.volume_smoothed.store(0, Ordering::Relaxed);
+        self.peak.store(0, Ordering::Relaxed);
+    }
+
+    /// 使用新配置重启音频流
+    pub fn restart(&mut self, voice_config: &VoiceConfig) -> Result<()> {
+        self.stop();
+        self.config = voice_config.clone();
+        self.set_wake_words(voice_config.wake_words.clone());
+        self.set_greeting(voice_config.greeting.clone());
+        self.start()
+    }
+
+    /// 重新加载 Vosk 模型, 不重建音频流
+    ///
+    /// 模型在后台线程加载, 加载失败时保留旧模型并记录错误
+    pub fn reload_model(&mut self, model_path: &str) -> Result<()> {
+        if !self.enabled {
+            return Err(anyhow!("Microphone not connected"));
+        }
+        if !Path::new(model_path).is_dir() {
+            return Err(anyhow!("Model directory not found: {model_path}"));
+        }
+
+        // 记录新路径, 之后重启音频流时使用
+        self.config.model_path = model_path.to_string();
+        let model_path = model_path.to_string();
+        let min_confidence = self.config.min_confidence;
+        let recognizer_tx = self.recognizer_tx.clone();
+        thread::spawn(move || match load_recognizer(&model_path, min_confidence) {
+            Ok(recognizer) => {
+                if recognizer_tx.send(recognizer).is_err() {
+                    log::warn!("Analysis thread stopped, model not applied");
                 }
             }
+            Err(e) => log::error!("{e}, keep the old one"),
         });
+        Ok(())
+    }
 
-        Ok(Self {
-            _stream: stream,
-            volume,
-        })
+    /// 是否已连接麦克风并在运行
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// 非阻塞获取唤醒事件
+    pub fn try_recv_event(&self) -> Option<WakeEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    /// 非阻塞获取语音命令
+    pub fn try_recv_command(&self) -> Option<VoiceCommand> {
+        self.command_rx.try_recv().ok()
+    }
+
+    /// 开始录音, 将重采样后的 16kHz 单声道音频写入 WAV 文件
+    pub fn start_recording(&self, path: &Path) -> Result<()> {
+        if !self.enabled {
+            return Err(anyhow!("Microphone not connected"));
+        }
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(path, spec)?;
+        let mut recorder = self.recorder.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = recorder.replace(writer) {
+            old.finalize()?;
+        }
+        self.recording.store(true, Ordering::Relaxed);
+        log::info!("Recording microphone to {}", path.display());
+        Ok(())
+    }
+
+    /// 停止录音并写入文件头
+    pub fn stop_recording(&self) -> Result<()> {
+        self.recording.store(false, Ordering::Relaxed);
+        let writer = self
+            .recorder
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(writer) = writer {
+            writer.finalize()?;
+            log::info!("Recording stopped");
+        }
+        Ok(())
+    }
+
+    /// 是否正在录音
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::Relaxed)
+    }
+
+    /// 设置是否将音频送入识别器
+    pub fn set_listening(&self, listening: bool) {
+        self.listening.store(listening, Ordering::Relaxed);
+        log::info!("Voice listening: {listening}");
+    }
+
+    /// 是否正在监听
+    pub fn is_listening(&self) -> bool {
+        self.listening.load(Ordering::Relaxed)
+    }
+
+    /// 设置麦克风静音, 不重建音频流
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+        if muted {
+            self.volume.store(0, Ordering::Relaxed);
+            self.volume_smoothed.store(0, Ordering::Relaxed);
+            self.peak.store(0, Ordering::Relaxed);
+        }
+        log::info!("Microphone muted: {muted}");
+    }
+
+    /// 是否静音
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    /// 非阻塞获取实时识别结果
+    pub fn try_recv_partial(&self) -> Option<PartialEvent> {
+        self.partial_rx.try_recv().ok()
+    }
+
+    /// 通过默认输出设备朗读文本, 不阻塞调用线程
+    pub fn speak(&self, text: &str) {
+        tts::speak(text);
+    }
+
+    /// 更新唤醒应答
+    pub fn set_greeting(&self, greeting: String) {
+        *self.greeting.write().unwrap_or_else(|e| e.into_inner()) = greeting;
+    }
+
+    /// 更新唤醒词列表, 对正在运行的识别立即生效
+    pub fn set_wake_words(&self, words: Vec<String>) {
+        *self.wake_words.write().unwrap_or_else(|e| e.into_inner()) = words;
     }
 
     /// 获取当前音量 (0-100)
     pub fn volume(&self) -> i32 {
         self.volume.load(Ordering::Relaxed)
     }
+
+    /// 获取平滑后的音量 (0-100), 上升快下降慢
+    pub fn volume_smoothed(&self) -> i32 {
+        self.volume_smoothed.load(Ordering::Relaxed)
+    }
+
+    /// 获取峰值保持音量 (0-100)
+    pub fn peak(&self) -> i32 {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for VoiceManager {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 音量表平滑处理
+///
+/// 指数滑动平均 (上升快下降慢) + 峰值保持后线性衰减
+struct VolumeMeter {
+    smoothed: f32,
+    peak: f32,
+    peak_time: Instant,
+    last_update: Instant,
+}
+
+impl VolumeMeter {
+    /// 音量上升时的平滑系数
+    const ATTACK: f32 = 0.6;
+    /// 音量下降时的平滑系数
+    const RELEASE: f32 = 0.08;
+    /// 峰值保持时间
+    const PEAK_HOLD: Duration = Duration::from_millis(800);
+    /// 峰值衰减速度 (每秒)
+    const PEAK_DECAY_PER_SEC: f32 = 40.0;
+
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            smoothed: 0.0,
+            peak: 0.0,
+            peak_time: now,
+            last_update: now,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// 输入原始音量, 返回 (平滑音量, 峰值)
+    fn update(&mut self, raw: f32) -> (f32, f32) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+
+        let alpha = if raw > self.smoothed {
+            Self::ATTACK
+        } else {
+            Self::RELEASE
+        };
+        self.smoothed += (raw - self.smoothed) * alpha;
+
+        if raw >= self.peak {
+            self.peak = raw;
+            self.peak_time = now;
+        } else if now.duration_since(self.peak_time) > Self::PEAK_HOLD {
+            self.peak = (self.peak - Self::PEAK_DECAY_PER_SEC * elapsed).max(self.smoothed);
+        }
+
+        (self.smoothed, self.peak)
+    }
+}
+
+/// 列出所有可用音频输入设备的名称
+pub fn input_device_names() -> Vec<String> {
+    list_devices().into_iter().map(|(name, _)| name).collect()
 }
 
 /// 列出所有可用的音频输入设备
@@ -144,10 +600,12 @@ fn list_devices() -> Vec<(String, Device)> {
 
 /// 将音频重采样到 16kHz
 ///
+/// 在相邻两个源采样点之间做线性插值, 末尾超出源数据的位置保持最后一个采样值
+///
 /// # Arguments
 ///
-/// * `samples`:
-/// * `from_rate`:
+/// * `samples`: 单声道源采样
+/// * `from_rate`: 源采样率
 ///
 /// returns: Vec<i16, Global>
 ///
@@ -157,27 +615,64 @@ fn list_devices() -> Vec<(String, Device)> {
 ///
 /// ```
 fn resample_to_16k(samples: &[i16], from_rate: u32) -> Vec<i16> {
+    if samples.is_empty() || from_rate == 0 {
+        return Vec::new();
+    }
+
     let ratio = from_rate as f64 / 16000.0;
     let new_len = (samples.len() as f64 / ratio) as usize;
-    let mut result = Vec::with_capacity(new_len);
+    let last = samples.len() - 1;
+
+    (0..new_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = (pos.floor() as usize).min(last);
+            let frac = pos - idx as f64;
+            let a = samples[idx] as f64;
+            let b = samples[(idx + 1).min(last)] as f64;
+            (a + (b - a) * frac).round() as i16
+        })
+        .collect()
+}
+
+/// 加载 Vosk 模型并创建识别器, 耗时数秒, 只在后台线程调用
+fn load_recognizer(model_path: &str, min_confidence: f32) -> Result<SpeechRecognizer> {
+    log::info!("Loading model: {model_path}");
+    Ok(SpeechRecognizer::new(model_path)?.with_confidence(min_confidence))
+}
+
+/// 音频回调送往分析线程的 16kHz 单声道分块
+struct AudioChunk {
+    samples: Vec<i16>,
+    /// 采集时是否在监听, 未监听的分块只写入录音, 不送入识别器
+    listening: bool,
+}
 
-    for i in 0..new_len {
-        let src_idx = (i as f64 * ratio) as usize;
-        if src_idx < samples.len() {
-            result.push(samples[src_idx]);
+/// 把分块写入正在进行的录音, 没有录音时忽略
+fn write_recording(recorder: &Mutex<Option<WavRecorder>>, samples: &[i16]) {
+    let mut recorder = recorder.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(writer) = recorder.as_mut() else {
+        return;
+    };
+    for &sample in samples {
+        if let Err(e) = writer.write_sample(sample) {
+            log::error!("Failed to write recording: {e}");
+            break;
         }
     }
-
-    result
 }
 
 /// 音频分析线程
 ///
 /// # Arguments
 ///
-/// * `wake_tx`:
-/// * `recognizer`:
+/// * `wake_tx`: 最终识别结果
+/// * `partial_tx`: 实时识别结果, 队列满时丢弃, 不阻塞识别
+/// * `recognizer`: 启动时加载的识别器, 加载失败时为 None, 此时只写录音不识别
+/// * `recognizer_rx`: 热切换模型时接收新的识别器
+/// * `vad`: 静音分块不送入识别器, 语音结束后强制输出最终结果
 /// * `audio_rx`:
+/// * `recorder`: 正在进行的录音, 在本线程写入文件
 ///
 /// returns: ()
 ///
@@ -188,24 +683,59 @@ fn resample_to_16k(samples: &[i16], from_rate: u32) -> Vec<i16> {
 /// ```
 fn audio_analysis_thread(
     wake_tx: SyncSender<WakeEvent>,
-    mut recognizer: SpeechRecognizer,
-    audio_rx: mpsc::Receiver<Vec<i16>>,
+    partial_tx: SyncSender<PartialEvent>,
+    mut recognizer: Option<SpeechRecognizer>,
+    recognizer_rx: mpsc::Receiver<SpeechRecognizer>,
+    mut vad: Vad,
+    audio_rx: mpsc::Receiver<AudioChunk>,
+    recorder: Arc<Mutex<Option<WavRecorder>>>,
 ) {
     let chunk_size = 1600;
     let mut buffer = Vec::new();
+    let mut last_partial = String::new();
 
-    for samples in audio_rx {
-        buffer.extend(samples);
+    let emit_final = |text: String, last_partial: &mut String| {
+        last_partial.clear();
+        let _ = partial_tx.try_send(PartialEvent {
+            text: text.clone(),
+            is_final: true,
+        });
+        let event = WakeEvent { text };
+        if let Err(e) = wake_tx.send(event) {
+            log::warn!("Failed to send wake event: {e}");
+        }
+    };
+
+    for chunk in audio_rx {
+        if let Ok(new_recognizer) = recognizer_rx.try_recv() {
+            recognizer = Some(new_recognizer);
+            last_partial.clear();
+            log::info!("Speech model reloaded");
+        }
+        write_recording(&recorder, &chunk.samples);
+        // 未监听或模型未加载时不识别
+        let Some(recognizer) = recognizer.as_mut().filter(|_| chunk.listening) else {
+            continue;
+        };
+        buffer.extend(chunk.samples);
 
         while buffer.len() >= chunk_size {
             let frame = &buffer[0..chunk_size];
-            if let Some(text) = recognizer.process(frame) {
-                if text.is_empty() {
-                    continue;
-                }
-                let event = WakeEvent { text };
-                if let Err(e) = wake_tx.send(event) {
-                    log::warn!("Failed to send wake event: {e}");
+            let result = match vad.update(frame) {
+                VadState::Silence => None,
+                VadState::Speech => recognizer.process(frame),
+                VadState::EndOfSpeech => recognizer.process(frame).or_else(|| recognizer.flush()),
+            };
+
+            if let Some(text) = result {
+                emit_final(text, &mut last_partial);
+            } else if let Some(text) = recognizer.partial() {
+                if text != last_partial {
+                    last_partial = text.clone();
+                    let _ = partial_tx.try_send(PartialEvent {
+                        text,
+                        is_final: false,
+                    });
                 }
             }
             buffer.drain(..chunk_size);
@@ -213,9 +743,109 @@ fn audio_analysis_thread(
     }
 }
 
+/// 语音活动检测结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VadState {
+    /// 静音, 跳过识别
+    Silence,
+    /// 说话中 (包含结束前的拖尾分块)
+    Speech,
+    /// 语音刚结束, 需要输出最终结果
+    EndOfSpeech,
+}
+
+/// 基于能量的语音活动检测
+///
+/// 分块 RMS 超过阈值视为说话, 低于阈值持续 `hangover` 个分块后视为说话结束
+struct Vad {
+    threshold: f32,
+    hangover: u32,
+    speaking: bool,
+    silent_chunks: u32,
+}
+
+impl Vad {
+    fn new(threshold: f32, hangover: u32) -> Self {
+        Self {
+            threshold,
+            hangover,
+            speaking: false,
+            silent_chunks: 0,
+        }
+    }
+
+    /// 根据当前分块更新状态
+    fn update(&mut self, frame: &[i16]) -> VadState {
+        // 阈值为 0 时关闭检测, 所有音频都送入识别器
+        if self.threshold <= 0.0 {
+            return VadState::Speech;
+        }
+
+        if chunk_rms(frame) >= self.threshold {
+            self.speaking = true;
+            self.silent_chunks = 0;
+            return VadState::Speech;
+        }
+
+        if !self.speaking {
+            return VadState::Silence;
+        }
+
+        self.silent_chunks += 1;
+        if self.silent_chunks >= self.hangover {
+            self.speaking = false;
+            self.silent_chunks = 0;
+            VadState::EndOfSpeech
+        } else {
+            VadState::Speech
+        }
+    }
+}
+
+/// 计算分块的 RMS (i16 幅度)
+fn chunk_rms(frame: &[i16]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / frame.len() as f64).sqrt() as f32
+}
+
+/// 按平均置信度过滤最终结果
+///
+/// # Arguments
+///
+/// * `text`: 识别到的文本, 为空时丢弃
+/// * `confidences`: 逐词置信度, 开启逐词输出后为空说明没有识别出可用的词, 设置了阈值时丢弃
+/// * `min_confidence`: 最低平均置信度
+///
+/// returns: Option<String>
+fn filter_result(text: &str, confidences: &[f32], min_confidence: f32) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if min_confidence <= 0.0 {
+        return Some(text.to_string());
+    }
+    if confidences.is_empty() {
+        log::debug!("Drop result without word confidences: {text}");
+        return None;
+    }
+    let confidence = confidences.iter().sum::<f32>() / confidences.len() as f32;
+    if confidence < min_confidence {
+        log::debug!("Drop low confidence result: {text} ({confidence:.2})");
+        return None;
+    }
+
+    Some(text.to_string())
+}
+
 /// 语音识别器
 pub struct SpeechRecognizer {
     recognizer: Recognizer,
+    min_confidence: f32,
 }
 
 impl SpeechRecognizer {
@@ -223,10 +853,21 @@ impl SpeechRecognizer {
         let model =
             Model::new(model_path).ok_or_else(|| anyhow!("Failed to load model: {model_path}"))?;
 
-        let recognizer = Recognizer::new(&model, 16000.0)
+        let mut recognizer = Recognizer::new(&model, 16000.0)
             .ok_or_else(|| anyhow!("Failed to create recognizer"))?;
+        // 输出逐词置信度, 用于过滤低置信度结果
+        recognizer.set_words(true);
+
+        Ok(Self {
+            recognizer,
+            min_confidence: 0.0,
+        })
+    }
 
-        Ok(Self { recognizer })
+    /// 设置最低平均置信度 (0.0 ~ 1.0), 0 表示不过滤
+    pub fn with_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence.clamp(0.0, 1.0);
+        self
     }
 
     /// 处理音频数据，返回识别到的文本
@@ -245,22 +886,40 @@ impl SpeechRecognizer {
     pub fn process(&mut self, audio_data: &[i16]) -> Option<String> {
         let state = self.recognizer.accept_waveform(audio_data).ok()?;
         if matches!(state, vosk::DecodingState::Finalized) {
-            let result = self.recognizer.final_result();
-            if let Some(single) = result.single() {
-                let text = single.text.trim().to_string();
-                if !text.is_empty() {
-                    return Some(text);
-                }
-            }
+            return self.finalize();
         }
         None
     }
 
+    /// 强制结束当前语段并返回最终结果, 没有内容时返回 None
+    pub fn flush(&mut self) -> Option<String> {
+        self.finalize()
+    }
+
+    /// 取出最终结果, 平均置信度低于阈值时丢弃
+    fn finalize(&mut self) -> Option<String> {
+        let min_confidence = self.min_confidence;
+        let single = self.recognizer.final_result().single()?;
+        let confidences: Vec<f32> = single.result.iter().map(|w| w.conf).collect();
+        filter_result(single.text, &confidences, min_confidence)
+    }
+
+    /// 获取当前的中间识别结果, 没有内容时返回 None
+    pub fn partial(&mut self) -> Option<String> {
+        let text = self.recognizer.partial_result().partial.trim().to_string();
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
     /// 检测是否包含唤醒词
     ///
     /// # Arguments
     ///
-    /// * `text`:
+    /// * `text`: 识别到的文本
+    /// * `wake_words`: 唤醒词列表 (包含常见误识别变体), 为空时始终返回 false
     ///
     /// returns: bool
     ///
@@ -269,21 +928,134 @@ impl SpeechRecognizer {
     /// ```
     ///
     /// ```
-    pub fn is_wake_word(text: &str) -> bool {
+    pub fn is_wake_word(text: &str, wake_words: &[String]) -> bool {
         let lower = text.to_lowercase();
 
-        if lower.contains("小波") {
-            return true;
+        wake_words
+            .iter()
+            .filter(|w| !w.is_empty())
+            .any(|w| lower.contains(&w.to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 生成一秒的正弦波
+    fn sine(rate: u32, freq: f64, amplitude: f64) -> Vec<i16> {
+        (0..rate)
+            .map(|i| {
+                let t = i as f64 / rate as f64;
+                (amplitude * (2.0 * std::f64::consts::PI * freq * t).sin()).round() as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resample_48k_sine_keeps_length_frequency_and_peak() {
+        let output = resample_to_16k(&sine(48000, 440.0, 10000.0), 48000);
+        assert_eq!(output.len(), 16000);
+
+        // 每个周期两次过零, 一秒 440Hz 应有约 880 次
+        let crossings = output
+            .windows(2)
+            .filter(|w| (w[0] < 0) != (w[1] < 0))
+            .count();
+        assert!((878..=882).contains(&crossings), "crossings: {crossings}");
+
+        let peak = output.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        assert!((9900..=10000).contains(&peak), "peak: {peak}");
+    }
+
+    #[test]
+    fn low_confidence_results_are_dropped() {
+        assert_eq!(filter_result("开心", &[0.3, 0.5], 0.6), None);
+        assert_eq!(
+            filter_result(" 开心 ", &[0.9, 0.7], 0.6),
+            Some("开心".to_string())
+        );
+        // 阈值为 0 时不过滤, 设置了阈值但没有逐词结果时丢弃
+        assert_eq!(filter_result("开心", &[0.1], 0.0), Some("开心".to_string()));
+        assert_eq!(filter_result("开心", &[], 0.0), Some("开心".to_string()));
+        assert_eq!(filter_result("开心", &[], 0.9), None);
+        assert_eq!(filter_result("  ", &[1.0], 0.0), None);
+    }
+
+    /// 把 16kHz 音频分块送入模型, 返回最终结果
+    fn recognize(samples: &[i16], min_confidence: f32) -> Option<String> {
+        let mut recognizer = SpeechRecognizer::new(DEFAULT_MODEL_PATH)
+            .expect("load model")
+            .with_confidence(min_confidence);
+        let mut result = None;
+        for chunk in samples.chunks(1600) {
+            result = result.or(recognizer.process(chunk));
         }
+        result.or_else(|| recognizer.flush())
+    }
 
-        // 常见误识别变体
-        let variants = ["晓波", "小博", "笑波", "晓博"];
-        for v in &variants {
-            if lower.contains(v) {
-                return true;
-            }
+    /// 需要模型文件, 运行: cargo test -- --ignored
+    ///
+    /// 设置 `ELE_BOT_TEST_WAV` 为 16kHz 单声道录音 (可按 Ctrl+W 录制) 时,
+    /// 额外检查该录音不设阈值时能识别出文本
+    #[test]
+    #[ignore]
+    fn model_drops_low_confidence_audio() {
+        // 固定种子的白噪声, 不应识别出可信的词
+        let mut seed = 1u32;
+        let noise: Vec<i16> = (0..16000 * 2)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                ((seed >> 16) as i16) / 4
+            })
+            .collect();
+        assert_eq!(recognize(&noise, 0.9), None);
+
+        if let Ok(path) = std::env::var("ELE_BOT_TEST_WAV") {
+            let samples: Vec<i16> = hound::WavReader::open(&path)
+                .expect("open wav")
+                .into_samples::<i16>()
+                .collect::<std::result::Result<_, _>>()
+                .expect("read wav");
+            let text = recognize(&samples, 0.0);
+            assert!(text.is_some(), "no text recognized from {path}");
         }
+    }
+
+    fn commands(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(phrase, action)| (phrase.to_string(), action.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn negated_phrase_does_not_match() {
+        let table = commands(&[("开心", "mood:happy")]);
+        assert_eq!(
+            parse_command("我很开心", &table),
+            Some(VoiceCommand::Mood("happy".to_string()))
+        );
+        assert_eq!(parse_command("我不开心", &table), None);
+        assert_eq!(parse_command("一点都没开心", &table), None);
+        // 否定只作用于紧跟的那一次出现
+        assert_eq!(
+            parse_command("不开心了又开心了", &table),
+            Some(VoiceCommand::Mood("happy".to_string()))
+        );
+    }
 
-        false
+    #[test]
+    fn longest_phrase_wins() {
+        let table = commands(&[("开心", "mood:happy"), ("不开心", "mood:tired")]);
+        assert_eq!(
+            parse_command("我不开心", &table),
+            Some(VoiceCommand::Mood("tired".to_string()))
+        );
+        let table = commands(&[("看前", "look:up"), ("看前面", "look:center")]);
+        assert_eq!(
+            parse_command("看前面", &table),
+            Some(VoiceCommand::Look("center".to_string()))
+        );
     }
 }
@@ -0,0 +1,57 @@
+//! 文字转语音
+//!
+//! 调用系统自带的语音合成程序, 通过默认输出设备播放:
+//! - Windows: PowerShell System.Speech
+//! - macOS: say
+//! - Linux: espeak-ng (需要安装, 中文使用 cmn 语音)
+
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// 异步朗读文本, 不阻塞调用线程
+pub fn speak(text: &str) {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return;
+    }
+
+    thread::spawn(move || {
+        let mut cmd = build_command(&text);
+        let result = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        match result {
+            Ok(status) if status.success() => log::debug!("TTS finished: {text}"),
+            Ok(status) => log::warn!("TTS exited with {status}"),
+            Err(e) => log::warn!("Failed to run TTS: {e}"),
+        }
+    });
+}
+
+#[cfg(target_os = "windows")]
+fn build_command(text: &str) -> Command {
+    let script = format!(
+        "Add-Type -AssemblyName System.Speech; \
+         (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+        text.replace('\'', "''")
+    );
+    let mut cmd = Command::new("powershell");
+    cmd.args(["-NoProfile", "-Command", &script]);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn build_command(text: &str) -> Command {
+    let mut cmd = Command::new("say");
+    cmd.arg(text);
+    cmd
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn build_command(text: &str) -> Command {
+    let mut cmd = Command::new("espeak-ng");
+    cmd.args(["-v", "cmn", text]);
+    cmd
+}